@@ -24,7 +24,6 @@ export const DisplayAsset = ({ asset, openFolder }: DisplayAssetProp) => {
   const openRenameFolderModal = () => {
     setIsRenameFolderModalOpen(true);
   };
-  const backend_url = import.meta.env.VITE_AVORED_BACKEND_BASE_URL;
   const { mutate: deleteFolderMutate } = useDeleteFolder();
 
   const onRemoveAssetOnClick = (
@@ -93,7 +92,7 @@ export const DisplayAsset = ({ asset, openFolder }: DisplayAssetProp) => {
           ) : (
             <>
               <img
-                src={`${backend_url}/${asset.path}`}
+                src={asset.url}
                 className="h-40"
                 alt={asset.name}
               />
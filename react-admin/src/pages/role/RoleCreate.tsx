@@ -2,6 +2,7 @@ import {Link} from "react-router-dom"
 import {Switch} from "@headlessui/react"
 import {useStoreRole} from "./hooks/useStoreRole";
 import {useTranslation} from "react-i18next";
+import _ from "lodash";
 import {Controller, useForm} from "react-hook-form";
 import {joiResolver} from "@hookform/resolvers/joi";
 import {useRoleCreateSchema} from "./schemas/role.create.schema";
@@ -10,10 +11,14 @@ import ErrorMessage from "../../components/ErrorMessage";
 import {CreatableRoleType} from "../../types/role/CreatableRoleType";
 import React from "react";
 import slug from "slug";
+import {useGetPermissions} from "./hooks/useGetPermissions";
+import {PermissionGroupType, PermissionOptionType} from "../../types/role/PermissionGroupType";
 
 function RoleCreate() {
     const {mutate, error} = useStoreRole()
     const [t] = useTranslation("global")
+    const permissionResult = useGetPermissions()
+    const permissionGroups: Array<PermissionGroupType> = _.get(permissionResult, "data.data.data", [])
 
     const {
         control,
@@ -49,9 +54,12 @@ function RoleCreate() {
         mutate(data)
     })
 
-    const renderSwitch = ((switchKey: string) => {
+    const renderSwitch = ((permission: PermissionOptionType) => {
+        const switchKey = permission.identifier
+
         return (
             <Controller
+                key={switchKey}
                 control={control}
                 name="permissions"
                 render={({field}) => {
@@ -59,15 +67,15 @@ function RoleCreate() {
                         <>
                             <div className="mb-4 flex items-center">
                                 <label
-                                    htmlFor="dashboard_permission"
+                                    htmlFor={switchKey}
                                     className="text-sm text-gray-600"
                                 >
-                                    {t(switchKey)}
+                                    {permission.label}
                                 </label>
                                 <Switch
-                                    checked={permissionAllowed('setting')}
+                                    checked={permissionAllowed(switchKey)}
                                     onChange={(e) => switchOnChange(switchKey)}
-                                    id="dashboard_permission"
+                                    id={switchKey}
                                     className={`${
                                         permissionAllowed(switchKey)
                                             ? "bg-primary-500"
@@ -126,85 +134,17 @@ function RoleCreate() {
                                 <ErrorMessage frontendErrors={errors} backendErrors={error} identifier="identifier" />
                             </div>
 
-                            <div className="mb-4 flex">
-                                <div className="border w-1/3 border-gray-200 rounded">
-                                    <div className="p-3 font-semibold border-b">
-                                        {t("generics")}
-                                    </div>
-                                    <div className="p-3">
-                                        {renderSwitch('dashboard')}
-                                        {renderSwitch('get_setting')}
-                                        {renderSwitch('save_setting')}
-                                    </div>
-                                </div>
-
-                                <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                    <div className="p-3 font-semibold border-b">
-                                        {t("page_permission")}
-                                    </div>
-                                    <div className="p-3">
-                                        {renderSwitch('page_table')}
-                                        {renderSwitch('page_create')}
-                                        {renderSwitch('page_edit')}
-                                        {renderSwitch('page_delete')}
-                                        {renderSwitch('get_page')}
-                                    </div>
-                                </div>
-
-                                <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                    <div className="p-3 font-semibold border-b">
-                                        {t("component_permission")}
-                                    </div>
-                                    <div className="p-3">
-                                        {renderSwitch('component_table')}
-                                        {renderSwitch('component_create')}
-                                        {renderSwitch('component_edit')}
-                                        {renderSwitch('component_delete')}
-                                        {renderSwitch('get_component')}
-                                    </div>
-                                </div>
-                            </div>
-
-                            <div className="mb-4 flex">
-                                <div className="border w-1/3 border-gray-200 rounded">
-                                    <div className="p-3 font-semibold border-b">
-                                        {t("asset_permission")}
-                                    </div>
-                                    <div className="p-3">
-                                        {renderSwitch('asset_table')}
-                                        {renderSwitch('asset_create')}
-                                        {renderSwitch('asset_edit')}
-                                        {renderSwitch('asset_delete')}
-                                    </div>
-                                </div>
-
-                                <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                    <div className="p-3 font-semibold border-b">
-                                        {t("admin_user_permission")}
-                                    </div>
-
-                                    <div className="p-3">
-                                        {renderSwitch('admin_user_table')}
-                                        {renderSwitch('admin_user_create')}
-                                        {renderSwitch('admin_user_edit')}
-                                        {renderSwitch('admin_user_delete')}
-                                        {renderSwitch('get_admin_user')}
-                                    </div>
-                                </div>
-
-                                <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                    <div className="p-3 font-semibold border-b">
-                                        {t("role_permission")}
-                                    </div>
-
-                                    <div className="p-3">
-                                        {renderSwitch('role_table')}
-                                        {renderSwitch('role_create')}
-                                        {renderSwitch('role_edit')}
-                                        {renderSwitch('role_delete')}
-                                        {renderSwitch('get_role')}
+                            <div className="mb-4 grid grid-cols-3 gap-3">
+                                {permissionGroups.map((permissionGroup) => (
+                                    <div key={permissionGroup.identifier} className="border border-gray-200 rounded">
+                                        <div className="p-3 font-semibold border-b">
+                                            {permissionGroup.label}
+                                        </div>
+                                        <div className="p-3">
+                                            {permissionGroup.permissions.map((permission) => renderSwitch(permission))}
+                                        </div>
                                     </div>
-                                </div>
+                                ))}
                             </div>
 
                             <div className="flex items-center">
@@ -1,6 +1,7 @@
 import {Link, useParams} from "react-router-dom"
 import {Switch} from "@headlessui/react"
 import {useTranslation} from "react-i18next";
+import _ from "lodash";
 import {Controller, useForm} from "react-hook-form";
 import {joiResolver} from "@hookform/resolvers/joi";
 import IEditableRole from "../../types/role/IEditableRole";
@@ -12,12 +13,16 @@ import {useState} from "react";
 import {PutRoleIdentifierType} from "../../types/role/PutRoleIdentifierType";
 import {useRolePutSchema} from "./schemas/role.put.schema";
 import {usePutRoleIdentifier} from "./hooks/usePutRoleIdentifier";
+import {useGetPermissions} from "./hooks/useGetPermissions";
+import {PermissionGroupType, PermissionOptionType} from "../../types/role/PermissionGroupType";
 
 function RoleEdit() {
     const params = useParams();
     const role_id = params.role_id ?? ''
     const { mutate } = useUpdateRole(role_id);
     const [t] = useTranslation("global")
+    const permissionResult = useGetPermissions()
+    const permissionGroups: Array<PermissionGroupType> = _.get(permissionResult, "data.data.data", [])
     const {data} = useGetRole(role_id)
     const [isEditableIdentifier, setIsEditableIdentifier] = useState<boolean>(true)
     const values = data?.data.role_model
@@ -83,9 +88,12 @@ function RoleEdit() {
         mutate(data)
     })
 
-    const renderSwitch = ((switchKey: string) => {
+    const renderSwitch = ((permission: PermissionOptionType) => {
+        const switchKey = permission.identifier
+
         return (
             <Controller
+                key={switchKey}
                 control={control}
                 name="permissions"
                 render={({field}) => {
@@ -96,12 +104,12 @@ function RoleEdit() {
                                     htmlFor={switchKey}
                                     className="text-sm text-gray-600"
                                 >
-                                    {t(switchKey)}
+                                    {permission.label}
                                 </label>
                                 <Switch
-                                    checked={permissionAllowed('setting')}
+                                    checked={permissionAllowed(switchKey)}
                                     onChange={(e) => switchOnChange(switchKey)}
-                                    id="dashboard_permission"
+                                    id={switchKey}
                                     className={`${
                                         permissionAllowed(switchKey)
                                             ? "bg-primary-500"
@@ -176,93 +184,19 @@ function RoleEdit() {
                                     )}
                                 </div>
                             </div>
-                            <div className="block">
-                                <div className="mb-4 flex">
-                                    <div className="border w-1/3 border-gray-200 rounded">
-                                        <div className="p-3 font-semibold border-b">
-                                            {t("generics")}
-                                        </div>
-                                        <div className="p-3">
-                                            {renderSwitch('dashboard')}
-
-                                            {renderSwitch('get_setting')}
-                                            {renderSwitch('save_setting')}
-                                        </div>
-                                    </div>
-
-                                    <div className="border w-1/3 ml-3 border-gray-200 rounded">
+                            <div className="mb-4 grid grid-cols-3 gap-3">
+                                {permissionGroups.map((permissionGroup) => (
+                                    <div key={permissionGroup.identifier} className="border border-gray-200 rounded">
                                         <div className="p-3 font-semibold border-b">
-                                            {t("page_permission")}
+                                            {permissionGroup.label}
                                         </div>
                                         <div className="p-3">
-                                            {renderSwitch('page_table')}
-                                            {renderSwitch('page_create')}
-                                            {renderSwitch('page_edit')}
-                                            {renderSwitch('page_delete')}
-                                            {renderSwitch('get_page')}
+                                            {permissionGroup.permissions.map((permission) => renderSwitch(permission))}
                                         </div>
                                     </div>
-
-                                    <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                        <div className="p-3 font-semibold border-b">
-                                            {t("component_permission")}
-                                        </div>
-                                        <div className="p-3">
-                                            {renderSwitch('component_table')}
-                                            {renderSwitch('component_create')}
-                                            {renderSwitch('component_edit')}
-                                            {renderSwitch('component_delete')}
-                                            {renderSwitch('get_component')}
-                                        </div>
-                                    </div>
-                                </div>
-
-                                <div className="mb-4 flex">
-                                    <div className="border w-1/3 border-gray-200 rounded">
-                                        <div className="p-3 font-semibold border-b">
-                                            {t("asset_permission")}
-                                        </div>
-                                        <div className="p-3">
-                                            {renderSwitch('asset_table')}
-                                            {renderSwitch('asset_create')}
-                                            {renderSwitch('rename_asset')}
-                                            {renderSwitch('asset_delete')}
-                                            {renderSwitch('create_folder')}
-                                            {renderSwitch('delete_folder')}
-                                        </div>
-                                    </div>
-
-                                    <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                        <div className="p-3 font-semibold border-b">
-                                            {t("admin_user_permission")}
-                                        </div>
-
-                                        <div className="p-3">
-                                            {renderSwitch('admin_user_table')}
-                                            {renderSwitch('admin_user_create')}
-                                            {renderSwitch('admin_user_edit')}
-                                            {renderSwitch('admin_user_delete')}
-                                            {renderSwitch('get_admin_user')}
-                                        </div>
-                                    </div>
-
-                                    <div className="border w-1/3 ml-3 border-gray-200 rounded">
-                                        <div className="p-3 font-semibold border-b">
-                                            {t("role_permission")}
-                                        </div>
-
-                                        <div className="p-3">
-                                            {renderSwitch('role_table')}
-                                            {renderSwitch('role_create')}
-                                            {renderSwitch('role_edit')}
-                                            {renderSwitch('role_delete')}
-                                            {renderSwitch('get_role')}
-                                        </div>
-                                    </div>
-                                </div>
+                                ))}
                             </div>
 
-
                             <div className="flex items-center">
                                 <button
                                     type="submit"
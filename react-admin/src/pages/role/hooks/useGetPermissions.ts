@@ -0,0 +1,23 @@
+import {useQuery} from '@tanstack/react-query'
+import { useAxios } from '../../../hooks/useAxios'
+import _ from 'lodash'
+import {useNavigate} from 'react-router-dom'
+
+export const useGetPermissions = () => {
+    const client = useAxios()
+    const redirect = useNavigate()
+
+    return useQuery({
+        queryKey: ['permissions'],
+        queryFn: (async () => {
+            try {
+                return await client.get("/permissions")
+            } catch (error) {
+                if (_.get(error, 'response.status') === 401) {
+                    localStorage.removeItem('AUTH_TOKEN')
+                    redirect("/admin/login")
+                }
+            }
+        })
+    })
+}
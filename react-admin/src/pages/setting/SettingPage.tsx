@@ -8,8 +8,6 @@ import { SettingSaveSchema } from "./schemas/setting.save.schema";
 import { joiResolver } from "@hookform/resolvers/joi";
 import { useForm } from "react-hook-form";
 import SaveSettingType from "../../types/settings/SaveSettingType";
-import AvoRedButton, {ButtonType} from "../../components/AvoRedButton";
-import {randomString} from "../../lib/common";
 
 function SettingPage() {
     const setting_api_all_response = useSetting()
@@ -21,7 +19,6 @@ function SettingPage() {
     const {
         register,
         handleSubmit,
-        setValue,
         formState: { errors }
     } = useForm<SaveSettingType>({
         resolver: joiResolver(SettingSaveSchema, { allowUnknown: true }),
@@ -35,12 +32,6 @@ function SettingPage() {
         mutate(data)
     })
 
-    const generateTokenOnClick = ((e: React.MouseEvent<HTMLButtonElement>) => {
-        e.preventDefault()
-        e.stopPropagation()
-        setValue(`settings.${getIdentifierIndex('auth_cms_token')}.value`, randomString(48))
-    })
-
     return (
         <div className="flex-1 bg-white">
             <div className="pl-64">
@@ -74,28 +65,7 @@ function SettingPage() {
                                             autoFocus
                                         />
                                     </div>
-
-                                    <div className="flex items-end mb-5">
-
-                                        <div className="flex-1">
-                                            <InputField
-                                                label={t("cms_frontend_auth_token")}
-                                                register={register(`settings.${getIdentifierIndex('auth_cms_token')}.value`)}
-                                                autoFocus
-                                            />
-                                        </div>
-                                        <div className="ml-3 mr-auto">
-                                            <AvoRedButton
-                                                className="bg-primary-500"
-                                                onClick={generateTokenOnClick}
-                                                type={ButtonType.button}
-                                                label={t("generate_token")}
-                                            />
-                                        </div>
-
-
-                                        </div>
-                                    </div>
+                                </div>
 
                                     <div className="flex">
 
@@ -6,6 +6,7 @@ export default interface IAdminUserModel {
     email: string;
     password: string;
     is_super_admin: boolean;
+    is_active: boolean;
     profile_image: string;
     roles: Array<IRoleModel>;
     created_at: string;
@@ -7,5 +7,8 @@ export default interface IRoleModel {
     updated_at: string;
     updated_by: string;
     permissions: Array<string>;
+    own_pages_only: boolean;
+    page_identifier_prefixes: Array<string>;
+    asset_folder_ids: Array<string>;
     action: string;
 }
\ No newline at end of file
@@ -2,8 +2,17 @@ type IAssetModel = {
     id: string;
     name: string;
     path: string;
+    url: string;
     asset_type: string;
     metadata: string;
+    original_name: string;
+    file_size: number;
+    width: number;
+    height: number;
+    checksum: string;
+    alt_text: string;
+    caption: string;
+    tags: Array<string>;
     created_at: string;
     created_by: string;
     updated_at: string;
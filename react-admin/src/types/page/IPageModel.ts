@@ -6,6 +6,7 @@ export default interface IPageModel {
     created_by: string;
     updated_at: string;
     updated_by: string;
+    locked_role_ids: Array<string>;
     action: string;
 }
 
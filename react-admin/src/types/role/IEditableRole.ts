@@ -2,4 +2,7 @@ export default  interface IEditableRole {
     id: string;
     name: string;
     permissions: Array<string>;
+    own_pages_only?: boolean;
+    page_identifier_prefixes?: Array<string>;
+    asset_folder_ids?: Array<string>;
 }
\ No newline at end of file
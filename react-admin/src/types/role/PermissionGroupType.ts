@@ -0,0 +1,10 @@
+export type PermissionOptionType = {
+    identifier: string;
+    label: string;
+}
+
+export type PermissionGroupType = {
+    identifier: string;
+    label: string;
+    permissions: Array<PermissionOptionType>;
+}
@@ -1,10 +1,14 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
-use axum::extract::State;
+use axum::extract::{ConnectInfo, State};
+use axum::http::HeaderMap;
 use axum::Json;
 use serde::Serialize;
+use tracing::log::error;
 use crate::api::handlers::admin_user::request::admin_user_forgot_password_request::AdminUserForgotPasswordRequest;
 use crate::avored_state::AvoRedState;
 use crate::error::{Error, Result};
+use crate::models::login_attempt_model::LoginAttemptAction;
 use crate::models::validation_error::ErrorResponse;
 use crate::responses::ApiResponse;
 
@@ -15,6 +19,8 @@ pub struct ForgotPasswordViewModel {
 
 pub async fn admin_user_forgot_password_api_handler (
     state: State<Arc<AvoRedState>>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     Json(payload): Json<AdminUserForgotPasswordRequest>,
 ) -> Result<Json<ApiResponse<bool>>> {
     println!("->> {:<12} - admin_user_forgot_password_api_handler", "HANDLER");
@@ -30,16 +36,49 @@ pub async fn admin_user_forgot_password_api_handler (
         return Err(Error::BadRequest(error_response));
     }
 
-    let template = &state.template;
-    let react_admin_url = &state.config.react_admin_app_url;
-    let sent_status = state
+    let config = &state.config;
+    let throttle = &state.login_throttle_service;
+    let client_ip = throttle.client_ip(&headers, connect_info, config.trust_proxy_headers);
+
+    let throttle_checks = [
+        (client_ip.as_str(), config.forgot_password_max_attempts_per_ip),
+        (payload.email.as_str(), config.forgot_password_max_attempts_per_email),
+    ];
+    for (key, max_attempts) in throttle_checks {
+        if let Some(retry_after) = throttle
+            .too_many_attempts(
+                &state.db,
+                LoginAttemptAction::ForgotPassword,
+                key,
+                max_attempts,
+                config.forgot_password_attempt_window_seconds
+            )
+            .await? {
+            return Err(Error::TooManyRequests(retry_after));
+        }
+    }
+
+    // every request counts here, not only the failed ones, as each of them sends an email
+    throttle.hit(&state.db, LoginAttemptAction::ForgotPassword, &client_ip, config.forgot_password_attempt_window_seconds).await?;
+    throttle.hit(&state.db, LoginAttemptAction::ForgotPassword, &payload.email, config.forgot_password_attempt_window_seconds).await?;
+
+    if let Err(e) = state
         .admin_user_service
-        .sent_forgot_password_email(&state.db, template, react_admin_url, payload.email)
-        .await?;
+        .sent_forgot_password_email(
+            &state.db,
+            &state.template,
+            &config.react_admin_app_url,
+            payload.email,
+            config.password_reset_expire_in_minutes
+        )
+        .await {
+        error!("there is an issue while sending the forgot password email: {e:?}");
+    }
 
+    // the same response for every email, so it does not reveal which admin users exist
     let response_data = ApiResponse {
         status: true,
-        data: sent_status,
+        data: true,
     };
 
     Ok(Json(response_data))
@@ -1,17 +1,18 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
-use axum::extract::State;
-use axum::http::{header, Response};
+use axum::extract::{ConnectInfo, State};
+use axum::http::{header, HeaderMap, Response};
 use axum::Json;
 use axum_extra::extract::cookie::{Cookie, SameSite};
-use jsonwebtoken::{encode, EncodingKey, Header};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use tracing::log::error;
 use utoipa::ToSchema;
 use crate::api::handlers::admin_user::request::authenticate_admin_user_request::AuthenticateAdminUserRequest;
 use crate::avored_state::AvoRedState;
 use crate::error::{Error, Result};
 use crate::models::admin_user_model::AdminUserModel;
-use crate::models::token_claim_model::TokenClaims;
+use crate::models::login_attempt_model::LoginAttemptAction;
 use crate::models::validation_error::ErrorResponse;
 
 
@@ -22,12 +23,15 @@ use crate::models::validation_error::ErrorResponse;
     post,
     path = "/api/login",
     responses(
-        (status = 200, description = "JSON file", body = LoginResponseData)
+        (status = 200, description = "JSON file", body = LoginResponseData),
+        (status = 429, description = "Too many failed login attempts, see the Retry-After header")
     ),
     request_body = AuthenticateAdminUserRequest,
 )]
 pub async fn admin_user_login_api_handler(
     state: State<Arc<AvoRedState>>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     Json(payload): Json<AuthenticateAdminUserRequest>,
 ) -> Result<Json<LoginResponseData>> {
     println!("->> {:<12} - admin_user_login_api_handler", "HANDLER");
@@ -43,10 +47,38 @@ pub async fn admin_user_login_api_handler(
         return Err(Error::BadRequest(error_response));
     }
 
-    let admin_user_model = state
+    let config = &state.config;
+    let throttle = &state.login_throttle_service;
+    let client_ip = throttle.client_ip(&headers, connect_info, config.trust_proxy_headers);
+
+    if let Some(retry_after) = throttle.find_active_lockout(&state.db, &payload.email).await? {
+        return Err(Error::TooManyRequests(retry_after));
+    }
+
+    let throttle_checks = [
+        (client_ip.as_str(), config.login_max_attempts_per_ip),
+        (payload.email.as_str(), config.login_max_attempts_per_email),
+    ];
+    for (key, max_attempts) in throttle_checks {
+        if let Some(retry_after) = throttle
+            .too_many_attempts(&state.db, LoginAttemptAction::Login, key, max_attempts, config.login_attempt_window_seconds)
+            .await? {
+            return Err(Error::TooManyRequests(retry_after));
+        }
+    }
+
+    let admin_user_model = match state
         .admin_user_service
         .find_by_email(&state.db, payload.email.to_owned())
-        .await?;
+        .await {
+        Ok(admin_user_model) => admin_user_model,
+        Err(e) => {
+            throttle.hit(&state.db, LoginAttemptAction::Login, &client_ip, config.login_attempt_window_seconds).await?;
+            throttle.hit(&state.db, LoginAttemptAction::Login, &payload.email, config.login_attempt_window_seconds).await?;
+
+            return Err(e);
+        }
+    };
 
     let is_password_match: bool = state
         .admin_user_service
@@ -56,25 +88,71 @@ pub async fn admin_user_login_api_handler(
         )?;
 
     if !is_password_match {
+        throttle.hit(&state.db, LoginAttemptAction::Login, &client_ip, config.login_attempt_window_seconds).await?;
+        throttle.hit(&state.db, LoginAttemptAction::Login, &payload.email, config.login_attempt_window_seconds).await?;
+
+        let failed_attempts = throttle
+            .too_many_attempts(
+                &state.db,
+                LoginAttemptAction::Login,
+                &payload.email,
+                config.login_max_attempts_per_email,
+                config.login_attempt_window_seconds
+            )
+            .await?;
+
+        if failed_attempts.is_some() {
+            // the lockout takes over from the per email counter from here on
+            throttle.clear(&state.db, LoginAttemptAction::Login, &payload.email).await?;
+
+            if let Err(e) = throttle
+                .lock_account(
+                    &state.db,
+                    &state.template,
+                    &config.react_admin_app_url,
+                    admin_user_model.email.clone(),
+                    config.login_lockout_seconds
+                )
+                .await {
+                error!("there is an issue while sending the unlock account email: {e:?}");
+            }
+
+            return Err(Error::TooManyRequests(config.login_lockout_seconds));
+        }
+
         return Err(Error::Authentication);
     }
 
-    let now = chrono::Utc::now();
-    let iat = now.timestamp() as usize;
-    let exp = (now + chrono::Duration::minutes(60)).timestamp() as usize;
-    let claims: TokenClaims = TokenClaims {
-        sub: admin_user_model.clone().id,
-        name: admin_user_model.clone().full_name,
-        email:admin_user_model.clone().email,
-        admin_user_model: admin_user_model.clone(),
-        exp,
-        iat,
-    };
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(state.config.jwt_secret_key.as_ref()),
-    )?;
+    throttle.clear(&state.db, LoginAttemptAction::Login, &payload.email).await?;
+
+    if !admin_user_model.is_active {
+        return Err(Error::AccountDeactivated);
+    }
+
+    if state.password_service.needs_rehash(&admin_user_model.password) {
+        let password_hash = state
+            .password_service
+            .hash_password(&payload.password)?;
+        state
+            .admin_user_service
+            .update_password_by_email(&state.db, password_hash, admin_user_model.email.clone())
+            .await?;
+    }
+
+    if let Some(two_factor_challenge) = two_factor_challenge(&state, &admin_user_model).await? {
+        let response_data = LoginResponseData {
+            status: true,
+            data: String::from(""),
+            admin_user: AdminUserModel::default(),
+            two_factor_challenge: Some(two_factor_challenge)
+        };
+
+        return Ok(Json(response_data));
+    }
+
+    let token = state
+        .admin_user_service
+        .create_login_token(admin_user_model.clone(), &state.config.jwt_secret_key)?;
     let cookie = Cookie::build("token")
         .path("/")
         // .max_age(Duration::h)
@@ -88,26 +166,65 @@ pub async fn admin_user_login_api_handler(
     let response_data = LoginResponseData {
         status: true,
         data: token,
-        admin_user: admin_user_model
+        admin_user: admin_user_model,
+        two_factor_challenge: None
     };
 
     Ok(Json(response_data))
 }
 
+/// Returns the challenge the admin user has to pass before getting an auth token, or
+/// none when two factor is neither enabled for the admin user nor enforced.
+pub async fn two_factor_challenge(
+    state: &AvoRedState,
+    admin_user_model: &AdminUserModel
+) -> Result<Option<TwoFactorChallengeResponse>> {
+    let enforce_two_factor_setting = state
+        .setting_service
+        .find_by_identifier(&state.db, String::from("auth_enforce_two_factor"))
+        .await
+        .unwrap_or_default();
+    let is_two_factor_enforced = enforce_two_factor_setting.value.eq("true");
+
+    if !admin_user_model.is_two_factor_enabled && !is_two_factor_enforced {
+        return Ok(None);
+    }
+
+    let challenge_token = state
+        .admin_user_service
+        .create_two_factor_challenge_token(admin_user_model.id.clone(), &state.config.jwt_secret_key)?;
+
+    Ok(Some(TwoFactorChallengeResponse {
+        challenge_token,
+        enrolment_required: !admin_user_model.is_two_factor_enabled
+    }))
+}
+
 #[derive(Serialize, ToSchema, Deserialize, Debug)]
 pub struct LoginResponseData {
     pub status: bool,
     pub data: String,
-    pub admin_user: AdminUserModel
+    pub admin_user: AdminUserModel,
+    pub two_factor_challenge: Option<TwoFactorChallengeResponse>
+}
+
+/// Returned instead of the auth token when the admin user still has to pass
+/// (or, if two factor is enforced, first enrol) the two factor check.
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct TwoFactorChallengeResponse {
+    pub challenge_token: String,
+    pub enrolment_required: bool
 }
 
 
 #[cfg(test)]
 mod tests {
+    use argon2::{Argon2, PasswordHasher};
+    use argon2::password_hash::SaltString;
     use axum::body::Body;
-    use axum::http::StatusCode;
+    use axum::http::{header, StatusCode};
     use tower::ServiceExt;
-    use crate::api::rest_api_routes::tests::{ get_axum_app, send_post_request};
+    use crate::api::rest_api_routes::tests::{ get_axum_app, send_post_request, setup_avored_db};
     use crate::error::Result;
 
 
@@ -121,7 +238,7 @@ mod tests {
         let payload = Body::from(
             r#"{
                     "email": "admin@admin.com",
-                    "password": "admin123"
+                    "password": "Tr0ub4dor&Horse"
                 }"#,
         );
 
@@ -135,4 +252,96 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_admin_user_login_api_handler_locks_account() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await.unwrap();
+        setup_avored_db(app.clone()).await;
+
+        let wrong_payload = r#"{
+                    "email": "admin@admin.com",
+                    "password": "wrong-password"
+                }"#;
+
+        for _ in 0..4 {
+            let response = app.clone()
+                .oneshot(send_post_request("/api/login", Body::from(wrong_payload)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/login", Body::from(wrong_payload)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "900");
+
+        // the correct password is rejected as well while the account is locked
+        let payload = Body::from(
+            r#"{
+                    "email": "admin@admin.com",
+                    "password": "Tr0ub4dor&Horse"
+                }"#,
+        );
+        let response = app
+            .oneshot(send_post_request("/api/login", payload))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_user_login_api_handler_lockout_keeps_legacy_password() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await.unwrap();
+        setup_avored_db(app.clone()).await;
+
+        let legacy_salt = SaltString::from_b64(state.config.password_salt.as_ref().unwrap())?;
+        let legacy_hash = Argon2::default()
+            .hash_password(b"Tr0ub4dor&Horse", &legacy_salt)?
+            .to_string();
+        state.admin_user_service
+            .update_password_by_email(&state.db, legacy_hash, String::from("admin@admin.com"))
+            .await?;
+
+        let wrong_payload = r#"{
+                    "email": "admin@admin.com",
+                    "password": "wrong-password"
+                }"#;
+        for _ in 0..5 {
+            app.clone()
+                .oneshot(send_post_request("/api/login", Body::from(wrong_payload)))
+                .await
+                .unwrap();
+        }
+
+        // ends the lockout, as if it had expired
+        let (datastore, database_session) = &state.db;
+        datastore.execute("DELETE admin_user_lockouts; DELETE login_attempts;", database_session, None).await?;
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/login", Body::from(wrong_payload)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let payload = Body::from(
+            r#"{
+                    "email": "admin@admin.com",
+                    "password": "Tr0ub4dor&Horse"
+                }"#,
+        );
+        let response = app
+            .oneshot(send_post_request("/api/login", payload))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
 }
@@ -30,31 +30,20 @@ pub async fn admin_user_reset_password_api_handler(
     }
 
     let password_hash = state
-        .admin_user_service
-        .get_password_hash_from_raw_password(payload.password, &state.config.password_salt)?;
+        .password_service
+        .hash_password(&payload.password)?;
 
+    // expires the used link together with any other pending one
     let update_password_status = state
         .admin_user_service
-        .update_password_by_email(&state.db, password_hash, payload.email.clone())
+        .update_password_by_email(&state.db, password_hash, payload.email)
         .await?;
 
-    let mut response_data = ApiResponse {
-        status: false,
-        data: false
+    let response_data = ApiResponse {
+        status: update_password_status,
+        data: update_password_status
     };
 
-    if update_password_status {
-        let expired_status = state
-            .admin_user_service
-            .expire_password_token_by_email_and_token(&state.db, payload.email, payload.token)
-            .await?;
-
-        response_data = ApiResponse {
-            status: true,
-            data: expired_status
-        };
-    }
-
     Ok(Json(response_data))
 }
 
@@ -63,3 +52,81 @@ pub async fn admin_user_reset_password_api_handler(
 pub struct ResponseData {
     status: bool
 }
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use sha2::{Digest, Sha256};
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, send_post_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_admin_user_reset_password_api_handler() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+
+        // unknown emails get the same answer as existing ones
+        let response = app.clone()
+            .oneshot(send_post_request("/api/forgot-password", Body::from(r#"{"email": "nobody@avored.com"}"#)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let unknown_body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response = app.clone()
+            .oneshot(send_post_request("/api/forgot-password", Body::from(r#"{"email": "admin@admin.com"}"#)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let known_body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(unknown_body, known_body);
+
+        let (datastore, database_session) = &state.db;
+        let sql = format!("
+            CREATE password_reset CONTENT {{
+                email: 'admin@admin.com',
+                token_hash: '{}',
+                status: 'Active',
+                expires_at: time::now() - 1m,
+                created_at: time::now()
+            }};
+            CREATE password_reset CONTENT {{
+                email: 'admin@admin.com',
+                token_hash: '{}',
+                status: 'Active',
+                expires_at: time::now() + 1h,
+                created_at: time::now()
+            }};",
+            hex::encode(Sha256::digest(b"expiredtoken")),
+            hex::encode(Sha256::digest(b"validtoken"))
+        );
+        datastore.execute(&sql, database_session, None).await?;
+
+        let reset_payload = |token: &str| Body::from(format!(
+            r#"{{"email": "admin@admin.com", "password": "Fresh123Secret", "confirm_password": "Fresh123Secret", "token": "{token}"}}"#
+        ));
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/reset-password", reset_payload("expiredtoken")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/reset-password", reset_payload("validtoken")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(send_post_request("/api/reset-password", reset_payload("validtoken")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
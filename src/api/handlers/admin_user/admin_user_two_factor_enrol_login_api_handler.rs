@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use rust_i18n::t;
+use crate::api::handlers::admin_user::request::two_factor_challenge_request::TwoFactorChallengeRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_two_factor_model::TwoFactorEnrolmentModel;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+/// Enrol Admin User Two Factor On Login
+///
+/// Used when the auth_enforce_two_factor setting is on and the admin user has not
+/// enrolled yet. The code is then confirmed through /api/login/two-factor.
+#[utoipa::path(
+    post,
+    path = "/api/login/two-factor/enrol",
+    responses(
+        (status = 200, description = "JSON file", body = TwoFactorEnrolmentResponse)
+    ),
+    request_body = TwoFactorChallengeRequest,
+)]
+pub async fn admin_user_two_factor_enrol_login_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<TwoFactorChallengeRequest>,
+) -> Result<Json<ApiResponse<TwoFactorEnrolmentModel>>> {
+    println!("->> {:<12} - admin_user_two_factor_enrol_login_api_handler", "HANDLER");
+
+    let mut error_messages = payload.validate()?;
+    let mut admin_user_id = String::from("");
+
+    if error_messages.is_empty() {
+        match state
+            .admin_user_service
+            .decode_two_factor_challenge_token(&payload.challenge_token, &state.config.jwt_secret_key) {
+            Ok(decoded_admin_user_id) => admin_user_id = decoded_admin_user_id,
+            Err(_) => error_messages.push(ErrorMessage {
+                key: String::from("challenge_token"),
+                message: t!("two_factor_challenge_expire").to_string()
+            })
+        }
+    }
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, admin_user_id)
+        .await?;
+
+    if admin_user_model.is_two_factor_enabled {
+        let error_messages = vec![ErrorMessage {
+            key: String::from("code"),
+            message: t!("two_factor_already_enabled").to_string()
+        }];
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let two_factor_enrolment_model = state
+        .admin_user_service
+        .generate_two_factor_secret(&state.db, &admin_user_model)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: two_factor_enrolment_model
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,251 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::api::handlers::admin_user::request::two_factor_login_request::TwoFactorLoginRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_model::AdminUserModel;
+use crate::models::admin_user_two_factor_model::TwoFactorStatus;
+use crate::models::login_attempt_model::LoginAttemptAction;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+
+
+/// Login Admin User Two Factor
+///
+/// Exchange the two factor challenge token and a totp (or recovery) code for an auth token
+#[utoipa::path(
+    post,
+    path = "/api/login/two-factor",
+    responses(
+        (status = 200, description = "JSON file", body = TwoFactorLoginResponseData)
+    ),
+    request_body = TwoFactorLoginRequest,
+)]
+pub async fn admin_user_two_factor_login_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<TwoFactorLoginRequest>,
+) -> Result<Json<TwoFactorLoginResponseData>> {
+    println!("->> {:<12} - admin_user_two_factor_login_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let admin_user_id = match state
+        .admin_user_service
+        .decode_two_factor_challenge_token(&payload.challenge_token, &state.config.jwt_secret_key) {
+        Ok(admin_user_id) => admin_user_id,
+        Err(_) => return Err(two_factor_error("challenge_token", t!("two_factor_challenge_expire").to_string()))
+    };
+
+    let config = &state.config;
+    let throttle = &state.login_throttle_service;
+
+    if let Some(retry_after) = throttle
+        .too_many_attempts(
+            &state.db,
+            LoginAttemptAction::TwoFactor,
+            &admin_user_id,
+            config.login_max_attempts_per_email,
+            config.login_attempt_window_seconds
+        )
+        .await? {
+        return Err(Error::TooManyRequests(retry_after));
+    }
+
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, admin_user_id.clone())
+        .await?;
+
+    let two_factor_model = match state
+        .admin_user_service
+        .find_two_factor_by_admin_user_id(&state.db, &admin_user_id)
+        .await {
+        Ok(two_factor_model) => two_factor_model,
+        Err(_) => return Err(two_factor_error("code", t!("two_factor_not_enabled").to_string()))
+    };
+
+    let mut recovery_codes: Vec<String> = vec![];
+
+    if two_factor_model.status == TwoFactorStatus::Enabled {
+        let is_code_valid = state
+            .admin_user_service
+            .verify_two_factor_login(&state.db, &two_factor_model, &admin_user_model.email, &payload.code)
+            .await?;
+
+        if !is_code_valid {
+            throttle.hit(&state.db, LoginAttemptAction::TwoFactor, &admin_user_id, config.login_attempt_window_seconds).await?;
+
+            return Err(two_factor_error("code", t!("two_factor_code_invalid").to_string()));
+        }
+    } else {
+        // the admin user is finishing an enrolment that was required by the
+        // auth_enforce_two_factor setting, so the first code confirms the secret.
+        let is_code_valid = state
+            .admin_user_service
+            .check_two_factor_code(&state.db, &two_factor_model, &admin_user_model.email, &payload.code)
+            .await?;
+
+        if !is_code_valid {
+            throttle.hit(&state.db, LoginAttemptAction::TwoFactor, &admin_user_id, config.login_attempt_window_seconds).await?;
+
+            return Err(two_factor_error("code", t!("two_factor_code_invalid").to_string()));
+        }
+
+        recovery_codes = state
+            .admin_user_service
+            .enable_two_factor(&state.db, &admin_user_id)
+            .await?;
+    }
+
+    throttle.clear(&state.db, LoginAttemptAction::TwoFactor, &admin_user_id).await?;
+
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, admin_user_id)
+        .await?;
+    let token = state
+        .admin_user_service
+        .create_login_token(admin_user_model.clone(), &state.config.jwt_secret_key)?;
+
+    let response_data = TwoFactorLoginResponseData {
+        status: true,
+        data: token,
+        admin_user: admin_user_model,
+        recovery_codes
+    };
+
+    Ok(Json(response_data))
+}
+
+fn two_factor_error(key: &str, message: String) -> Error {
+    let error_response = ErrorResponse {
+        status: false,
+        errors: vec![ErrorMessage {
+            key: String::from(key),
+            message
+        }]
+    };
+
+    Error::BadRequest(error_response)
+}
+
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct TwoFactorLoginResponseData {
+    pub status: bool,
+    pub data: String,
+    pub admin_user: AdminUserModel,
+    pub recovery_codes: Vec<String>
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+    use totp_rs::{Algorithm, Secret, TOTP};
+    use tower::ServiceExt;
+    use crate::api::handlers::admin_user::admin_user_login_api_handler::LoginResponseData;
+    use crate::api::handlers::admin_user::admin_user_two_factor_login_api_handler::TwoFactorLoginResponseData;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_authenticated_post_request, send_post_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn response_json(response: axum::response::Response) -> Value {
+        let res_b = response.into_body();
+        let body = axum::body::to_bytes(res_b, usize::MAX).await.unwrap();
+
+        serde_json::from_slice(&body).expect("Failed to parse JSON")
+    }
+
+    #[tokio::test]
+    async fn test_admin_user_two_factor_login_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/two-factor/enrol", Body::empty(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let enrolment = response_json(response).await;
+        let secret = enrolment["data"]["secret"].as_str().unwrap().to_string();
+        assert!(enrolment["data"]["otpauth_uri"].as_str().unwrap().starts_with("otpauth://totp/"));
+
+        let totp = TOTP::new(
+            Algorithm::SHA1,
+            6,
+            1,
+            30,
+            Secret::Encoded(secret).to_bytes().unwrap(),
+            Some(String::from("AvoRed")),
+            String::from("admin@admin.com"),
+        ).unwrap();
+
+        let payload = Body::from(json!({"code": "000000x"}).to_string());
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/two-factor/confirm", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let confirm_code = totp.generate_current().unwrap();
+        let payload = Body::from(json!({"code": confirm_code}).to_string());
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/two-factor/confirm", payload, token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let recovery_codes = response_json(response).await["data"].clone();
+        assert_eq!(recovery_codes.as_array().unwrap().len(), 10);
+
+        // the password alone now only returns a challenge
+        let payload = Body::from(json!({"email": "admin@admin.com", "password": "Tr0ub4dor&Horse"}).to_string());
+        let response = app.clone().oneshot(send_post_request("/api/login", payload)).await.unwrap();
+        let login_response: LoginResponseData = serde_json::from_value(response_json(response).await)?;
+        assert!(login_response.data.is_empty());
+        let challenge = login_response.two_factor_challenge.expect("two factor challenge expected");
+        assert!(!challenge.enrolment_required);
+
+        let payload = Body::from(json!({
+            "challenge_token": challenge.challenge_token,
+            "code": recovery_codes[0]
+        }).to_string());
+        let response = app.clone().oneshot(send_post_request("/api/login/two-factor", payload)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let two_factor_response: TwoFactorLoginResponseData = serde_json::from_value(response_json(response).await)?;
+        assert!(!two_factor_response.data.is_empty());
+        assert!(two_factor_response.admin_user.is_two_factor_enabled);
+
+        // a recovery code can only be used once
+        let payload = Body::from(json!({
+            "challenge_token": challenge.challenge_token,
+            "code": recovery_codes[0]
+        }).to_string());
+        let response = app.clone().oneshot(send_post_request("/api/login/two-factor", payload)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // the totp code that confirmed the enrolment cannot be replayed inside its time step
+        let payload = Body::from(json!({
+            "challenge_token": challenge.challenge_token,
+            "code": confirm_code
+        }).to_string());
+        let response = app.oneshot(send_post_request("/api/login/two-factor", payload)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,4 @@
 use std::sync::Arc;
-use argon2::{Argon2, PasswordHasher};
-use argon2::password_hash::SaltString;
 use axum::extract::{ State};
 use axum::{Extension, Json};
 use rust_i18n::t;
@@ -19,6 +17,11 @@ pub async fn change_password_api_handler (
     println!("->> {:<12} - change_password_api_handler", "HANDLER");
 
     let mut error_messages = payload.validate()?;
+    error_messages.append(&mut state.password_service.validate_password(
+        &payload.password,
+        &logged_in_user.admin_user_model.email,
+        &logged_in_user.admin_user_model.full_name
+    ));
 
     let is_password_match: bool = state
         .admin_user_service
@@ -44,14 +47,9 @@ pub async fn change_password_api_handler (
         return Err(Error::BadRequest(error_response));
     }
 
-    let password = payload.password.as_bytes();
-    let salt = SaltString::from_b64(&state.config.password_salt)?;
-
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(password, &salt)
-        .expect("Error occurred while encrypted password")
-        .to_string();
+    let password_hash = state
+        .password_service
+        .hash_password(&payload.password)?;
 
     let update_password_status = state
         .admin_user_service
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::{Extension, Json};
+use rust_i18n::t;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_model::AdminUserModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+pub async fn deactivate_admin_user_api_handler(
+    AxumPath(admin_user_id): AxumPath<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<AdminUserModel>>> {
+    println!("->> {:<12} - deactivate_admin_user_api_handler", "HANDLER");
+
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, admin_user_id.clone())
+        .await?;
+
+    if admin_user_model.is_super_admin && admin_user_model.is_active {
+        let active_super_admin_count = state
+            .admin_user_service
+            .count_of_active_super_admins(&state.db)
+            .await?;
+
+        if active_super_admin_count.total <= 1 {
+            let error_message = ErrorMessage {
+                key: String::from("is_active"),
+                message: t!("admin_user_last_active_super_admin").to_string()
+            };
+            let error_response = ErrorResponse {
+                status: false,
+                errors: vec![error_message]
+            };
+
+            return Err(Error::BadRequest(error_response));
+        }
+    }
+
+    let admin_user_model = state
+        .admin_user_service
+        .update_active_status(&state.db, admin_user_id, false, logged_in_user.email)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: admin_user_model
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_authenticated_post_request, send_get_request, send_post_request, setup_avored_db};
+    use crate::error::Result;
+    use crate::models::admin_user_model::CreatableAdminUserModel;
+    use crate::models::token_claim_model::LoggedInUser;
+
+    #[tokio::test]
+    async fn test_deactivate_admin_user_api_handler() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let super_admin = state
+            .admin_user_service
+            .find_by_email(&state.db, String::from("admin@admin.com"))
+            .await?;
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request(&format!("/api/admin-user/{}/deactivate", super_admin.id), Body::empty(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let logged_in_user = LoggedInUser {
+            id: super_admin.id.clone(),
+            name: super_admin.full_name.clone(),
+            email: super_admin.email.clone(),
+            demo_data_status: false,
+            admin_user_model: super_admin.clone(),
+            token_permissions: None
+        };
+        let password = state.password_service.hash_password("Editor123Secret")?;
+        let creatable_admin_user_model = CreatableAdminUserModel {
+            full_name: String::from("Editor"),
+            email: String::from("editor@avored.com"),
+            password,
+            profile_image: String::from(""),
+            is_super_admin: false,
+            logged_in_username: String::from("admin@admin.com"),
+            role_ids: vec![],
+        };
+        let editor = state
+            .admin_user_service
+            .create_admin_user(&state.db, creatable_admin_user_model, logged_in_user)
+            .await?;
+
+        let login_payload = || Body::from(r#"{"email": "editor@avored.com", "password": "Editor123Secret"}"#);
+        let response = app.clone()
+            .oneshot(send_post_request("/api/login", login_payload()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let editor_token = body["data"].as_str().unwrap().to_string();
+
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request(&format!("/api/admin-user/{}/deactivate", editor.id), Body::empty(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.clone()
+            .oneshot(send_get_request("/api/logged-in-user", editor_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/login", login_payload()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // a deactivated admin user with two factor gets no challenge token either
+        state.admin_user_service.generate_two_factor_secret(&state.db, &editor).await?;
+        state.admin_user_service.enable_two_factor(&state.db, &editor.id).await?;
+        let response = app.clone()
+            .oneshot(send_post_request("/api/login", login_payload()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request(&format!("/api/admin-user/{}/reactivate", editor.id), Body::empty(), token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(send_get_request("/api/logged-in-user", editor_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
+}
@@ -3,27 +3,16 @@ use crate::{
     avored_state::AvoRedState, error::Result
 };
 
-use axum::{Extension, extract::{Path as AxumPath, State}, Json, response::IntoResponse};
+use axum::{extract::{Path as AxumPath, State}, Json, response::IntoResponse};
 use serde::Serialize;
-use crate::error::Error;
 use crate::models::admin_user_model::AdminUserModel;
-use crate::models::token_claim_model::LoggedInUser;
 
 pub async fn fetch_admin_user_api_handler(
-    Extension(logged_in_user): Extension<LoggedInUser>,
     AxumPath(admin_user_id): AxumPath<String>,
     state: State<Arc<AvoRedState>>
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - fetch_admin_user_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user, String::from("get_admin_user"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let admin_user_model = state
         .admin_user_service
         .find_by_id(&state.db, admin_user_id)
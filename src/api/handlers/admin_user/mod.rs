@@ -7,4 +7,13 @@ pub mod logged_in_user_api_handler;
 pub mod admin_user_forgot_password_api_handler;
 pub mod admin_user_reset_password_api_handler;
 pub mod change_password_api_handler;
-pub mod request;
\ No newline at end of file
+pub mod admin_user_two_factor_login_api_handler;
+pub mod admin_user_two_factor_enrol_login_api_handler;
+pub mod two_factor_enrol_api_handler;
+pub mod two_factor_confirm_api_handler;
+pub mod two_factor_disable_api_handler;
+pub mod request;
+pub mod unlock_account_api_handler;
+pub mod deactivate_admin_user_api_handler;
+pub mod reactivate_admin_user_api_handler;
+pub mod update_admin_user_avatar_api_handler;
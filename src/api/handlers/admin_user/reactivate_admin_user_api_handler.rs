@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::{Extension, Json};
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::admin_user_model::AdminUserModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn reactivate_admin_user_api_handler(
+    AxumPath(admin_user_id): AxumPath<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<AdminUserModel>>> {
+    println!("->> {:<12} - reactivate_admin_user_api_handler", "HANDLER");
+
+    state
+        .admin_user_service
+        .find_by_id(&state.db, admin_user_id.clone())
+        .await?;
+
+    let admin_user_model = state
+        .admin_user_service
+        .update_active_status(&state.db, admin_user_id, true, logged_in_user.email)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: admin_user_model
+    };
+
+    Ok(Json(response))
+}
@@ -1,7 +1,6 @@
 use rust_i18n::t;
 use serde::Deserialize;
 use crate::avored_state::AvoRedState;
-use crate::models::password_rest_model::PasswordResetTokenStatus;
 use crate::models::validation_error::{ErrorMessage, Validate};
 
 #[derive(Deserialize, Debug, Clone)]
@@ -41,6 +40,14 @@ impl AdminUserResetPasswordRequest {
 
             errors.push(error_message);
         }
+        let full_name = state
+            .admin_user_service
+            .find_by_email(&state.db, self.email.clone())
+            .await
+            .map(|admin_user_model| admin_user_model.full_name)
+            .unwrap_or_default();
+        errors.append(&mut state.password_service.validate_password(&self.password, &self.email, &full_name));
+
         if !self.confirm_password.required()? {
             let error_message = ErrorMessage {
                 key: String::from("confirm_password"),
@@ -68,12 +75,14 @@ impl AdminUserResetPasswordRequest {
             errors.push(error_message);
         }
 
-        let password_reset_model = state
+        let is_token_valid = state
             .admin_user_service
             .get_password_reset_by_email(&state.db, self.email.clone(), self.token.clone())
-            .await?;
+            .await
+            .map(|password_reset_model| !password_reset_model.is_expired())
+            .unwrap_or(false);
 
-        if password_reset_model.status == PasswordResetTokenStatus::Expire {
+        if !is_token_valid {
             let error_message = ErrorMessage {
                 key: String::from("token"),
                 message: t!("password_reset_token_expire").to_string()
@@ -0,0 +1,25 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DisableTwoFactorRequest {
+    pub password: String,
+}
+
+impl DisableTwoFactorRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.password.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("password"),
+                message: t!("validation_required", attribute = t!("password")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
@@ -3,4 +3,9 @@ pub mod authenticate_admin_user_request;
 pub mod store_admin_user_request;
 pub mod admin_user_forgot_password_request;
 pub mod admin_user_reset_password_request;
-pub mod change_password_request;
\ No newline at end of file
+pub mod change_password_request;
+pub mod two_factor_code_request;
+pub mod two_factor_login_request;
+pub mod two_factor_challenge_request;
+pub mod disable_two_factor_request;
+pub mod unlock_account_request;
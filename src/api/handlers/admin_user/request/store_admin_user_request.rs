@@ -53,6 +53,8 @@ impl StoreAdminUserRequest {
             errors.push(error_message);
         }
 
+        errors.append(&mut state.password_service.validate_password(&self.password, &self.email, &self.full_name));
+
         if !self.confirmation_password.required()? {
             let error_message = ErrorMessage {
                 key: String::from("confirmation_password"),
@@ -0,0 +1,25 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+impl TwoFactorCodeRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.code.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("code"),
+                message: t!("validation_required", attribute = t!("code")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
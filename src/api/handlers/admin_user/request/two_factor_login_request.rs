@@ -0,0 +1,36 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct TwoFactorLoginRequest {
+    pub challenge_token: String,
+    pub code: String,
+}
+
+impl TwoFactorLoginRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.challenge_token.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("challenge_token"),
+                message: t!("validation_required", attribute = t!("challenge_token")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if !self.code.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("code"),
+                message: t!("validation_required", attribute = t!("code")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
@@ -0,0 +1,37 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::error::Result;
+use crate::models::validation_error::ErrorMessage;
+use crate::models::validation_error::Validate;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UnlockAccountRequest {
+    pub email: String,
+    pub token: String,
+}
+
+impl UnlockAccountRequest {
+    pub fn validate(&self) -> Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.email.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("email"),
+                message: t!("validation_required", attribute = t!("email")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if !self.token.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("token"),
+                message: t!("validation_required", attribute = t!("token")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
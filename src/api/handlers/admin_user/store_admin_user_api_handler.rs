@@ -1,9 +1,6 @@
-use std::path::Path;
 use std::sync::Arc;
 use axum::extract::{Multipart,  State};
 use axum::{Extension, Json};
-use rand::distributions::Alphanumeric;
-use rand::Rng;
 use serde::Serialize;
 use urlencoding::decode_binary;
 use crate::api::handlers::admin_user::request::store_admin_user_request::StoreAdminUserRequest;
@@ -19,14 +16,6 @@ pub async fn store_admin_user_api_handler(
     mut multipart: Multipart
 ) -> Result<Json<CreateAdminUserResponse>> {
     println!("->> {:<12} - store_admin_user_api_handler", "HANDLER");
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("admin_user_create"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let mut payload = StoreAdminUserRequest {
         full_name: String::from(""),
         email: String::from(""),
@@ -35,32 +24,18 @@ pub async fn store_admin_user_api_handler(
         confirmation_password: String::from(""),
         role_ids: vec![]
     };
-    let mut profile_image = String::from("");
+    let mut image_data: Option<Vec<u8>> = None;
 
     while let Some(field) = multipart.next_field().await? {
         let name = field.name().unwrap_or_default();
 
         match name {
             "image" => {
-                let s: String = rand::thread_rng()
-                    .sample_iter(&Alphanumeric)
-                    .take(32)
-                    .map(char::from)
-                    .collect();
-
-                let _file_content_type = field.content_type().unwrap().to_string();
-                let file_name = field.file_name().unwrap().to_string();
-                let data = field.bytes().await.expect("data expected");
-
-                if !file_name.is_empty() {
-                    let file_ext = file_name.split('.').last().unwrap_or(".png");
-                    let new_file_name = format!("{}.{}", s, file_ext);
+                let file_name = field.file_name().unwrap_or_default().to_string();
+                let data = field.bytes().await?;
 
-                    let file_name = Path::new(&new_file_name).file_name().unwrap();
-
-                    profile_image = format!("upload/{}", new_file_name);
-                    let full_path = Path::new("public").join("upload").join(file_name);
-                    tokio::fs::write(full_path, data).await.unwrap();
+                if !file_name.is_empty() && !data.is_empty() {
+                    image_data = Some(data.to_vec());
                 }
             }
             "full_name" => {
@@ -114,7 +89,10 @@ pub async fn store_admin_user_api_handler(
         }
     }
 
-    let error_messages = payload.validate(&state).await?;
+    let mut error_messages = payload.validate(&state).await?;
+    if let Some(data) = &image_data {
+        error_messages.append(&mut state.avatar_service.validate_avatar(data));
+    }
 
     if !error_messages.is_empty() {
         let error_response = ErrorResponse {
@@ -125,10 +103,13 @@ pub async fn store_admin_user_api_handler(
     }
 
     let password_hash = state
-        .admin_user_service
-        .get_password_hash_from_raw_password(
-            payload.password, &state.config.password_salt
-        )?;
+        .password_service
+        .hash_password(&payload.password)?;
+
+    let profile_image = match image_data {
+        Some(data) => state.avatar_service.store_avatar(data).await?,
+        None => String::from("")
+    };
 
     let creatable_admin_user = CreatableAdminUserModel {
         full_name: payload.full_name,
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use rust_i18n::t;
+use crate::api::handlers::admin_user::request::two_factor_code_request::TwoFactorCodeRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_two_factor_model::TwoFactorStatus;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+pub async fn two_factor_confirm_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> Result<Json<ApiResponse<Vec<String>>>> {
+    println!("->> {:<12} - two_factor_confirm_api_handler", "HANDLER");
+
+    let mut error_messages = payload.validate()?;
+
+    if error_messages.is_empty() {
+        let two_factor_model = state
+            .admin_user_service
+            .find_two_factor_by_admin_user_id(&state.db, &logged_in_user.id)
+            .await?;
+
+        if two_factor_model.status == TwoFactorStatus::Enabled {
+            error_messages.push(ErrorMessage {
+                key: String::from("code"),
+                message: t!("two_factor_already_enabled").to_string()
+            });
+        } else if !state
+            .admin_user_service
+            .check_two_factor_code(&state.db, &two_factor_model, &logged_in_user.email, &payload.code)
+            .await? {
+            error_messages.push(ErrorMessage {
+                key: String::from("code"),
+                message: t!("two_factor_code_invalid").to_string()
+            });
+        }
+    }
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let recovery_codes = state
+        .admin_user_service
+        .enable_two_factor(&state.db, &logged_in_user.id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: recovery_codes
+    };
+
+    Ok(Json(response))
+}
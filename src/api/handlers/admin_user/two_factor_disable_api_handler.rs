@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use rust_i18n::t;
+use crate::api::handlers::admin_user::request::disable_two_factor_request::DisableTwoFactorRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+pub async fn two_factor_disable_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<DisableTwoFactorRequest>,
+) -> Result<Json<ApiResponse<bool>>> {
+    println!("->> {:<12} - two_factor_disable_api_handler", "HANDLER");
+
+    let mut error_messages = payload.validate()?;
+
+    let enforce_two_factor_setting = state
+        .setting_service
+        .find_by_identifier(&state.db, String::from("auth_enforce_two_factor"))
+        .await
+        .unwrap_or_default();
+
+    if enforce_two_factor_setting.value.eq("true") {
+        error_messages.push(ErrorMessage {
+            key: String::from("password"),
+            message: t!("two_factor_enforced").to_string()
+        });
+    }
+
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, logged_in_user.id.clone())
+        .await?;
+
+    let is_password_match: bool = state
+        .admin_user_service
+        .compare_password(payload.password, admin_user_model.password)?;
+
+    if !is_password_match {
+        error_messages.push(ErrorMessage {
+            key: String::from("password"),
+            message: t!("password_match_error").to_string()
+        });
+    }
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let disabled_status = state
+        .admin_user_service
+        .disable_two_factor(&state.db, &logged_in_user.id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: disabled_status
+    };
+
+    Ok(Json(response))
+}
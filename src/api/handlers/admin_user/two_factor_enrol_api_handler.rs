@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use rust_i18n::t;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_two_factor_model::TwoFactorEnrolmentModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+pub async fn two_factor_enrol_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<TwoFactorEnrolmentModel>>> {
+    println!("->> {:<12} - two_factor_enrol_api_handler", "HANDLER");
+
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, logged_in_user.id)
+        .await?;
+
+    if admin_user_model.is_two_factor_enabled {
+        let error_messages = vec![ErrorMessage {
+            key: String::from("code"),
+            message: t!("two_factor_already_enabled").to_string()
+        }];
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let two_factor_enrolment_model = state
+        .admin_user_service
+        .generate_two_factor_secret(&state.db, &admin_user_model)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: two_factor_enrolment_model
+    };
+
+    Ok(Json(response))
+}
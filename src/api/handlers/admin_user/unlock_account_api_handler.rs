@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use rust_i18n::t;
+use serde::Serialize;
+use crate::api::handlers::admin_user::request::unlock_account_request::UnlockAccountRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+#[derive(Serialize, Default)]
+pub struct UnlockAccountViewModel {
+    pub link: String,
+    pub minutes: i64
+}
+
+pub async fn unlock_account_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<UnlockAccountRequest>,
+) -> Result<Json<ApiResponse<bool>>> {
+    println!("->> {:<12} - unlock_account_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let is_unlocked = state
+        .login_throttle_service
+        .unlock_account(&state.db, &payload.email, &payload.token)
+        .await?;
+
+    if !is_unlocked {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: vec![ErrorMessage {
+                key: String::from("token"),
+                message: t!("unlock_token_invalid").to_string()
+            }]
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let response_data = ApiResponse {
+        status: true,
+        data: is_unlocked,
+    };
+
+    Ok(Json(response_data))
+}
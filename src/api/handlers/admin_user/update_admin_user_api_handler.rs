@@ -1,4 +1,3 @@
-use std::path::Path;
 use std::sync::Arc;
 use crate::{
     avored_state::AvoRedState, error::Result
@@ -6,8 +5,6 @@ use crate::{
 
 use axum::{Extension, extract::{Path as AxumPath, State}, Json};
 use axum::extract::Multipart;
-use rand::distributions::Alphanumeric;
-use rand::Rng;
 use serde::Serialize;
 use urlencoding::decode_binary;
 use crate::api::handlers::admin_user::request::update_admin_user_request::UpdateAdminUserRequest;
@@ -24,44 +21,23 @@ pub async fn update_admin_user_api_handler(
 ) -> Result<Json<UpdatableAdminUserResponse>> {
     println!("->> {:<12} - update_admin_user_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("admin_user_edit"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
     let mut payload = UpdateAdminUserRequest {
         full_name: String::from(""),
         is_super_admin: false,
         role_ids: vec![]
     };
-    let mut profile_image = String::from("");
+    let mut image_data: Option<Vec<u8>> = None;
 
     while let Some(field) = multipart.next_field().await.expect("cant find next field") {
         let name = field.name().expect("field name missing");
 
         match name {
             "image" => {
-                let s: String = rand::thread_rng()
-                    .sample_iter(&Alphanumeric)
-                    .take(32)
-                    .map(char::from)
-                    .collect();
-
-                let _file_content_type = field.content_type().unwrap().to_string();
-                let file_name = field.file_name().unwrap().to_string();
-                let data = field.bytes().await.expect("data expected");
-
-                if !file_name.is_empty() {
-                    let file_ext = file_name.split('.').last().unwrap_or(".png");
-                    let new_file_name = format!("{}.{}", s, file_ext);
+                let file_name = field.file_name().unwrap_or_default().to_string();
+                let data = field.bytes().await?;
 
-                    let file_name = Path::new(&new_file_name).file_name().unwrap();
-
-                    profile_image = format!("upload/{}", new_file_name);
-                    let full_path = Path::new("public").join("upload").join(file_name);
-                    tokio::fs::write(full_path, data).await.unwrap();
+                if !file_name.is_empty() && !data.is_empty() {
+                    image_data = Some(data.to_vec());
                 }
             }
             "full_name" => {
@@ -94,7 +70,10 @@ pub async fn update_admin_user_api_handler(
         }
     }
 
-    let error_messages = payload.validate()?;
+    let mut error_messages = payload.validate()?;
+    if let Some(data) = &image_data {
+        error_messages.append(&mut state.avatar_service.validate_avatar(data));
+    }
     if !error_messages.is_empty() {
         let error_response = ErrorResponse {
             status: false,
@@ -103,10 +82,25 @@ pub async fn update_admin_user_api_handler(
         return Err(Error::BadRequest(error_response));
     }
 
+    if let Some(data) = image_data {
+        let previous_admin_user_model = state
+            .admin_user_service
+            .find_by_id(&state.db, admin_user_id.clone())
+            .await?;
+        let profile_image = state.avatar_service.store_avatar(data).await?;
+        state
+            .admin_user_service
+            .update_profile_image(&state.db, admin_user_id.clone(), profile_image, logged_in_user.email.clone())
+            .await?;
+        state
+            .avatar_service
+            .delete_avatar(&previous_admin_user_model.profile_image)
+            .await;
+    }
+
     let updateable_admin_user_model = UpdatableAdminUserModel {
         id: admin_user_id,
         full_name: payload.full_name,
-        profile_image,
         is_super_admin: payload.is_super_admin,
         logged_in_username: logged_in_user.email.clone(),
         role_ids: payload.role_ids
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+use axum::extract::{Multipart, Path as AxumPath, State};
+use axum::{Extension, Json};
+use rust_i18n::t;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_model::AdminUserModel;
+use crate::models::permission_model::Permission;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+/// Admin users can always change their own avatar, others need the admin user edit permission.
+pub async fn update_admin_user_avatar_api_handler(
+    AxumPath(admin_user_id): AxumPath<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<AdminUserModel>>> {
+    println!("->> {:<12} - update_admin_user_avatar_api_handler", "HANDLER");
+
+    if logged_in_user.id != admin_user_id && !logged_in_user.has_permission(Permission::AdminUserEdit) {
+        return Err(Error::Forbidden);
+    }
+
+    let previous_admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, admin_user_id.clone())
+        .await?;
+
+    let mut image_data: Option<Vec<u8>> = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name().unwrap_or_default() == "image" {
+            image_data = Some(field.bytes().await?.to_vec());
+        }
+    }
+
+    let mut error_messages: Vec<ErrorMessage> = vec![];
+    match &image_data {
+        Some(data) if !data.is_empty() => {
+            error_messages.append(&mut state.avatar_service.validate_avatar(data));
+        }
+        _ => error_messages.push(ErrorMessage {
+            key: String::from("image"),
+            message: t!("validation_required", attribute = t!("image")).to_string()
+        }),
+    }
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let profile_image = state
+        .avatar_service
+        .store_avatar(image_data.unwrap_or_default())
+        .await?;
+    let admin_user_model = state
+        .admin_user_service
+        .update_profile_image(&state.db, admin_user_id, profile_image, logged_in_user.email)
+        .await?;
+    state
+        .avatar_service
+        .delete_avatar(&previous_admin_user_model.profile_image)
+        .await;
+
+    let response = ApiResponse {
+        status: true,
+        data: admin_user_model
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use image::{ImageFormat, RgbImage};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, setup_avored_db};
+    use crate::error::Result;
+
+    fn send_image_request(uri: &str, file_name: &str, data: Vec<u8>, token: String) -> Request<Body> {
+        let boundary = "AvoRedAvatarBoundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"image\"; filename=\"{file_name}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        ).into_bytes();
+        body.extend(data);
+        body.extend(format!("\r\n--{boundary}--\r\n").into_bytes());
+
+        Request::builder()
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={boundary}"))
+            .method("POST")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_admin_user_avatar_api_handler() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let login_response = get_login_response(app.clone()).await?;
+        let token = login_response.data;
+        let avatar_uri = format!("/api/admin-user/{}/avatar", login_response.admin_user.id);
+
+        assert!(login_response.admin_user.profile_image.starts_with("data:image/svg+xml;base64,"));
+
+        // the content decides, not the file name
+        let response = app.clone()
+            .oneshot(send_image_request(&avatar_uri, "avatar.png", b"not an image".to_vec(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let mut png = Cursor::new(Vec::new());
+        RgbImage::new(300, 200).write_to(&mut png, ImageFormat::Png)?;
+        let png = png.into_inner();
+        let mut stored_paths: Vec<String> = vec![];
+        for _ in 0..2 {
+            let response = app.clone()
+                .oneshot(send_image_request(&avatar_uri, "avatar.png", png.clone(), token.clone()))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+            let profile_image = body["data"]["profile_image"].as_str().unwrap();
+
+            let stored_path = profile_image
+                .strip_prefix("http://localhost:8080/")
+                .expect("avatar url is built from the back end app url");
+            let stored_avatar = image::open(stored_path)?;
+            assert_eq!((stored_avatar.width(), stored_avatar.height()), (256, 256));
+            stored_paths.push(stored_path.to_string());
+        }
+
+        // a new avatar replaces every size of the previous one
+        for size in ["64", "128", "256"] {
+            let previous_path = stored_paths[0].replace("-256.png", &format!("-{size}.png"));
+            assert!(!std::path::Path::new(&previous_path).exists());
+            let current_path = stored_paths[1].replace("-256.png", &format!("-{size}.png"));
+            assert!(std::path::Path::new(&current_path).exists());
+        }
+
+        // older uploads only stored the path below the public folder
+        let (datastore, database_session) = &state.db;
+        datastore
+            .execute("UPDATE admin_users SET profile_image='upload/legacy.png';", database_session, None)
+            .await?;
+        state.admin_user_service.upgrade_legacy_profile_images(&state.db, &state.config.back_end_app_url).await?;
+        let admin_user_model = state.admin_user_service.find_by_id(&state.db, login_response.admin_user.id).await?;
+        assert_eq!(admin_user_model.profile_image, "http://localhost:8080/public/upload/legacy.png");
+
+        Ok(())
+    }
+}
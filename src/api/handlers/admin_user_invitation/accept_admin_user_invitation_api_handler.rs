@@ -0,0 +1,48 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use crate::api::handlers::admin_user_invitation::request::accept_admin_user_invitation_request::AcceptAdminUserInvitationRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_model::AdminUserModel;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+pub async fn accept_admin_user_invitation_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<AcceptAdminUserInvitationRequest>,
+) -> Result<Json<ApiResponse<AdminUserModel>>> {
+    println!("->> {:<12} - accept_admin_user_invitation_api_handler", "HANDLER");
+
+    let invitation = state
+        .admin_user_invitation_service
+        .find_by_token(&state.db, &payload.token)
+        .await?;
+
+    let error_messages = payload.validate(&state, &invitation.email)?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let password_hash = state
+        .password_service
+        .hash_password(&payload.password)?;
+
+    let admin_user_model = state
+        .admin_user_invitation_service
+        .accept_invitation(&state.db, &payload.token, payload.full_name, password_hash)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: admin_user_model
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::admin_user_invitation_model::AdminUserInvitationModel;
+use crate::responses::ApiResponse;
+
+pub async fn admin_user_invitation_table_api_handler(
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<Vec<AdminUserInvitationModel>>>> {
+    println!("->> {:<12} - admin_user_invitation_table_api_handler", "HANDLER");
+
+    let admin_user_invitations = state
+        .admin_user_invitation_service
+        .all(&state.db)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: admin_user_invitations
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,6 @@
+pub mod admin_user_invitation_table_api_handler;
+pub mod store_admin_user_invitation_api_handler;
+pub mod resend_admin_user_invitation_api_handler;
+pub mod revoke_admin_user_invitation_api_handler;
+pub mod accept_admin_user_invitation_api_handler;
+pub mod request;
@@ -0,0 +1,50 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::avored_state::AvoRedState;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AcceptAdminUserInvitationRequest {
+    pub token: String,
+    pub full_name: String,
+    pub password: String,
+    pub confirmation_password: String,
+}
+
+impl AcceptAdminUserInvitationRequest {
+    /// The email comes from the invitation, the invitee can not change it.
+    pub fn validate(&self, state: &AvoRedState, email: &str) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.full_name.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("full_name"),
+                message: t!("validation_required", attribute = t!("full_name")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if !self.password.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("password"),
+                message: t!("validation_required", attribute = t!("password")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        errors.append(&mut state.password_service.validate_password(&self.password, email, &self.full_name));
+
+        if self.password != self.confirmation_password {
+            let error_message = ErrorMessage {
+                key: String::from("password"),
+                message: t!("password_did_not_match_confirmation_password").to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
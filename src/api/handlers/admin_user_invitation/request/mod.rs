@@ -0,0 +1,2 @@
+pub mod store_admin_user_invitation_request;
+pub mod accept_admin_user_invitation_request;
@@ -0,0 +1,76 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::avored_state::AvoRedState;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct StoreAdminUserInvitationRequest {
+    pub email: String,
+    #[serde(default)]
+    pub role_ids: Vec<String>,
+}
+
+impl StoreAdminUserInvitationRequest {
+    pub async fn validate(&self, state: &AvoRedState) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.email.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("email"),
+                message: t!("validation_required", attribute = t!("email")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if !self.email.validate_email()? {
+            let error_message = ErrorMessage {
+                key: String::from("email"),
+                message: t!("email_address_not_valid").to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        let admin_user_count = state
+            .admin_user_service
+            .count_of_email(&state.db, self.email.clone())
+            .await?;
+
+        if admin_user_count.total > 0 {
+            let error_message = ErrorMessage {
+                key: String::from("email"),
+                message: t!("validation_count", attribute = t!("email")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        let invitation_count = state
+            .admin_user_invitation_service
+            .count_of_email(&state.db, self.email.clone())
+            .await?;
+
+        if invitation_count > 0 {
+            let error_message = ErrorMessage {
+                key: String::from("email"),
+                message: t!("admin_user_invitation_pending", email = self.email).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        for role_id in &self.role_ids {
+            if state.role_service.find_by_id(&state.db, role_id.clone()).await.is_err() {
+                let error_message = ErrorMessage {
+                    key: String::from("role_ids"),
+                    message: t!("role_invalid", role = role_id).to_string()
+                };
+
+                errors.push(error_message);
+            }
+        }
+
+        Ok(errors)
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::{Extension, Json};
+use tracing::log::error;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::admin_user_invitation_model::AdminUserInvitationModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn resend_admin_user_invitation_api_handler(
+    AxumPath(admin_user_invitation_id): AxumPath<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<AdminUserInvitationModel>>> {
+    println!("->> {:<12} - resend_admin_user_invitation_api_handler", "HANDLER");
+
+    let issued_invitation = state
+        .admin_user_invitation_service
+        .reissue_invitation(&state.db, &admin_user_invitation_id, &logged_in_user.email)
+        .await?;
+
+    if let Err(e) = state
+        .admin_user_invitation_service
+        .send_invitation_email(&state.template, &state.config.react_admin_app_url, &issued_invitation)
+        .await {
+        error!("there is an issue while sending the admin user invitation email: {e:?}");
+    }
+
+    let response = ApiResponse {
+        status: true,
+        data: issued_invitation.invitation
+    };
+
+    Ok(Json(response))
+}
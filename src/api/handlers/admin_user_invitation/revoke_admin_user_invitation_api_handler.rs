@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::Json;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::responses::ApiResponse;
+
+pub async fn revoke_admin_user_invitation_api_handler(
+    AxumPath(admin_user_invitation_id): AxumPath<String>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<bool>>> {
+    println!("->> {:<12} - revoke_admin_user_invitation_api_handler", "HANDLER");
+
+    let revoked_status = state
+        .admin_user_invitation_service
+        .revoke_invitation(&state.db, &admin_user_invitation_id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: revoked_status
+    };
+
+    Ok(Json(response))
+}
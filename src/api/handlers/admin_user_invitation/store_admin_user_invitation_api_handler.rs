@@ -0,0 +1,159 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use serde::Serialize;
+use tracing::log::error;
+use crate::api::handlers::admin_user_invitation::request::store_admin_user_invitation_request::StoreAdminUserInvitationRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::admin_user_invitation_model::{AdminUserInvitationModel, CreatableAdminUserInvitationModel};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+#[derive(Serialize, Default)]
+pub struct AdminUserInvitationViewModel {
+    pub link: String,
+    pub hours: i64
+}
+
+pub async fn store_admin_user_invitation_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<StoreAdminUserInvitationRequest>,
+) -> Result<Json<ApiResponse<AdminUserInvitationModel>>> {
+    println!("->> {:<12} - store_admin_user_invitation_api_handler", "HANDLER");
+
+    let error_messages = payload.validate(&state).await?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let creatable_invitation_model = CreatableAdminUserInvitationModel {
+        email: payload.email,
+        role_ids: payload.role_ids,
+        logged_in_username: logged_in_user.email,
+    };
+
+    let issued_invitation = state
+        .admin_user_invitation_service
+        .create_invitation(&state.db, creatable_invitation_model)
+        .await?;
+
+    // the invitation can be resent from the list when the email does not go out
+    if let Err(e) = state
+        .admin_user_invitation_service
+        .send_invitation_email(&state.template, &state.config.react_admin_app_url, &issued_invitation)
+        .await {
+        error!("there is an issue while sending the admin user invitation email: {e:?}");
+    }
+
+    let response = ApiResponse {
+        status: true,
+        data: issued_invitation.invitation
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_authenticated_post_request, send_get_request, send_post_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_store_admin_user_invitation_api_handler() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let payload = Body::from(r#"{"email": "invited@avored.com", "role_ids": ["missing_role"]}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/admin-user-invitation", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let payload = Body::from(r#"{"email": "invited@avored.com", "role_ids": []}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/admin-user-invitation", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let invitation_id = body["data"]["id"].as_str().unwrap().to_string();
+
+        let payload = Body::from(r#"{"email": "invited@avored.com", "role_ids": []}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/admin-user-invitation", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.clone()
+            .oneshot(send_get_request("/api/admin-user-invitation", token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+        let old_invitation_token = state
+            .admin_user_invitation_service
+            .reissue_invitation(&state.db, &invitation_id, "admin@admin.com")
+            .await?
+            .token;
+        let invitation_token = state
+            .admin_user_invitation_service
+            .reissue_invitation(&state.db, &invitation_id, "admin@admin.com")
+            .await?
+            .token;
+
+        let accept_payload = |invitation_token: &str| Body::from(format!(
+            r#"{{"token": "{invitation_token}", "full_name": "Invited User", "password": "Welcome123Aboard", "confirmation_password": "Welcome123Aboard"}}"#
+        ));
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/accept-invitation", accept_payload(&old_invitation_token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/accept-invitation", accept_payload(&invitation_token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.clone()
+            .oneshot(send_post_request("/api/accept-invitation", accept_payload(&invitation_token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let revoke_request = Request::builder()
+            .uri(format!("/api/admin-user-invitation/{invitation_id}"))
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .method("DELETE")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(revoke_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::asset_model::DuplicateAssetGroupModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn asset_duplicate_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+) -> Result<Json<ApiResponse<Vec<DuplicateAssetGroupModel>>>> {
+    println!("->> {:<12} - asset_duplicate_api_handler", "HANDLER");
+
+    let duplicate_groups = state.asset_service
+        .duplicate_groups(&state.db, &logged_in_user)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: duplicate_groups
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use crate::api::handlers::asset::request::search_asset_request::SearchAssetRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::asset_model::AssetPagination;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+
+pub async fn asset_search_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Query(query_param): Query<SearchAssetRequest>,
+) -> Result<Json<AssetPagination>> {
+    println!("->> {:<12} - asset_search_api_handler", "HANDLER");
+
+    let error_messages = query_param.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let current_page = query_param.current_page();
+    let asset_pagination = state.asset_service
+        .search(&state.db, current_page, query_param.into_search(), &logged_in_user)
+        .await?;
+
+    Ok(Json(asset_pagination))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_authenticated_post_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn search(app: axum::Router, uri: &str, token: &str) -> (StatusCode, Value) {
+        let response = app.oneshot(send_get_request(uri, token.to_string())).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).expect("Failed to parse JSON"))
+    }
+
+    #[tokio::test]
+    async fn test_asset_search_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/create-folder", Body::from(r#"{"name": "brand"}"#), token.clone()))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let folder_id = body["data"]["id"].as_str().unwrap().to_string();
+
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="10" height="10" /></svg>"#;
+        let mut request = send_asset_upload_request("Company-Logo.svg", "image/svg+xml", svg.to_vec(), token.clone());
+        *request.uri_mut() = format!("/api/asset?parent_id={folder_id}").parse().unwrap();
+        app.clone().oneshot(request).await.unwrap();
+        app.clone()
+            .oneshot(send_asset_upload_request("price-list.csv", "text/csv", b"name,price\n".to_vec(), token.clone()))
+            .await
+            .unwrap();
+
+        // found below its folder by a part of the uploaded name
+        let (status, body) = search(app.clone(), "/api/asset-search?q=logo", &token).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["pagination"]["total"], 1);
+        assert_eq!(body["data"][0]["parent_id"], folder_id.as_str());
+
+        let (_, body) = search(app.clone(), "/api/asset-search?file_type=text/*&created_by=admin@admin.com", &token).await;
+        assert_eq!(body["pagination"]["total"], 1);
+        assert_eq!(body["data"][0]["original_name"], "price-list.csv");
+
+        let (_, body) = search(app.clone(), "/api/asset-search?order=file_size:DESC", &token).await;
+        assert_eq!(body["pagination"]["total"], 3);
+        assert_eq!(body["data"][0]["original_name"], "Company-Logo.svg");
+
+        let (_, body) = search(app.clone(), "/api/asset-search?created_to=2000-01-01", &token).await;
+        assert_eq!(body["pagination"]["total"], 0);
+
+        let (status, body) = search(app.clone(), "/api/asset-search?order=path;DELETE:ASC&created_from=yesterday", &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["key"], "order");
+        assert_eq!(body["errors"][1]["key"], "created_from");
+
+        Ok(())
+    }
+}
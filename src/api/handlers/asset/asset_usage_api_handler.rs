@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::asset_reference_model::AssetReferenceModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn asset_usage_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+) -> Result<Json<ApiResponse<Vec<AssetReferenceModel>>>> {
+    println!("->> {:<12} - asset_usage_api_handler", "HANDLER");
+
+    let asset_model = state.asset_service
+        .find_accessible_by_id(&state.db, &asset_id, &logged_in_user)
+        .await?;
+
+    let asset_references = state.asset_reference_service
+        .usages(&state.db, vec![asset_model.id])
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: asset_references
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, Method, Request, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_authenticated_post_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn into_json(response: axum::response::Response) -> (StatusCode, Value) {
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    async fn send_delete(app: axum::Router, uri: &str, token: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_asset_usage_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let request = send_asset_upload_request("notes.txt", "text/plain", b"used".to_vec(), token.clone());
+        let (_, body) = into_json(app.clone().oneshot(request).await.unwrap()).await;
+        let asset_id = body["asset_model"]["id"].as_str().unwrap().to_string();
+        let asset_url = body["asset_model"]["url"].as_str().unwrap().to_string();
+
+        let request = send_get_request("/api/setting", token.clone());
+        let (_, body) = into_json(app.clone().oneshot(request).await.unwrap()).await;
+        let setting = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|setting| setting["identifier"] == "general_site_name")
+            .unwrap();
+        let settings = serde_json::json!({
+            "settings": [{
+                "id": setting["id"],
+                "identifier": "general_site_name",
+                "value": format!("<img src=\"{asset_url}\"> Avored rust cms")
+            }]
+        });
+        let request = send_authenticated_post_request("/api/setting", Body::from(settings.to_string()), token.clone());
+        let (status, _) = into_json(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let request = send_get_request(&format!("/api/asset-usage/{asset_id}"), token.clone());
+        let (status, body) = into_json(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["source_type"], "setting");
+        assert_eq!(body["data"][0]["source_name"], "general_site_name");
+        assert_eq!(body["data"][0]["field"], "value");
+
+        // a used asset is only deleted when forced
+        let status = send_delete(app.clone(), &format!("/api/delete-asset/{asset_id}"), &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let status = send_delete(app.clone(), &format!("/api/delete-asset/{asset_id}?force=true"), &token).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let request = send_get_request(&format!("/api/asset-usage/{asset_id}"), token);
+        let (status, _) = into_json(app.oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
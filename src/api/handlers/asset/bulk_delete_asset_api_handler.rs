@@ -0,0 +1,206 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use tracing::log::error;
+use crate::api::handlers::asset::request::bulk_asset_request::BulkDeleteAssetRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::asset_model::AssetOperationResultModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+pub async fn bulk_delete_asset_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    Json(payload): Json<BulkDeleteAssetRequest>,
+) -> Result<Json<ApiResponse<Vec<AssetOperationResultModel>>>> {
+    println!("->> {:<12} - bulk_delete_asset_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let (results, deleted_assets) = state
+        .asset_service
+        .delete_assets(&state.db, &payload.asset_ids, payload.recursive, payload.force, &logged_in_user)
+        .await?;
+
+    for file in deleted_assets.iter().filter(|asset_model| asset_model.asset_type == "FILE") {
+        if let Err(e) = state.image_service.remove_renditions(&file.id).await {
+            error!("there is an issue while removing the image renditions: {e:?}");
+        }
+    }
+
+    let response = ApiResponse {
+        status: results.iter().all(|result| result.success),
+        data: results
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use axum::body::Body;
+    use axum::http::{header, Method, Request, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_authenticated_post_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn into_json(response: axum::response::Response) -> (StatusCode, Value) {
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    async fn send_json(app: axum::Router, uri: &str, body: String, token: &str) -> (StatusCode, Value) {
+        let response = app
+            .oneshot(send_authenticated_post_request(uri, Body::from(body), token.to_string()))
+            .await
+            .unwrap();
+
+        into_json(response).await
+    }
+
+    async fn send_delete(app: axum::Router, uri: &str, token: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+
+        into_json(app.oneshot(request).await.unwrap()).await
+    }
+
+    async fn upload(app: axum::Router, parent_id: &str, token: &str) -> String {
+        let mut request = send_asset_upload_request("notes.txt", "text/plain", b"bulk".to_vec(), token.to_string());
+        *request.uri_mut() = format!("/api/asset?parent_id={parent_id}").parse().unwrap();
+        let (_, body) = into_json(app.oneshot(request).await.unwrap()).await;
+
+        body["asset_model"]["id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_bulk_asset_api_handlers() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let (_, body) = send_json(app.clone(), "/api/create-folder", r#"{"name": "bulk-folder"}"#.to_string(), &token).await;
+        let folder_id = body["data"]["id"].as_str().unwrap().to_string();
+        let first_file_id = upload(app.clone(), &folder_id, &token).await;
+        let second_file_id = upload(app.clone(), "", &token).await;
+
+        // a folder with content is only deleted recursively, the dry run deletes nothing
+        let (status, body) = send_delete(app.clone(), &format!("/api/delete-folder/{folder_id}"), &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["key"], "folder_existed");
+        let (status, body) = send_delete(
+            app.clone(),
+            &format!("/api/delete-folder/{folder_id}?recursive=true&dry_run=true"),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 2);
+        assert_eq!(body["data"][1]["id"], first_file_id.as_str());
+        assert!(Path::new("public/upload/bulk-folder").exists());
+
+        let (status, body) = send_json(
+            app.clone(),
+            "/api/bulk-tag-asset",
+            format!(r#"{{"asset_ids": ["{first_file_id}", "{second_file_id}"], "add_tags": ["summer", " sale "]}}"#),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], true);
+
+        // one unknown id and nothing is deleted
+        let (status, body) = send_json(
+            app.clone(),
+            "/api/bulk-delete-asset",
+            format!(r#"{{"asset_ids": ["{folder_id}", "{second_file_id}", "missing"], "recursive": true}}"#),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], false);
+        assert_eq!(body["data"][0]["success"], false);
+        assert_eq!(body["data"][2]["asset_id"], "missing");
+        assert!(Path::new("public/upload/bulk-folder").exists());
+
+        let (status, body) = send_json(
+            app.clone(),
+            "/api/bulk-delete-asset",
+            format!(r#"{{"asset_ids": ["{folder_id}", "{second_file_id}"], "recursive": true}}"#),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], true);
+        assert!(!Path::new("public/upload/bulk-folder").exists());
+
+        let (status, _) = send_delete(app, &format!("/api/delete-asset/{first_file_id}"), &token).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_move_asset_rolls_back_a_partial_move() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let (_, body) = send_json(app.clone(), "/api/create-folder", r#"{"name": "rollback-source"}"#.to_string(), &token).await;
+        let source_id = body["data"]["id"].as_str().unwrap().to_string();
+        let (_, body) = send_json(app.clone(), "/api/create-folder", r#"{"name": "rollback-target"}"#.to_string(), &token).await;
+        let target_id = body["data"]["id"].as_str().unwrap().to_string();
+        let (_, body) = send_json(
+            app.clone(),
+            "/api/create-folder",
+            format!(r#"{{"name": "nested", "parent_id": "{source_id}"}}"#),
+            &token
+        ).await;
+        let nested_id = body["data"]["id"].as_str().unwrap().to_string();
+        let first_file_id = upload(app.clone(), &source_id, &token).await;
+        let second_file_id = upload(app.clone(), &nested_id, &token).await;
+
+        // the file of the nested folder is moved last and is gone from the storage,
+        // so the move fails after the first file was moved already
+        let first_file = state.asset_service.find_by_id(&state.db, &first_file_id).await?;
+        let second_file = state.asset_service.find_by_id(&state.db, &second_file_id).await?;
+        std::fs::remove_file(Path::new("public/upload").join(&second_file.path))?;
+
+        let (status, body) = send_json(
+            app.clone(),
+            "/api/bulk-move-asset",
+            format!(r#"{{"asset_ids": ["{source_id}"], "parent_id": "{target_id}"}}"#),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], false);
+
+        assert!(Path::new("public/upload").join(&first_file.path).exists());
+        assert!(!Path::new("public/upload/rollback-target/rollback-source").exists());
+        let first_file_after = state.asset_service.find_by_id(&state.db, &first_file_id).await?;
+        assert_eq!(first_file_after.path, first_file.path);
+
+        std::fs::remove_dir_all("public/upload/rollback-source")?;
+        std::fs::remove_dir_all("public/upload/rollback-target")?;
+
+        Ok(())
+    }
+}
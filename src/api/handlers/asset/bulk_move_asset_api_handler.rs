@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use crate::api::handlers::asset::request::bulk_asset_request::BulkMoveAssetRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::asset_model::AssetOperationResultModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+pub async fn bulk_move_asset_api_handler(
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    Json(payload): Json<BulkMoveAssetRequest>,
+) -> Result<Json<ApiResponse<Vec<AssetOperationResultModel>>>> {
+    println!("->> {:<12} - bulk_move_asset_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let results = state
+        .asset_service
+        .move_assets(&state.db, &payload.asset_ids, &payload.parent_id, &logged_in_user)
+        .await?;
+
+    let response = ApiResponse {
+        status: results.iter().all(|result| result.success),
+        data: results
+    };
+
+    Ok(Json(response))
+}
@@ -17,14 +17,6 @@ pub async fn create_folder_api_handler(
 ) -> Result<Json<ApiResponse<NewAssetModel>>> {
     println!("->> {:<12} - store_asset_folder_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("create_folder"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
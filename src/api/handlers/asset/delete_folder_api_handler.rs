@@ -2,67 +2,42 @@ use std::sync::Arc;
 use crate::{
     avored_state::AvoRedState, error::Result
 };
-use axum::{Extension, extract::State};
+use axum::{Extension, Json};
+use axum::extract::{Query, State};
 use axum::extract::Path;
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
-use tokio::fs;
-use crate::error::Error;
+use tracing::log::error;
+use crate::api::handlers::asset::request::delete_folder_request::DeleteFolderRequest;
+use crate::models::asset_model::NewAssetModel;
 use crate::models::token_claim_model::LoggedInUser;
-use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
 
+/// Responds with the folder and everything deleted along with it, a dry run deletes nothing.
 pub async fn delete_folder_api_handler(
     Path(asset_id): Path<String>,
+    Query(query_param): Query<DeleteFolderRequest>,
     Extension(logged_in_user): Extension<LoggedInUser>,
     state: State<Arc<AvoRedState>>
-) -> Result<impl IntoResponse> {
+) -> Result<Json<ApiResponse<Vec<NewAssetModel>>>> {
     println!("->> {:<12} - delete_folder_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("delete_folder"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
-    let asset_model = state.asset_service
-        .find_by_id(&state.db, &asset_id)
+    let mut assets = state.asset_service
+        .deletable_assets(&state.db, &asset_id, query_param.recursive, query_param.force, &logged_in_user)
         .await?;
 
-    let folder_path = format!("./{path}", path = asset_model.path);
+    if !query_param.dry_run {
+        assets = state.asset_service.remove_assets(&state.db, assets).await?;
 
-    // @todo return early
-    if fs::try_exists(&folder_path).await? {
-        let mut entries = fs::read_dir(&folder_path).await?;
-        let mut counter = 0;
-
-        while let Some(_entry) = entries.next_entry().await? {
-            counter += 1;
+        for file in assets.iter().filter(|asset_model| asset_model.asset_type == "FILE") {
+            if let Err(e) = state.image_service.remove_renditions(&file.id).await {
+                error!("there is an issue while removing the image renditions: {e:?}");
+            }
         }
-
-        if counter > 0 {
-            let error_messages = vec![ErrorMessage {
-                key: String::from("folder_existed"),
-                message: String::from("folder is not empty. Please make sure folder is empty before deleting it.")
-            }];
-            let error_response = ErrorResponse {
-                status: false,
-                errors: error_messages
-            };
-
-            return Err(Error::BadRequest(error_response));
-        }
-        tokio::fs::remove_dir(&folder_path).await?;
     }
 
-    let result = state
-        .asset_service
-        .delete_by_id(&state.db, &asset_id)
-        .await?;
-    if !result {
-        return Err(Error::Generic(String::from("there is an issue while deleting an folder")));
-    }
+    let response = ApiResponse {
+        status: true,
+        data: assets
+    };
 
-    Ok(StatusCode::OK)
+    Ok(Json(response))
 }
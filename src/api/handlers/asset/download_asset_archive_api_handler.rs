@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::Extension;
+use tokio_util::io::ReaderStream;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::asset_model::NewAssetModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::services::asset_archive_service::ArchiveFile;
+
+pub async fn download_asset_archive_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+) -> Result<impl IntoResponse> {
+    println!("->> {:<12} - download_asset_archive_api_handler", "HANDLER");
+
+    let (folder, descendants) = state.asset_service
+        .folder_contents(&state.db, &asset_id, &logged_in_user)
+        .await?;
+
+    let total_size = descendants.iter().map(|asset_model| asset_model.file_size).sum();
+    state.asset_archive_service.validate_download_size(total_size)?;
+
+    let archive_file = state.asset_archive_service
+        .write_archive(archive_entry_names(&folder, descendants))
+        .await?;
+    let body = Body::from_stream(ReaderStream::new(archive_file));
+
+    let file_name = folder.name.replace(['"', '\\'], "_");
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, String::from("application/zip")),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{file_name}.zip\"")),
+        ],
+        body
+    ))
+}
+
+/// The files named as uploaded and relative to the folder. Files which would end up with the
+/// same name get a number added.
+fn archive_entry_names(folder: &NewAssetModel, descendants: Vec<NewAssetModel>) -> Vec<ArchiveFile> {
+    let mut folder_paths: HashMap<String, String> = HashMap::from([(folder.id.clone(), String::new())]);
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut files: Vec<ArchiveFile> = vec![];
+
+    for asset_model in descendants {
+        let Some(parent_path) = folder_paths.get(&asset_model.parent_id).cloned() else {
+            continue;
+        };
+        let name = if asset_model.original_name.is_empty() { &asset_model.name } else { &asset_model.original_name };
+        let entry_name = format!("{parent_path}{name}");
+
+        if asset_model.asset_type == "FOLDER" {
+            folder_paths.insert(asset_model.id.clone(), format!("{entry_name}/"));
+            continue;
+        }
+
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+            _ => (name.as_str(), String::new()),
+        };
+        let mut unique_name = entry_name;
+        let mut copy = 1;
+        while !used_names.insert(unique_name.clone()) {
+            copy += 1;
+            unique_name = format!("{parent_path}{stem} ({copy}){extension}");
+        }
+
+        files.push(ArchiveFile {
+            entry_name: unique_name,
+            key: asset_model.path,
+            is_private: asset_model.is_private,
+        });
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Write};
+    use axum::http::{header, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use zip::write::SimpleFileOptions;
+    use zip::{ZipArchive, ZipWriter};
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    fn zip_of(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut zip_writer = ZipWriter::new(Cursor::new(vec![]));
+        for (name, data) in entries {
+            zip_writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip_writer.write_all(data).unwrap();
+        }
+
+        zip_writer.finish().unwrap().into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_asset_archive_api_handlers() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let archive = zip_of(&[
+            ("catalog/summer/a.txt", b"hello"),
+            ("catalog/prices.csv", b"name,price\n"),
+            ("catalog/binary.png", &[0x00, 0x01, 0xFE, 0xFF]),
+        ]);
+        let mut request = send_asset_upload_request("catalog.zip", "application/zip", archive, token.clone());
+        *request.uri_mut() = "/api/asset-archive".parse().unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        // every entry is checked like a single upload
+        assert_eq!(body["status"], false);
+        let results = body["data"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["success"], true);
+        assert_eq!(results[1]["success"], true);
+        assert_eq!(results[2]["path"], "catalog/binary.png");
+        assert_eq!(results[2]["success"], false);
+        let folder_id = results[0]["asset_model"]["parent_id"].as_str().unwrap().to_string();
+
+        let traversal = zip_of(&[("../../etc/a.txt", b"hello")]);
+        let mut request = send_asset_upload_request("traversal.zip", "application/zip", traversal, token.clone());
+        *request.uri_mut() = "/api/asset-archive".parse().unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/api/asset-archive/{folder_id}"), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/zip");
+        assert_eq!(response.headers()[header::CONTENT_DISPOSITION], "attachment; filename=\"summer.zip\"");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(body.to_vec())).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut content = String::new();
+        archive.by_name("a.txt").unwrap().read_to_string(&mut content)?;
+        assert_eq!(content, "hello");
+
+        Ok(())
+    }
+}
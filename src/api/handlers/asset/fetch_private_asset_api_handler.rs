@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::asset_model::NewAssetModel;
+use crate::models::token_claim_model::LoggedInUser;
+
+pub async fn fetch_private_asset_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+) -> Result<Response> {
+    println!("->> {:<12} - fetch_private_asset_api_handler", "HANDLER");
+
+    let (asset_model, data) = state
+        .asset_service
+        .file_data(&state.db, &asset_id, Some(&logged_in_user))
+        .await?;
+
+    Ok(file_response(&asset_model, data))
+}
+
+/// The file with headers which keep it out of shared caches.
+pub fn file_response(asset_model: &NewAssetModel, data: Vec<u8>) -> Response {
+    let file_name = match asset_model.original_name.is_empty() {
+        true => &asset_model.name,
+        false => &asset_model.original_name,
+    };
+    let file_name = file_name.replace(|c: char| c == '"' || c == '\\' || c.is_control(), "_");
+
+    (
+        [
+            (header::CONTENT_TYPE, asset_model.metadata.get_file_metadata().file_type),
+            (header::CONTENT_DISPOSITION, format!("inline; filename=\"{file_name}\"")),
+            (header::CACHE_CONTROL, String::from("private, no-store")),
+            (header::X_CONTENT_TYPE_OPTIONS, String::from("nosniff")),
+        ],
+        data
+    ).into_response()
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use axum::extract::{Path, Query, State};
+use axum::response::Response;
+use crate::api::handlers::asset::fetch_private_asset_api_handler::file_response;
+use crate::api::handlers::asset::request::signed_asset_request::SignedAssetRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+
+pub async fn fetch_signed_asset_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Query(query_param): Query<SignedAssetRequest>,
+) -> Result<Response> {
+    println!("->> {:<12} - fetch_signed_asset_api_handler", "HANDLER");
+
+    if !state.asset_service.verify_signed_url(&asset_id, query_param.expires, &query_param.signature) {
+        return Err(Error::Forbidden);
+    }
+
+    let (asset_model, data) = state
+        .asset_service
+        .file_data(&state.db, &asset_id, None)
+        .await?;
+
+    Ok(file_response(&asset_model, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_authenticated_post_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn into_json(response: axum::response::Response) -> (StatusCode, Value) {
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    fn uri_of(url: &str) -> String {
+        let path_start = url.find("://").map(|scheme_end| scheme_end + 3).unwrap_or(0);
+        let path_start = path_start + url[path_start..].find('/').unwrap();
+
+        url[path_start..].to_string()
+    }
+
+    #[tokio::test]
+    async fn test_private_asset_api_handlers() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let request = send_asset_upload_request("whitepaper.txt", "text/plain", b"gated".to_vec(), token.clone());
+        let (_, body) = into_json(app.clone().oneshot(request).await.unwrap()).await;
+        let asset_id = body["asset_model"]["id"].as_str().unwrap().to_string();
+        let path = body["asset_model"]["path"].as_str().unwrap().to_string();
+        let public_uri = uri_of(body["asset_model"]["url"].as_str().unwrap());
+
+        let request = send_authenticated_post_request(
+            &format!("/api/asset-visibility/{asset_id}"),
+            Body::from(r#"{"is_private": true}"#),
+            token.clone()
+        );
+        let (status, body) = into_json(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["is_private"], true);
+        let private_uri = uri_of(body["data"]["url"].as_str().unwrap());
+        assert_eq!(private_uri, format!("/api/private-asset/{asset_id}"));
+
+        // the file left the public folder
+        assert!(!Path::new("public/upload").join(&path).exists());
+        let response = app.clone().oneshot(send_get_request(&public_uri, String::new())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app.clone().oneshot(send_get_request(&private_uri, String::new())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app.clone().oneshot(send_get_request(&private_uri, token.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"gated");
+
+        let asset_model = state.asset_service.find_by_id(&state.db, &asset_id).await?;
+        let signed_uri = uri_of(&state.asset_service.signed_url(&asset_model, Some(60))?.url);
+        let response = app.clone().oneshot(send_get_request(&signed_uri, String::new())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let tampered_uri = signed_uri.replace("expires=", "expires=1");
+        let response = app.clone().oneshot(send_get_request(&tampered_uri, String::new())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let expired_uri = uri_of(&state.asset_service.signed_url(&asset_model, Some(-10))?.url);
+        let response = app.clone().oneshot(send_get_request(&expired_uri, String::new())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let request = send_authenticated_post_request(
+            &format!("/api/asset-visibility/{asset_id}"),
+            Body::from(r#"{"is_private": false}"#),
+            token
+        );
+        app.clone().oneshot(request).await.unwrap();
+        let response = app.oneshot(send_get_request(&public_uri, String::new())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
+}
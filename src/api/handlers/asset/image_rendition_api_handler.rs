@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use crate::api::handlers::asset::request::image_rendition_request::ImageRenditionRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use rust_i18n::t;
+
+pub async fn image_rendition_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Query(query_param): Query<ImageRenditionRequest>,
+) -> Result<impl IntoResponse> {
+    println!("->> {:<12} - image_rendition_api_handler", "HANDLER");
+
+    let error_messages = query_param.validate(&state.image_service)?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let asset_model = state.asset_service
+        .find_by_id(&state.db, &asset_id)
+        .await?;
+    // private images are only handed out as files, never as public renditions
+    if !state.image_service.is_image(&asset_model) || asset_model.is_private {
+        return Err(Error::NotFound(format!("asset {asset_id} is not an image")));
+    }
+
+    let has_crop = |name: &str| asset_model.crops.iter().any(|crop| crop.name == name);
+    if query_param.crop.as_deref().is_some_and(|crop| !has_crop(crop)) {
+        return Err(Error::BadRequest(ErrorResponse {
+            status: false,
+            errors: vec![ErrorMessage {
+                key: String::from("crop"),
+                message: t!("image_crop_invalid").to_string()
+            }]
+        }));
+    }
+
+    let rendition = query_param.into_rendition(&state.image_service);
+    let (data, output_format) = state.image_service
+        .rendition(&asset_model, &rendition)
+        .await?;
+
+    // `Cache-Control` and the 304 responses come from the static file cache layer of the route
+    let etag = format!(
+        "\"{}-{}.{}\"",
+        asset_model.checksum,
+        state.image_service.rendition_key(&asset_model, &rendition),
+        output_format.as_str()
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, output_format.content_type().to_string()),
+            (header::ETAG, etag),
+        ],
+        data
+    ))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::path::Path;
+    use axum::http::{header, StatusCode};
+    use image::{ImageFormat, RgbImage};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_image_rendition_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let mut png = Cursor::new(Vec::new());
+        RgbImage::new(300, 200).write_to(&mut png, ImageFormat::Png)?;
+        let response = app.clone()
+            .oneshot(send_asset_upload_request("photo.png", "image/png", png.into_inner(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let asset_id = body["asset_model"]["id"].as_str().unwrap().to_string();
+
+        // presets are generated at upload
+        assert!(Path::new("cache/image").join(&asset_id).join("150x150-cover.png").exists());
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/public/image/{asset_id}?w=100&h=100&fit=cover&format=webp"), String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/webp");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let rendition = image::load_from_memory_with_format(&body, ImageFormat::WebP)?;
+        assert_eq!((rendition.width(), rendition.height()), (100, 100));
+        assert!(Path::new("cache/image").join(&asset_id).join("100x100-cover.webp").exists());
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/public/image/{asset_id}?w=0&fit=stretch"), String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // sizes outside of the presets have to be a multiple of the size step
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/public/image/{asset_id}?w=150"), String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .oneshot(send_get_request("/public/image/missing?preset=thumbnail", String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
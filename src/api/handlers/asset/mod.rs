@@ -4,4 +4,18 @@ pub mod create_folder_api_handler;
 pub mod delete_folder_api_handler;
 pub mod rename_asset_api_handler;
 pub mod delete_asset_api_handler;
-pub mod request;
\ No newline at end of file
+pub mod request;
+pub mod image_rendition_api_handler;
+pub mod update_asset_api_handler;
+pub mod move_asset_api_handler;
+pub mod bulk_delete_asset_api_handler;
+pub mod bulk_move_asset_api_handler;
+pub mod bulk_tag_asset_api_handler;
+pub mod asset_usage_api_handler;
+pub mod asset_duplicate_api_handler;
+pub mod store_asset_archive_api_handler;
+pub mod download_asset_archive_api_handler;
+pub mod asset_search_api_handler;
+pub mod update_asset_visibility_api_handler;
+pub mod fetch_private_asset_api_handler;
+pub mod fetch_signed_asset_api_handler;
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use crate::api::handlers::asset::request::move_asset_request::MoveAssetRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::asset_model::NewAssetModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn move_asset_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    Json(payload): Json<MoveAssetRequest>,
+) -> Result<Json<ApiResponse<NewAssetModel>>> {
+    println!("->> {:<12} - move_asset_api_handler", "HANDLER");
+
+    let moved_asset_model = state.asset_service
+        .move_asset(&state.db, &asset_id, &payload.parent_id, &logged_in_user)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: moved_asset_model
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use axum::body::Body;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use axum::http::StatusCode;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_authenticated_post_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn send_json(app: axum::Router, uri: &str, body: String, token: &str) -> (StatusCode, Value) {
+        let response = app
+            .oneshot(send_authenticated_post_request(uri, Body::from(body), token.to_string()))
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_move_asset_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let (_, body) = send_json(app.clone(), "/api/create-folder", r#"{"name": "move-source"}"#.to_string(), &token).await;
+        let source_id = body["data"]["id"].as_str().unwrap().to_string();
+        let (_, body) = send_json(app.clone(), "/api/create-folder", r#"{"name": "move-target"}"#.to_string(), &token).await;
+        let target_id = body["data"]["id"].as_str().unwrap().to_string();
+        let (_, body) = send_json(
+            app.clone(),
+            "/api/create-folder",
+            format!(r#"{{"name": "nested", "parent_id": "{source_id}"}}"#),
+            &token
+        ).await;
+        let nested_id = body["data"]["id"].as_str().unwrap().to_string();
+
+        let uri = format!("/api/asset?parent_id={nested_id}");
+        let mut request = send_asset_upload_request("notes.txt", "text/plain", b"moving".to_vec(), token.clone());
+        *request.uri_mut() = uri.parse().unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let file_name = body["asset_model"]["name"].as_str().unwrap().to_string();
+
+        // a folder can not go below itself
+        let (status, body) = send_json(
+            app.clone(),
+            &format!("/api/move-asset/{source_id}"),
+            format!(r#"{{"parent_id": "{nested_id}"}}"#),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["key"], "parent_id");
+
+        let (status, body) = send_json(
+            app.clone(),
+            &format!("/api/move-asset/{source_id}"),
+            format!(r#"{{"parent_id": "{target_id}"}}"#),
+            &token
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["path"], "move-target/move-source");
+        assert_eq!(body["data"]["parent_id"], target_id.as_str());
+
+        let moved_file = Path::new("public/upload/move-target/move-source/nested").join(&file_name);
+        assert!(moved_file.exists());
+        assert!(!Path::new("public/upload/move-source").exists());
+
+        let (status, body) = send_json(app, &format!("/api/rename-asset/{nested_id}"), r#"{"name": "inner"}"#.to_string(), &token).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["path"], "move-target/move-source/inner");
+        assert!(Path::new("public/upload/move-target/move-source/inner").join(&file_name).exists());
+
+        std::fs::remove_dir_all("public/upload/move-target")?;
+
+        Ok(())
+    }
+}
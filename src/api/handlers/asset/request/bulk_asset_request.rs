@@ -0,0 +1,99 @@
+use serde::Deserialize;
+use rust_i18n::t;
+use crate::api::handlers::asset::request::update_asset_request::{normalize_tags, TAG_MAX_LENGTH};
+use crate::models::validation_error::ErrorMessage;
+
+const BULK_ASSET_MAX_IDS: usize = 500;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BulkDeleteAssetRequest {
+    #[serde(default)]
+    pub asset_ids: Vec<String>,
+    /// Folders with content are only deleted when this is set, with all their content.
+    #[serde(default)]
+    pub recursive: bool,
+    /// Files which are used somewhere are only deleted when this is set.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// An empty parent id moves the assets to the upload root.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BulkMoveAssetRequest {
+    #[serde(default)]
+    pub asset_ids: Vec<String>,
+    #[serde(default)]
+    pub parent_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BulkTagAssetRequest {
+    #[serde(default)]
+    pub asset_ids: Vec<String>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+}
+
+impl BulkDeleteAssetRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        Ok(validate_asset_ids(&self.asset_ids))
+    }
+}
+
+impl BulkMoveAssetRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        Ok(validate_asset_ids(&self.asset_ids))
+    }
+}
+
+impl BulkTagAssetRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors = validate_asset_ids(&self.asset_ids);
+
+        if self.add_tags().is_empty() && self.remove_tags().is_empty() {
+            errors.push(ErrorMessage {
+                key: String::from("tags"),
+                message: t!("validation_required", attribute = t!("tags")).to_string()
+            });
+        }
+
+        if self.add_tags.iter().any(|tag| tag.trim().chars().count() > TAG_MAX_LENGTH) {
+            errors.push(ErrorMessage {
+                key: String::from("add_tags"),
+                message: t!("validation_max_length", attribute = t!("tags"), max = TAG_MAX_LENGTH).to_string()
+            });
+        }
+
+        Ok(errors)
+    }
+
+    pub fn add_tags(&self) -> Vec<String> {
+        normalize_tags(&self.add_tags)
+    }
+
+    pub fn remove_tags(&self) -> Vec<String> {
+        normalize_tags(&self.remove_tags)
+    }
+}
+
+fn validate_asset_ids(asset_ids: &[String]) -> Vec<ErrorMessage> {
+    let mut errors: Vec<ErrorMessage> = vec![];
+
+    if asset_ids.iter().all(|asset_id| asset_id.trim().is_empty()) {
+        errors.push(ErrorMessage {
+            key: String::from("asset_ids"),
+            message: t!("validation_required", attribute = t!("asset_ids")).to_string()
+        });
+    }
+
+    if asset_ids.len() > BULK_ASSET_MAX_IDS {
+        errors.push(ErrorMessage {
+            key: String::from("asset_ids"),
+            message: t!("asset_bulk_too_many", max = BULK_ASSET_MAX_IDS).to_string()
+        });
+    }
+
+    errors
+}
@@ -22,6 +22,13 @@ impl CreateFolderRequest {
             errors.push(error_message);
         }
 
+        if self.name.contains(['/', '\\']) || self.name == "." || self.name == ".." {
+            errors.push(ErrorMessage {
+                key: String::from("name"),
+                message: t!("asset_name_invalid").to_string()
+            });
+        }
+
         Ok(errors)
     }
 }
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// Files which are used somewhere are only deleted when forced.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DeleteAssetRequest {
+    #[serde(default)]
+    pub force: bool,
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/// A dry run only lists what would be deleted, forcing deletes files which are still used.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DeleteFolderRequest {
+    #[serde(default)]
+    pub recursive: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub force: bool,
+}
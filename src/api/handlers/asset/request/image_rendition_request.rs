@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use rust_i18n::t;
+use crate::models::image_rendition_model::{ImageFit, ImageOutputFormat, ImageRenditionModel};
+use crate::models::validation_error::ErrorMessage;
+use crate::services::image_service::ImageService;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ImageRenditionRequest {
+    pub w: Option<String>,
+    pub h: Option<String>,
+    pub fit: Option<String>,
+    pub format: Option<String>,
+    pub preset: Option<String>,
+    pub crop: Option<String>,
+}
+
+impl ImageRenditionRequest {
+    pub fn validate(&self, image_service: &ImageService) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        for (key, value) in [("w", &self.w), ("h", &self.h)] {
+            let Some(value) = value else { continue };
+            let is_valid = value
+                .parse::<u32>()
+                .is_ok_and(|size| {
+                    size > 0 && size <= image_service.max_dimension() && size % image_service.size_step() == 0
+                });
+            if !is_valid {
+                errors.push(ErrorMessage {
+                    key: String::from(key),
+                    message: t!(
+                        "image_dimension_invalid",
+                        step = image_service.size_step(),
+                        max = image_service.max_dimension()
+                    ).to_string()
+                });
+            }
+        }
+
+        if self.fit.as_deref().is_some_and(|fit| ImageFit::from_identifier(fit).is_none()) {
+            errors.push(ErrorMessage {
+                key: String::from("fit"),
+                message: t!("image_fit_invalid").to_string()
+            });
+        }
+
+        if self.format.as_deref().is_some_and(|format| ImageOutputFormat::from_identifier(format).is_none()) {
+            errors.push(ErrorMessage {
+                key: String::from("format"),
+                message: t!("image_format_invalid").to_string()
+            });
+        }
+
+        if self.crop.as_deref().is_some_and(|crop| crop.trim().is_empty()) {
+            errors.push(ErrorMessage {
+                key: String::from("crop"),
+                message: t!("image_crop_invalid").to_string()
+            });
+        }
+
+        if self.preset.as_deref().is_some_and(|preset| image_service.find_preset(preset).is_none()) {
+            errors.push(ErrorMessage {
+                key: String::from("preset"),
+                message: t!("image_preset_invalid").to_string()
+            });
+        }
+
+        Ok(errors)
+    }
+
+    /// A preset gives the size and fit, an explicit format still applies on top of it. Presets
+    /// use the crop of the asset named like them unless a crop is asked for.
+    pub fn into_rendition(self, image_service: &ImageService) -> ImageRenditionModel {
+        let format = self.format.as_deref().and_then(ImageOutputFormat::from_identifier);
+
+        if let Some(preset) = self.preset.as_deref().and_then(|preset| image_service.find_preset(preset)) {
+            return ImageRenditionModel {
+                format,
+                crop: self.crop.or_else(|| Some(preset.name.clone())),
+                ..preset.rendition.clone()
+            };
+        }
+
+        ImageRenditionModel {
+            width: self.w.and_then(|w| w.parse().ok()),
+            height: self.h.and_then(|h| h.parse().ok()),
+            fit: self.fit.as_deref().and_then(ImageFit::from_identifier).unwrap_or_default(),
+            format,
+            crop: self.crop,
+        }
+    }
+}
@@ -1,3 +1,13 @@
 pub mod create_folder_request;
 pub mod rename_asset_request;
-pub mod store_asset_request;
\ No newline at end of file
+pub mod store_asset_request;
+pub mod image_rendition_request;
+pub mod update_asset_request;
+pub mod move_asset_request;
+pub mod bulk_asset_request;
+pub mod delete_folder_request;
+pub mod delete_asset_request;
+pub mod store_asset_archive_request;
+pub mod search_asset_request;
+pub mod update_asset_visibility_request;
+pub mod signed_asset_request;
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// An empty parent id moves the asset to the upload root.
+#[derive(Deserialize, Debug, Clone)]
+pub struct MoveAssetRequest {
+    #[serde(default)]
+    pub parent_id: String,
+}
@@ -21,6 +21,14 @@ impl RenameAssetRequest {
             errors.push(error_message);
         }
 
+        // the name becomes the last part of the storage key
+        if self.name.contains(['/', '\\']) || self.name == "." || self.name == ".." {
+            errors.push(ErrorMessage {
+                key: String::from("name"),
+                message: t!("asset_name_invalid").to_string()
+            });
+        }
+
         Ok(errors)
     }
 }
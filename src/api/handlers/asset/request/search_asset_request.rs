@@ -0,0 +1,110 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
+use rust_i18n::t;
+use surrealdb::sql::Datetime;
+use crate::models::asset_model::AssetSearchModel;
+use crate::models::validation_error::ErrorMessage;
+
+/// Columns the search results can be ordered by.
+pub const SEARCH_ORDER_COLUMNS: [&str; 7] = ["name", "original_name", "file_size", "width", "height", "created_at", "updated_at"];
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchAssetRequest {
+    pub page: Option<i64>,
+    /// `column:ASC` or `column:DESC`, the newest assets come first by default.
+    pub order: Option<String>,
+    pub q: Option<String>,
+    pub tag: Option<String>,
+    pub file_type: Option<String>,
+    /// A date like `2024-01-31` or a RFC 3339 date and time, both ends are included.
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+    pub created_by: Option<String>,
+    pub min_width: Option<i64>,
+    pub max_width: Option<i64>,
+    pub min_height: Option<i64>,
+    pub max_height: Option<i64>,
+}
+
+impl SearchAssetRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if self.order.is_some() && self.order_by().is_none() {
+            errors.push(ErrorMessage {
+                key: String::from("order"),
+                message: t!("asset_search_order_invalid", columns = SEARCH_ORDER_COLUMNS.join(", ")).to_string()
+            });
+        }
+
+        for (key, value) in [("created_from", &self.created_from), ("created_to", &self.created_to)] {
+            if value.as_deref().is_some_and(|value| parse_date(value, false).is_none()) {
+                errors.push(ErrorMessage {
+                    key: String::from(key),
+                    message: t!("asset_search_date_invalid", attribute = t!(key)).to_string()
+                });
+            }
+        }
+
+        Ok(errors)
+    }
+
+    pub fn current_page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn into_search(self) -> AssetSearchModel {
+        let (order_column, order_type) = self.order_by().unwrap_or(("created_at", "DESC"));
+
+        AssetSearchModel {
+            query: non_blank(&self.q).map(|query| query.to_lowercase()),
+            tag: non_blank(&self.tag),
+            file_type: non_blank(&self.file_type),
+            created_from: self.created_from.as_deref().and_then(|value| parse_date(value, false)),
+            created_to: self.created_to.as_deref().and_then(|value| parse_date(value, true)),
+            created_by: non_blank(&self.created_by),
+            min_width: self.min_width,
+            max_width: self.max_width,
+            min_height: self.min_height,
+            max_height: self.max_height,
+            order_column: order_column.to_string(),
+            order_type: order_type.to_string(),
+        }
+    }
+
+    fn order_by(&self) -> Option<(&str, &str)> {
+        let (column, order_type) = self.order.as_deref()?.split_once(':')?;
+        let order_type = ["ASC", "DESC"]
+            .into_iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(order_type))?;
+
+        SEARCH_ORDER_COLUMNS
+            .into_iter()
+            .find(|allowed| *allowed == column)
+            .map(|column| (column, order_type))
+    }
+}
+
+fn non_blank(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+/// A plain date covers the whole day, so as an upper bound it stands for its last moment.
+fn parse_date(value: &str, end_of_day: bool) -> Option<Datetime> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Some(Datetime::from(date_time.with_timezone(&Utc)));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let time = if end_of_day {
+        NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999)?
+    } else {
+        NaiveTime::MIN
+    };
+
+    Some(Datetime::from(date.and_time(time).and_utc()))
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// The query of a signed url.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SignedAssetRequest {
+    pub expires: i64,
+    pub signature: String,
+}
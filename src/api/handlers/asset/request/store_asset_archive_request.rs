@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StoreAssetArchiveRequest {
+    pub parent_id: Option<String>,
+}
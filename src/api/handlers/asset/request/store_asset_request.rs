@@ -1,6 +1,33 @@
 use serde::Deserialize;
+use rust_i18n::t;
+use crate::models::validation_error::ErrorMessage;
+
+/// Stores an identical upload next to the existing files and lists them as duplicates.
+pub const ON_DUPLICATE_WARN: &str = "warn";
+/// Responds with the existing file instead of storing an identical upload.
+pub const ON_DUPLICATE_EXISTING: &str = "existing";
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct StoreAssetRequest {
-    pub parent_id: Option<String>
-}
\ No newline at end of file
+    pub parent_id: Option<String>,
+    pub on_duplicate: Option<String>,
+}
+
+impl StoreAssetRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !matches!(self.on_duplicate.as_deref(), None | Some(ON_DUPLICATE_WARN) | Some(ON_DUPLICATE_EXISTING)) {
+            errors.push(ErrorMessage {
+                key: String::from("on_duplicate"),
+                message: t!("asset_on_duplicate_invalid").to_string()
+            });
+        }
+
+        Ok(errors)
+    }
+
+    pub fn reuses_existing(&self) -> bool {
+        self.on_duplicate.as_deref() == Some(ON_DUPLICATE_EXISTING)
+    }
+}
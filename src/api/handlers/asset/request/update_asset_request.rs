@@ -0,0 +1,106 @@
+use serde::Deserialize;
+use rust_i18n::t;
+use crate::models::image_rendition_model::{FocalPointModel, ImageCropModel};
+use crate::models::validation_error::ErrorMessage;
+
+const ALT_TEXT_MAX_LENGTH: usize = 255;
+const CAPTION_MAX_LENGTH: usize = 1000;
+pub const TAG_MAX_LENGTH: usize = 50;
+const CROPS_MAX_COUNT: usize = 20;
+const CROP_NAME_MAX_LENGTH: usize = 50;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct UpdateAssetRequest {
+    #[serde(default)]
+    pub alt_text: String,
+    #[serde(default)]
+    pub caption: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub focal_point: Option<FocalPointModel>,
+    #[serde(default)]
+    pub crops: Vec<ImageCropModel>,
+}
+
+impl UpdateAssetRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if self.alt_text.chars().count() > ALT_TEXT_MAX_LENGTH {
+            errors.push(ErrorMessage {
+                key: String::from("alt_text"),
+                message: t!("validation_max_length", attribute = t!("alt_text"), max = ALT_TEXT_MAX_LENGTH).to_string()
+            });
+        }
+
+        if self.caption.chars().count() > CAPTION_MAX_LENGTH {
+            errors.push(ErrorMessage {
+                key: String::from("caption"),
+                message: t!("validation_max_length", attribute = t!("caption"), max = CAPTION_MAX_LENGTH).to_string()
+            });
+        }
+
+        if self.tags.iter().any(|tag| tag.trim().chars().count() > TAG_MAX_LENGTH) {
+            errors.push(ErrorMessage {
+                key: String::from("tags"),
+                message: t!("validation_max_length", attribute = t!("tags"), max = TAG_MAX_LENGTH).to_string()
+            });
+        }
+
+        let is_in_image = |value: f64| (0.0..=1.0).contains(&value);
+        if self.focal_point.is_some_and(|focal_point| !is_in_image(focal_point.x) || !is_in_image(focal_point.y)) {
+            errors.push(ErrorMessage {
+                key: String::from("focal_point"),
+                message: t!("image_focal_point_invalid").to_string()
+            });
+        }
+
+        if self.crops.len() > CROPS_MAX_COUNT {
+            errors.push(ErrorMessage {
+                key: String::from("crops"),
+                message: t!("image_crops_too_many", max = CROPS_MAX_COUNT).to_string()
+            });
+        }
+
+        // names end up in urls, so they are kept to what needs no escaping
+        let is_valid_name = |name: &str| {
+            !name.is_empty()
+                && name.len() <= CROP_NAME_MAX_LENGTH
+                && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        };
+        let has_invalid_name = self.crops.iter().enumerate().any(|(index, crop)| {
+            !is_valid_name(&crop.name) || self.crops[..index].iter().any(|other| other.name == crop.name)
+        });
+        if has_invalid_name {
+            errors.push(ErrorMessage {
+                key: String::from("crops"),
+                message: t!("image_crop_name_invalid").to_string()
+            });
+        }
+
+        if self.crops.iter().any(|crop| crop.width == 0 || crop.height == 0) {
+            errors.push(ErrorMessage {
+                key: String::from("crops"),
+                message: t!("image_crop_size_invalid").to_string()
+            });
+        }
+
+        Ok(errors)
+    }
+
+    pub fn normalized_tags(&self) -> Vec<String> {
+        normalize_tags(&self.tags)
+    }
+}
+
+/// Trimmed tags without blanks and duplicates, in the given order.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized_tags: Vec<String> = vec![];
+    for tag in tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !normalized_tags.iter().any(|existing| existing == tag) {
+            normalized_tags.push(tag.to_string());
+        }
+    }
+
+    normalized_tags
+}
@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UpdateAssetVisibilityRequest {
+    pub is_private: bool,
+}
@@ -1,20 +1,24 @@
+use std::io::Cursor;
 use std::sync::Arc;
 
 use crate::{
     avored_state::AvoRedState,
-    error::Result
+    error::{Error, Result}
 };
 use axum::{Extension, extract::State, Json, response::IntoResponse};
 use axum::extract::{Multipart, Query};
+use image::ImageReader;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
+use rust_i18n::t;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::log::error;
 use crate::api::handlers::asset::request::store_asset_request::StoreAssetRequest;
-use crate::error::Error;
 use crate::models::asset_model::{CreatableAssetModelNew, MetaDataType, NewAssetModel};
 use crate::models::token_claim_model::LoggedInUser;
-
-const ALLOW_TYPES: [&str; 3] = ["image/jpeg", "image/jpg", "image/png"];
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::services::asset_upload_service::{detect_file_type, sanitize_svg, DetectedFileType};
 
 pub async fn store_asset_api_handler(
     Extension(logged_in_user): Extension<LoggedInUser>,
@@ -24,104 +28,283 @@ pub async fn store_asset_api_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - store_asset_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("asset_create"))
+    let error_messages = query_param.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let query_parent_id = query_param.parent_id.clone().unwrap_or_default();
+    state
+        .asset_service
+        .authorize_parent(&state.db, &query_parent_id, &logged_in_user)
         .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
+    let mut upload: Option<(String, Vec<u8>)> = None;
+
+    while let Some(field) = multipart.next_field().await? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            let file_name = field.file_name().unwrap_or_default().to_string();
+            upload = Some((file_name, field.bytes().await?.to_vec()));
+        }
     }
 
+    let Some((file_name, data)) = upload else {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: vec![ErrorMessage {
+                key: String::from("file"),
+                message: t!("validation_required", attribute = t!("file")).to_string()
+            }]
+        };
+
+        return Err(Error::BadRequest(error_response));
+    };
+
+    let (detected_file_type, data) = validate_upload(&state, &file_name, data)?;
+
+    let checksum = hex::encode(Sha256::digest(&data));
+    let duplicates = state
+        .asset_service
+        .find_duplicates(&state.db, &checksum, &query_parent_id, &logged_in_user)
+        .await?;
+
+    if query_param.reuses_existing() {
+        if let Some(existing_asset_model) = duplicates.first() {
+            let existing_asset_response = AssetResponseViewModel {
+                asset_model: existing_asset_model.clone(),
+                success: true,
+                is_existing: true,
+                duplicates: vec![]
+            };
+
+            return Ok(Json(existing_asset_response).into_response());
+        }
+    }
+
+    let asset_model = store_upload(&state, &query_parent_id, file_name, detected_file_type, data, &logged_in_user).await?;
+
+    let creatable_asset_response = AssetResponseViewModel {
+        asset_model,
+        success: true,
+        is_existing: false,
+        duplicates
+    };
+
+    Ok(Json(creatable_asset_response).into_response())
+}
+
+/// Checks the upload against the allowed types and sizes, svgs come back sanitized.
+pub fn validate_upload(
+    state: &AvoRedState,
+    file_name: &str,
+    mut data: Vec<u8>
+) -> Result<(DetectedFileType, Vec<u8>)> {
+    let detected_file_type = detect_file_type(file_name, &data);
+    let mut error_messages = state
+        .asset_upload_service
+        .validate_asset(detected_file_type, data.len());
+
+    if detected_file_type.is_some_and(|detected| detected.mime_type == "image/svg+xml") {
+        match sanitize_svg(&data) {
+            Some(sanitized) => data = sanitized,
+            None => error_messages.push(ErrorMessage {
+                key: String::from("file"),
+                message: t!("asset_svg_invalid").to_string()
+            }),
+        }
+    }
+
+    match detected_file_type {
+        Some(detected_file_type) if error_messages.is_empty() => Ok((detected_file_type, data)),
+        _ => {
+            let error_response = ErrorResponse {
+                status: false,
+                errors: error_messages
+            };
+
+            Err(Error::BadRequest(error_response))
+        }
+    }
+}
+
+/// Stores a validated upload below the parent under a random name and creates its asset.
+pub async fn store_upload(
+    state: &AvoRedState,
+    parent_id: &str,
+    file_name: String,
+    detected_file_type: DetectedFileType,
+    data: Vec<u8>,
+    logged_in_user: &LoggedInUser
+) -> Result<NewAssetModel> {
     //@todo we need to move this logic to service
     // we need to make the parent_id works as per it path will be changed too. if exist
     let mut creatable_asset_model = CreatableAssetModelNew {
-        logged_in_username: logged_in_user.email,
+        logged_in_username: logged_in_user.email.clone(),
         .. Default::default()
     };
-    let mut is_allow_file_type = true;
-
-    while let Some(field) = multipart.next_field().await.unwrap() {
-        let name = field.name().unwrap().to_string();
-        match name.as_ref() {
-            "file" => {
-                let s: String = rand::thread_rng()
-                    .sample_iter(&Alphanumeric)
-                    .take(16)
-                    .map(char::from)
-                    .collect();
-
-                let file_type = field.content_type().unwrap().to_string();
-
-                // file type should be part of metadata
-                // creatable_asset_model.file_type = field.content_type().unwrap().to_string();
-
-                is_allow_file_type = ALLOW_TYPES.contains(&file_type.as_str());
-
-                if !is_allow_file_type {
-                    continue;
-                }
-                let file_name = field.file_name().unwrap().to_string();
-                let data = field.bytes().await.unwrap();
-                // file size should be part of metadata
-                // creatable_asset_model.file_size = i64::try_from(data.len()).unwrap_or(0);
-
-                if !file_name.is_empty() {
-                    let file_ext = file_name.split('.').last().unwrap_or(".png");
-                    let new_file_name = format!("{}.{}", s, file_ext);
-                    let query_parent_id = query_param.parent_id.clone().unwrap_or_default();
-                    let asset_file;
-
-                    if !query_parent_id.is_empty() {
-                        let parent_asset = &state
-                            .asset_service
-                            .find_by_id(&state.db, &query_parent_id)
-                            .await?;
-
-                        creatable_asset_model.parent_id = query_parent_id;
-                        asset_file = format!("{}/{}", parent_asset.path, new_file_name.clone());
-                    } else {
-                        asset_file = format!("public/upload/{}", new_file_name.clone());
-                    }
-
-                    creatable_asset_model.name = new_file_name.clone();
-                    creatable_asset_model.path = asset_file.clone();
-                    creatable_asset_model.asset_type = String::from("FILE");
-                    creatable_asset_model.metadata = MetaDataType::FileTypeMetaData {file_type};
-
-                    let full_path = format!("./{}", asset_file);
-
-
-                    tokio::fs::write(full_path, data).await?;
-                }
-            },
-            &_ => continue
-        }
-    }
 
-    if !is_allow_file_type {
-        let asset_model = NewAssetModel::default();
-        let creatable_asset_response = AssetResponseViewModel {
-            asset_model,
-            success: false
-        };
+    let s: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect();
+    // the stored extension follows the detected type, never the name given by the client
+    let new_file_name = format!("{}.{}", s, detected_file_type.extension);
+    let asset_file;
+
+    if !parent_id.is_empty() {
+        let parent_asset = &state
+            .asset_service
+            .find_by_id(&state.db, parent_id)
+            .await?;
 
-        return Ok(Json(creatable_asset_response).into_response())
+        creatable_asset_model.parent_id = parent_id.to_string();
+        asset_file = format!("{}/{}", parent_asset.path, new_file_name.clone());
+    } else {
+        asset_file = new_file_name.clone();
     }
 
+    let (width, height) = ImageReader::new(Cursor::new(&data))
+        .with_guessed_format()
+        .ok()
+        .and_then(|reader| reader.into_dimensions().ok())
+        .unwrap_or_default();
+
+    creatable_asset_model.name = new_file_name.clone();
+    creatable_asset_model.path = asset_file.clone();
+    creatable_asset_model.asset_type = String::from("FILE");
+    creatable_asset_model.metadata = MetaDataType::FileTypeMetaData {
+        file_type: detected_file_type.mime_type.to_string()
+    };
+    creatable_asset_model.original_name = file_name;
+    creatable_asset_model.file_size = i64::try_from(data.len()).unwrap_or(0);
+    creatable_asset_model.width = i64::from(width);
+    creatable_asset_model.height = i64::from(height);
+    creatable_asset_model.checksum = hex::encode(Sha256::digest(&data));
+
+    state
+        .asset_service
+        .store_file(&asset_file, data, detected_file_type.mime_type)
+        .await?;
+
     let asset_model = state.asset_service
-        .create_asset(&state.db, creatable_asset_model)
+        .create_asset(&state.db, creatable_asset_model, logged_in_user)
         .await?;
 
-    let creatable_asset_response = AssetResponseViewModel {
-        asset_model,
-        success: true
-    };
+    if state.image_service.is_image(&asset_model) {
+        if let Err(e) = state.image_service.generate_presets(&asset_model).await {
+            error!("there is an issue while generating the image presets: {e:?}");
+        }
+    }
 
-    Ok(Json(creatable_asset_response).into_response())
+    Ok(asset_model)
 }
 
 #[derive(Serialize)]
 pub struct AssetResponseViewModel {
     pub asset_model: NewAssetModel,
-    pub success: bool
+    pub success: bool,
+    /// The asset was already stored and is returned instead of the upload.
+    pub is_existing: bool,
+    /// Assets with the same content as the upload, which was stored anyway.
+    pub duplicates: Vec<NewAssetModel>
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_store_asset_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        // the client content type and name do not make a binary an image
+        let response = app.clone()
+            .oneshot(send_asset_upload_request("photo.png", "image/png", vec![0x00, 0x01, 0xFE, 0xFF], token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        assert_eq!(body["status"], false);
+        assert_eq!(body["errors"][0]["key"], "file");
+
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" onload="alert(1)"><script>alert(2)</script><rect width="10" height="10" /></svg>"#;
+        let response = app
+            .oneshot(send_asset_upload_request("logo.png", "image/png", svg.to_vec(), token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let asset_model = &body["asset_model"];
+        assert_eq!(asset_model["metadata"]["file_type"], "image/svg+xml");
+        let path = asset_model["path"].as_str().unwrap();
+        assert!(path.ends_with(".svg"));
+        let checksum = asset_model["checksum"].as_str().unwrap();
+        assert!(asset_model["url"].as_str().unwrap().ends_with(&format!("/public/upload/{path}?v={}", &checksum[..16])));
+        let stored = std::fs::read_to_string(format!("public/upload/{path}"))?;
+        assert!(!stored.contains("alert"));
+        assert!(stored.contains("<rect"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_duplicate_asset_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        async fn upload(app: axum::Router, uri: &str, token: &str) -> Value {
+            let mut request = send_asset_upload_request("price-list.csv", "text/csv", b"name,price\n".to_vec(), token.to_string());
+            *request.uri_mut() = uri.parse().unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+            serde_json::from_slice(&body).expect("Failed to parse JSON")
+        }
+
+        let original = upload(app.clone(), "/api/asset", &token).await;
+        assert_eq!(original["duplicates"].as_array().unwrap().len(), 0);
+        let original_id = original["asset_model"]["id"].as_str().unwrap();
+
+        // stored again by default, with a warning
+        let copy = upload(app.clone(), "/api/asset", &token).await;
+        assert_eq!(copy["is_existing"], false);
+        assert_eq!(copy["duplicates"][0]["id"], original_id);
+
+        let existing = upload(app.clone(), "/api/asset?on_duplicate=existing", &token).await;
+        assert_eq!(existing["is_existing"], true);
+        assert_eq!(existing["asset_model"]["id"], original_id);
+
+        let response = app
+            .oneshot(send_get_request("/api/asset-duplicates", token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let group = &body["data"][0];
+        assert_eq!(group["assets"].as_array().unwrap().len(), 2);
+        assert_eq!(group["assets"][0]["id"], original_id);
+        assert_eq!(group["wasted_size"], group["file_size"]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use axum::extract::{Multipart, Query, State};
+use axum::{Extension, Json};
+use rust_i18n::t;
+use crate::api::handlers::asset::request::store_asset_archive_request::StoreAssetArchiveRequest;
+use crate::api::handlers::asset::store_asset_api_handler::{store_upload, validate_upload};
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::asset_model::{ArchiveEntryResultModel, NewAssetModel};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+pub async fn store_asset_archive_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Query(query_param): Query<StoreAssetArchiveRequest>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<Vec<ArchiveEntryResultModel>>>> {
+    println!("->> {:<12} - store_asset_archive_api_handler", "HANDLER");
+
+    let parent_id = query_param.parent_id.unwrap_or_default();
+    state
+        .asset_service
+        .authorize_parent(&state.db, &parent_id, &logged_in_user)
+        .await?;
+
+    let mut archive: Option<Vec<u8>> = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name().unwrap_or_default() == "file" {
+            archive = Some(field.bytes().await?.to_vec());
+        }
+    }
+
+    let Some(archive) = archive else {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: vec![ErrorMessage {
+                key: String::from("file"),
+                message: t!("validation_required", attribute = t!("file")).to_string()
+            }]
+        };
+
+        return Err(Error::BadRequest(error_response));
+    };
+
+    // the whole archive is checked before anything is stored
+    let entries = state.asset_archive_service.extract(archive).await?;
+
+    // folder ids by their path in the archive, the target folder is the empty path
+    let mut folder_ids: HashMap<String, String> = HashMap::from([(String::new(), parent_id)]);
+    let mut results: Vec<ArchiveEntryResultModel> = vec![];
+
+    for entry in entries {
+        let entry_path = entry.path.join("/");
+        let Some(data) = entry.data else {
+            if let Err(e) = folder_id(&state, &mut folder_ids, &entry.path, &logged_in_user).await {
+                results.push(ArchiveEntryResultModel::failed(&entry_path, &e));
+            }
+            continue;
+        };
+
+        let (file_name, folder_path) = entry.path
+            .split_last()
+            .expect("archive entries have a name");
+        let stored = match folder_id(&state, &mut folder_ids, folder_path, &logged_in_user).await {
+            Ok(folder_id) => store_entry(&state, &folder_id, file_name.clone(), data, &logged_in_user).await,
+            Err(e) => Err(e),
+        };
+
+        results.push(match stored {
+            Ok(asset_model) => ArchiveEntryResultModel::succeeded(&entry_path, asset_model),
+            Err(e) => ArchiveEntryResultModel::failed(&entry_path, &e),
+        });
+    }
+
+    let response = ApiResponse {
+        status: results.iter().all(|result| result.success),
+        data: results
+    };
+
+    Ok(Json(response))
+}
+
+/// The id of the folder at the path below the target folder, missing folders are created.
+async fn folder_id(
+    state: &AvoRedState,
+    folder_ids: &mut HashMap<String, String>,
+    folder_path: &[String],
+    logged_in_user: &LoggedInUser
+) -> Result<String> {
+    let mut folder_id = folder_ids[""].clone();
+    for depth in 1..=folder_path.len() {
+        let path = folder_path[..depth].join("/");
+        folder_id = match folder_ids.get(&path) {
+            Some(existing_folder_id) => existing_folder_id.clone(),
+            None => {
+                let folder = state
+                    .asset_service
+                    .find_or_create_folder(&state.db, &folder_path[depth - 1], &folder_id, logged_in_user)
+                    .await?;
+                folder_ids.insert(path, folder.id.clone());
+
+                folder.id
+            },
+        };
+    }
+
+    Ok(folder_id)
+}
+
+async fn store_entry(
+    state: &AvoRedState,
+    folder_id: &str,
+    file_name: String,
+    data: Vec<u8>,
+    logged_in_user: &LoggedInUser
+) -> Result<NewAssetModel> {
+    let (detected_file_type, data) = validate_upload(state, &file_name, data)?;
+
+    store_upload(state, folder_id, file_name, detected_file_type, data, logged_in_user).await
+}
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use tracing::log::error;
+use crate::api::handlers::asset::request::update_asset_request::UpdateAssetRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::asset_model::{NewAssetModel, UpdatableAssetModel};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+pub async fn update_asset_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    Json(payload): Json<UpdateAssetRequest>,
+) -> Result<Json<ApiResponse<NewAssetModel>>> {
+    println!("->> {:<12} - update_asset_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let updatable_asset_model = UpdatableAssetModel {
+        id: asset_id,
+        alt_text: payload.alt_text.trim().to_string(),
+        caption: payload.caption.trim().to_string(),
+        tags: payload.normalized_tags(),
+        focal_point: payload.focal_point,
+        crops: payload.crops,
+        logged_in_username: logged_in_user.email.clone(),
+    };
+    let asset_model = state
+        .asset_service
+        .update_asset(&state.db, updatable_asset_model, &logged_in_user)
+        .await?;
+
+    // the cached renditions were cut for the previous focal point and crops
+    if state.image_service.is_image(&asset_model) {
+        if let Err(e) = state.image_service.remove_renditions(&asset_model.id).await {
+            error!("there is an issue while removing the image renditions: {e:?}");
+        }
+        if let Err(e) = state.image_service.generate_presets(&asset_model).await {
+            error!("there is an issue while generating the image presets: {e:?}");
+        }
+    }
+
+    let response = ApiResponse {
+        status: true,
+        data: asset_model
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use image::{ImageFormat, RgbImage};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, send_authenticated_post_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_update_asset_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let mut png = Cursor::new(Vec::new());
+        RgbImage::new(300, 200).write_to(&mut png, ImageFormat::Png)?;
+        let png = png.into_inner();
+        let response = app.clone()
+            .oneshot(send_asset_upload_request("holiday.png", "image/png", png.clone(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let asset_model = &body["asset_model"];
+        assert_eq!(asset_model["original_name"], "holiday.png");
+        assert_eq!(asset_model["file_size"], png.len());
+        assert_eq!((asset_model["width"].as_i64(), asset_model["height"].as_i64()), (Some(300), Some(200)));
+        assert_eq!(asset_model["checksum"].as_str().map(str::len), Some(64));
+
+        let request = Request::builder()
+            .uri(format!("/api/asset/{}", asset_model["id"].as_str().unwrap()))
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .method("PUT")
+            .body(Body::from(r#"{
+                "alt_text": " A beach at sunset ",
+                "caption": "Summer holiday",
+                "tags": ["beach", " summer ", "", "beach"]
+            }"#))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        assert_eq!(body["data"]["alt_text"], "A beach at sunset");
+        assert_eq!(body["data"]["caption"], "Summer holiday");
+        assert_eq!(body["data"]["tags"], serde_json::json!(["beach", "summer"]));
+        assert_eq!(body["data"]["width"], 300);
+
+        let asset_id = asset_model["id"].as_str().unwrap();
+        let update_request = |body: &'static str| Request::builder()
+            .uri(format!("/api/asset/{asset_id}"))
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .method("PUT")
+            .body(Body::from(body))
+            .unwrap();
+
+        let response = app.clone()
+            .oneshot(update_request(r#"{"crops": [{"name": "hero", "x": 200, "y": 0, "width": 200, "height": 100}]}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.clone()
+            .oneshot(update_request(r#"{
+                "focal_point": {"x": 0.25, "y": 0.5},
+                "crops": [{"name": "hero", "x": 0, "y": 50, "width": 200, "height": 100}]
+            }"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        assert_eq!(body["data"]["focal_point"], serde_json::json!({"x": 0.25, "y": 0.5}));
+        assert_eq!(body["data"]["crops"][0]["name"], "hero");
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/public/image/{asset_id}?crop=hero&w=100"), String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let rendition = image::load_from_memory(&body)?;
+        assert_eq!((rendition.width(), rendition.height()), (100, 50));
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/public/image/{asset_id}?crop=missing"), String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let payload = Body::from(r#"{"name": "Website", "scopes": ["read_assets"]}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/cms-token", payload, token.clone()))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let cms_token = body["data"]["token"].as_str().unwrap().to_string();
+
+        let response = app
+            .oneshot(send_get_request(&format!("/cms/asset/{asset_id}"), cms_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        assert!(body["data"]["crop_urls"]["hero"].as_str().unwrap().ends_with(&format!("/public/image/{asset_id}?crop=hero")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use tracing::log::error;
+use crate::api::handlers::asset::request::update_asset_visibility_request::UpdateAssetVisibilityRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::asset_model::NewAssetModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn update_asset_visibility_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    Json(payload): Json<UpdateAssetVisibilityRequest>,
+) -> Result<Json<ApiResponse<NewAssetModel>>> {
+    println!("->> {:<12} - update_asset_visibility_api_handler", "HANDLER");
+
+    let asset_model = state
+        .asset_service
+        .update_visibility(&state.db, &asset_id, payload.is_private, &logged_in_user)
+        .await?;
+
+    // renditions are served publicly, they come back on request once the file is public again
+    if asset_model.is_private {
+        if let Err(e) = state.image_service.remove_renditions(&asset_id).await {
+            error!("there is an issue while removing the image renditions: {e:?}");
+        }
+    }
+
+    let response = ApiResponse {
+        status: true,
+        data: asset_model
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+use crate::{
+    avored_state::AvoRedState, error::Result
+};
+use axum::{extract::State, Json, response::IntoResponse};
+
+pub async fn all_models_cms_api_handler(
+    state: State<Arc<AvoRedState>>,
+) -> Result<impl IntoResponse> {
+    println!("->> {:<12} - all_models_cms_api_handler", "HANDLER");
+    let model_models = state
+        .model_service
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(model_models))
+}
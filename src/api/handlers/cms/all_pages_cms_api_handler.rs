@@ -2,15 +2,17 @@ use std::sync::Arc;
 use crate::{
     avored_state::AvoRedState, error::Result
 };
-use axum::{extract::State, Json, response::IntoResponse};
+use axum::{extract::State, Extension, Json, response::IntoResponse};
+use crate::models::token_claim_model::LoggedInUser;
 
 pub async fn all_pages_cms_api_handler(
-    state: State<Arc<AvoRedState>>
+    state: State<Arc<AvoRedState>>,
+    Extension(issuer): Extension<LoggedInUser>
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - all_pages_cms_api_handler", "HANDLER");
     let page_model = state
         .page_service
-        .all(&state.db)
+        .all_accessible(&state.db, &issuer)
         .await?;
 
     Ok(Json(page_model))
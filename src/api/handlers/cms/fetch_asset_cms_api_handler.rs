@@ -0,0 +1,26 @@
+use std::sync::Arc;
+use crate::{
+    avored_state::AvoRedState, error::{Error, Result}
+};
+use axum::{extract::{Path as AxumPath, State}, Extension, Json, response::IntoResponse};
+use crate::models::token_claim_model::LoggedInUser;
+
+pub async fn fetch_asset_cms_api_handler(
+    AxumPath(asset_id): AxumPath<String>,
+    state: State<Arc<AvoRedState>>,
+    Extension(issuer): Extension<LoggedInUser>
+) -> Result<impl IntoResponse> {
+    println!("->> {:<12} - fetch_asset_cms_api_handler", "HANDLER");
+    let asset_model = state
+        .asset_service
+        .find_accessible_by_id(&state.db, &asset_id, &issuer)
+        .await?;
+    if asset_model.asset_type != "FILE" {
+        return Err(Error::NotFound(format!("asset {asset_id} is not a file")));
+    }
+
+    let crop_urls = state.image_service.crop_urls(&asset_model);
+    let res = asset_model.convert_to_response(crop_urls)?;
+
+    Ok(Json(res))
+}
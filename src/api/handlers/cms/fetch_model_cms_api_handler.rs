@@ -0,0 +1,18 @@
+use std::sync::Arc;
+use crate::{
+    avored_state::AvoRedState, error::Result
+};
+use axum::{extract::{Path as AxumPath, State}, Json, response::IntoResponse};
+
+pub async fn fetch_model_cms_api_handler(
+    AxumPath(model_id): AxumPath<String>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<impl IntoResponse> {
+    println!("->> {:<12} - fetch_model_cms_api_handler", "HANDLER");
+    let model_model = state
+        .model_service
+        .find_by_id(&state.db, model_id)
+        .await?;
+
+    Ok(Json(model_model))
+}
@@ -2,19 +2,131 @@ use std::sync::Arc;
 use crate::{
     avored_state::AvoRedState, error::Result
 };
-use axum::{extract::{Path as AxumPath, State}, Json, response::IntoResponse};
+use axum::{extract::{Path as AxumPath, State}, Extension, Json, response::IntoResponse};
+use crate::models::token_claim_model::LoggedInUser;
 
 pub async fn fetch_page_cms_api_handler(
     AxumPath(page_id): AxumPath<String>,
-    state: State<Arc<AvoRedState>>
+    state: State<Arc<AvoRedState>>,
+    Extension(issuer): Extension<LoggedInUser>
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - fetch_page_cms_api_handler", "HANDLER");
     let page_model = state
         .page_service
-        .find_by_id(&state.db, page_id)
+        .find_accessible_by_id(&state.db, page_id, &issuer)
         .await?;
 
     let res = page_model.convert_to_response()?;
 
     Ok(Json(res))
 }
+
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, send_get_request, setup_avored_db};
+    use crate::error::Result;
+    use crate::models::admin_user_model::CreatableAdminUserModel;
+    use crate::models::page_model::{NewCreatablePageModel, PageStatus};
+    use crate::models::role_model::CreatableRole;
+    use crate::models::token_claim_model::LoggedInUser;
+
+    #[tokio::test]
+    async fn test_fetch_page_cms_api_handler_follows_issuer_rules() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+
+        let super_admin = state
+            .admin_user_service
+            .find_by_email(&state.db, String::from("admin@admin.com"))
+            .await?;
+        let logged_in_user = LoggedInUser {
+            id: super_admin.id.clone(),
+            name: super_admin.full_name.clone(),
+            email: super_admin.email.clone(),
+            demo_data_status: false,
+            admin_user_model: super_admin,
+            token_permissions: None
+        };
+
+        let creatable_role = CreatableRole {
+            name: String::from("Blog editor"),
+            identifier: String::from("blog-editor"),
+            logged_in_username: logged_in_user.email.clone(),
+            permissions: vec![],
+            own_pages_only: false,
+            page_identifier_prefixes: vec![String::from("blog-")],
+            asset_folder_ids: vec![],
+        };
+        let role = state.role_service.create_role(&state.db, creatable_role).await?;
+        let creatable_admin_user_model = CreatableAdminUserModel {
+            full_name: String::from("Blog Editor"),
+            email: String::from("blog@avored.com"),
+            password: state.password_service.hash_password("Editor123Secret")?,
+            profile_image: String::from(""),
+            is_super_admin: false,
+            logged_in_username: logged_in_user.email.clone(),
+            role_ids: vec![role.id],
+        };
+        state
+            .admin_user_service
+            .create_admin_user(&state.db, creatable_admin_user_model, logged_in_user.clone())
+            .await?;
+
+        let mut page_ids = vec![];
+        for identifier in ["blog-first-post", "about-us"] {
+            let creatable_page_model = NewCreatablePageModel {
+                name: identifier.to_string(),
+                identifier: identifier.to_string(),
+                status: PageStatus::Published,
+                logged_in_username: logged_in_user.email.clone(),
+                page_fields: vec![],
+                locked_role_ids: vec![],
+            };
+            let page_model = state
+                .page_service
+                .new_create_page(&state.db, creatable_page_model, &logged_in_user)
+                .await?;
+            page_ids.push(page_model.id);
+        }
+
+        let issued_cms_token = state
+            .cms_token_service
+            .create_cms_token(&state.db, String::from("Blog"), vec![String::from("read_pages")], vec![], None, String::from("blog@avored.com"))
+            .await?;
+        let cms_token = issued_cms_token.token;
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/cms/page/{}", page_ids[0]), cms_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.clone()
+            .oneshot(send_get_request(&format!("/cms/page/{}", page_ids[1]), cms_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .oneshot(send_get_request("/cms/page", cms_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let identifiers: Vec<&str> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|page| page["identifier"].as_str())
+            .collect();
+        assert_eq!(identifiers, vec!["blog-first-post"]);
+
+        Ok(())
+    }
+}
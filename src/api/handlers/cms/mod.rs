@@ -1,3 +1,7 @@
 pub mod fetch_page_cms_api_handler;
 pub mod all_pages_cms_api_handler;
-pub mod sent_contact_us_email_handler;
\ No newline at end of file
+pub mod sent_contact_us_email_handler;
+pub mod fetch_asset_cms_api_handler;
+pub mod signed_asset_url_cms_api_handler;
+pub mod fetch_model_cms_api_handler;
+pub mod all_models_cms_api_handler;
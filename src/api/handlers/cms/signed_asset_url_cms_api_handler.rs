@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use axum::extract::{Path, Query, State};
+use axum::{Extension, Json};
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::asset_model::SignedAssetUrlModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::responses::ApiResponse;
+
+/// Issues a signed url for gated downloads, the file is served by it until it expires.
+pub async fn signed_asset_url_cms_api_handler(
+    Path(asset_id): Path<String>,
+    state: State<Arc<AvoRedState>>,
+    Query(query_param): Query<SignedAssetUrlRequest>,
+    Extension(issuer): Extension<LoggedInUser>,
+) -> Result<Json<ApiResponse<SignedAssetUrlModel>>> {
+    println!("->> {:<12} - signed_asset_url_cms_api_handler", "HANDLER");
+
+    let error_messages = query_param.validate(state.asset_service.signed_url_max_expires_in());
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let asset_model = state
+        .asset_service
+        .find_accessible_by_id(&state.db, &asset_id, &issuer)
+        .await?;
+    if asset_model.asset_type != "FILE" {
+        return Err(Error::NotFound(format!("asset {asset_id} is not a file")));
+    }
+
+    let response = ApiResponse {
+        status: true,
+        data: state.asset_service.signed_url(&asset_model, query_param.expires_in)?
+    };
+
+    Ok(Json(response))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SignedAssetUrlRequest {
+    /// Seconds the url works for, the configured default when not given.
+    pub expires_in: Option<i64>,
+}
+
+impl SignedAssetUrlRequest {
+    fn validate(&self, max_expires_in: i64) -> Vec<ErrorMessage> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if self.expires_in.is_some_and(|expires_in| expires_in < 1 || expires_in > max_expires_in) {
+            errors.push(ErrorMessage {
+                key: String::from("expires_in"),
+                message: t!("asset_signed_url_expires_in_invalid", max = max_expires_in).to_string()
+            });
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::cms_token_model::CmsTokenModel;
+use crate::responses::ApiResponse;
+
+pub async fn cms_token_table_api_handler(
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<Vec<CmsTokenModel>>>> {
+    println!("->> {:<12} - cms_token_table_api_handler", "HANDLER");
+
+    let cms_tokens = state.cms_token_service.all(&state.db).await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: cms_tokens
+    };
+
+    Ok(Json(response))
+}
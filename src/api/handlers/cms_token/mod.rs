@@ -0,0 +1,5 @@
+pub mod request;
+pub mod cms_token_table_api_handler;
+pub mod store_cms_token_api_handler;
+pub mod rotate_cms_token_api_handler;
+pub mod revoke_cms_token_api_handler;
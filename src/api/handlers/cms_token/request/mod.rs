@@ -0,0 +1 @@
+pub mod store_cms_token_request;
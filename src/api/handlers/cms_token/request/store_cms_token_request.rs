@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::models::cms_token_model::CmsTokenScope;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct StoreCmsTokenRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// RFC 3339 date time, the token never expires when it is not given.
+    pub expires_at: Option<String>,
+}
+
+impl StoreCmsTokenRequest {
+    pub fn validate(&self) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.name.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("name"),
+                message: t!("validation_required", attribute = t!("name")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if self.scopes.is_empty() {
+            let error_message = ErrorMessage {
+                key: String::from("scopes"),
+                message: t!("validation_required", attribute = t!("scopes")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        let valid_scopes = CmsTokenScope::all();
+        for scope in self.scopes.iter() {
+            if !valid_scopes.iter().any(|valid_scope| valid_scope.as_str() == scope) {
+                let error_message = ErrorMessage {
+                    key: String::from("scopes"),
+                    message: t!("cms_token_scope_invalid", scope = scope).to_string()
+                };
+
+                errors.push(error_message);
+            }
+        }
+
+        for origin in self.allowed_origins.iter() {
+            if !origin.starts_with("http://") && !origin.starts_with("https://") {
+                let error_message = ErrorMessage {
+                    key: String::from("allowed_origins"),
+                    message: t!("cms_token_origin_invalid", origin = origin).to_string()
+                };
+
+                errors.push(error_message);
+            }
+        }
+
+        if let Some(expires_at) = &self.expires_at {
+            let is_valid = match DateTime::parse_from_rfc3339(expires_at) {
+                Ok(expires_at) => expires_at.with_timezone(&Utc) > Utc::now(),
+                Err(_) => false
+            };
+
+            if !is_valid {
+                let error_message = ErrorMessage {
+                    key: String::from("expires_at"),
+                    message: t!("cms_token_expires_at_invalid").to_string()
+                };
+
+                errors.push(error_message);
+            }
+        }
+
+        Ok(errors)
+    }
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::Json;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::responses::ApiResponse;
+
+pub async fn revoke_cms_token_api_handler(
+    AxumPath(cms_token_id): AxumPath<String>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<bool>>> {
+    println!("->> {:<12} - revoke_cms_token_api_handler", "HANDLER");
+
+    let revoked_status = state
+        .cms_token_service
+        .revoke_cms_token(&state.db, &cms_token_id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: revoked_status
+    };
+
+    Ok(Json(response))
+}
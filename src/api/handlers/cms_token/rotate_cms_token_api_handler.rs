@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::{Extension, Json};
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::cms_token_model::IssuedCmsTokenModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn rotate_cms_token_api_handler(
+    AxumPath(cms_token_id): AxumPath<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<IssuedCmsTokenModel>>> {
+    println!("->> {:<12} - rotate_cms_token_api_handler", "HANDLER");
+
+    let issued_cms_token = state
+        .cms_token_service
+        .rotate_cms_token(&state.db, &cms_token_id, logged_in_user.email)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: issued_cms_token
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use surrealdb::sql::Datetime;
+use crate::api::handlers::cms_token::request::store_cms_token_request::StoreCmsTokenRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::cms_token_model::IssuedCmsTokenModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+pub async fn store_cms_token_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<StoreCmsTokenRequest>,
+) -> Result<Json<ApiResponse<IssuedCmsTokenModel>>> {
+    println!("->> {:<12} - store_cms_token_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    // the date time format is already checked by the validation
+    let expires_at = payload
+        .expires_at
+        .and_then(|expires_at| DateTime::parse_from_rfc3339(&expires_at).ok())
+        .map(|expires_at| Datetime::from(expires_at.with_timezone(&Utc)));
+
+    let issued_cms_token = state
+        .cms_token_service
+        .create_cms_token(
+            &state.db,
+            payload.name,
+            payload.scopes,
+            payload.allowed_origins,
+            expires_at,
+            logged_in_user.email
+        )
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: issued_cms_token
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_authenticated_post_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    async fn response_json(response: axum::response::Response) -> Value {
+        let res_b = response.into_body();
+        let body = axum::body::to_bytes(res_b, usize::MAX).await.unwrap();
+
+        serde_json::from_slice(&body).expect("Failed to parse JSON")
+    }
+
+    #[tokio::test]
+    async fn test_store_cms_token_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let payload = Body::from(r#"{"name": "Website", "scopes": ["read_pages"]}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/cms-token", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response_json(response).await;
+        let cms_token = body["data"]["token"].as_str().unwrap().to_string();
+        let cms_token_id = body["data"]["cms_token"]["id"].as_str().unwrap().to_string();
+        assert!(body["data"]["cms_token"].get("token_hash").is_none());
+
+        let response = app.clone()
+            .oneshot(send_get_request("/cms/page", cms_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // the token has no submit_forms scope
+        let payload = Body::from(r#"{"email": "a@b.com", "first_name": "", "last_name": "", "message": "", "phone": ""}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/cms/sent-contact-us-email", payload, cms_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // nor the read_assets scope
+        let response = app.clone()
+            .oneshot(send_get_request("/cms/asset/missing", cms_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // nor the read_models scope
+        let response = app.clone()
+            .oneshot(send_get_request("/cms/model", cms_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let payload = Body::from(r#"{"name": "Models", "scopes": ["read_models"]}"#);
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/cms-token", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let models_cms_token = response_json(response).await["data"]["token"].as_str().unwrap().to_string();
+        let response = app.clone()
+            .oneshot(send_get_request("/cms/model", models_cms_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let rotate_uri = format!("/api/cms-token/{cms_token_id}/rotate");
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request(&rotate_uri, Body::empty(), token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let rotated_cms_token = response_json(response).await["data"]["token"].as_str().unwrap().to_string();
+
+        let response = app.clone()
+            .oneshot(send_get_request("/cms/page", cms_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let revoke_request = Request::builder()
+            .uri(format!("/api/cms-token/{cms_token_id}"))
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .method("DELETE")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(revoke_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(send_get_request("/cms/page", rotated_cms_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+}
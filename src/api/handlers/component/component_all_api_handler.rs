@@ -33,7 +33,7 @@ mod tests {
     async fn test_component_all_api_handler() -> Result<()>
     {
         let (app, state) = get_axum_app().await.unwrap();
-        let token = get_auth_token(state.clone())?;
+        let token = get_auth_token(state.clone()).await?;
 
         let sql = "
             CREATE components:content_id_1 CONTENT {
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use tracing::log::error;
 
 use crate::{
     avored_state::AvoRedState, error::Result
@@ -19,14 +20,6 @@ pub async fn store_component_api_handler(
 ) -> Result<Json<CreatedComponentResponse>> {
     println!("->> {:<12} - store_component_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("component_create"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -77,6 +70,10 @@ pub async fn store_component_api_handler(
         .create_component(&state.db, creatable_component)
         .await?;
 
+    if let Err(e) = state.asset_reference_service.index_component(&state.db, &created_component).await {
+        error!("there is an issue while indexing the assets of the component: {e:?}");
+    }
+
     let created_response = CreatedComponentResponse {
         status: true,
         component_model: created_component
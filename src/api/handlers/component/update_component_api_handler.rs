@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use tracing::log::error;
 
 use crate::{
     avored_state::AvoRedState,
@@ -20,14 +21,6 @@ pub async fn update_component_api_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - update_component_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("component_edit"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -75,6 +68,10 @@ pub async fn update_component_api_handler(
         .update_component(&state.db, updatable_component_model)
         .await?;
 
+    if let Err(e) = state.asset_reference_service.index_component(&state.db, &updated_component_model).await {
+        error!("there is an issue while indexing the assets of the component: {e:?}");
+    }
+
     let response = UpdatedComponentResponse {
         status: true,
         component_model: updated_component_model
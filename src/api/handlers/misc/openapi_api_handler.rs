@@ -4,7 +4,12 @@ use axum::Json;
 use utoipa::OpenApi;
 use utoipa::openapi::Server;
 use crate::error::Result;
-use crate::api::handlers::admin_user::admin_user_login_api_handler::LoginResponseData;
+use crate::api::handlers::admin_user::admin_user_login_api_handler::{LoginResponseData, TwoFactorChallengeResponse};
+use crate::api::handlers::admin_user::admin_user_two_factor_login_api_handler::TwoFactorLoginResponseData;
+use crate::api::handlers::admin_user::request::two_factor_login_request::TwoFactorLoginRequest;
+use crate::api::handlers::admin_user::request::two_factor_challenge_request::TwoFactorChallengeRequest;
+use crate::models::admin_user_two_factor_model::TwoFactorEnrolmentModel;
+use crate::responses::TwoFactorEnrolmentResponse;
 use crate::models::admin_user_model::AdminUserModel;
 use crate::models::role_model::RoleModel;
 use crate::api::handlers::admin_user::request::authenticate_admin_user_request::AuthenticateAdminUserRequest;
@@ -27,15 +32,56 @@ pub async fn openapi_api_handler(
 #[derive(OpenApi)]
 #[openapi(
     paths(
-        crate::api::handlers::admin_user::admin_user_login_api_handler::admin_user_login_api_handler
+        crate::api::handlers::admin_user::admin_user_login_api_handler::admin_user_login_api_handler,
+        crate::api::handlers::admin_user::admin_user_two_factor_login_api_handler::admin_user_two_factor_login_api_handler,
+        crate::api::handlers::admin_user::admin_user_two_factor_enrol_login_api_handler::admin_user_two_factor_enrol_login_api_handler
     ),
     components(
         schemas(
             LoginResponseData,
+            TwoFactorChallengeResponse,
+            TwoFactorLoginResponseData,
             AdminUserModel,
             RoleModel,
-            AuthenticateAdminUserRequest
+            AuthenticateAdminUserRequest,
+            TwoFactorLoginRequest,
+            TwoFactorChallengeRequest,
+            TwoFactorEnrolmentModel,
+            TwoFactorEnrolmentResponse
         )
     )
 )]
 struct ApiDoc;
+
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_openapi_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let response = app
+            .oneshot(send_get_request("/api/openapi.json", token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        for path in ["/api/login", "/api/login/two-factor", "/api/login/two-factor/enrol"] {
+            assert!(body["paths"][path]["post"].is_object(), "{path}");
+        }
+        assert!(body["components"]["schemas"]["TwoFactorEnrolmentResponse"].is_object());
+
+        Ok(())
+    }
+}
@@ -9,4 +9,8 @@ pub mod setting;
 pub mod graphql;
 pub mod model;
 pub mod misc;
+pub mod cms_token;
+pub mod personal_access_token;
+pub mod oidc;
+pub mod admin_user_invitation;
 
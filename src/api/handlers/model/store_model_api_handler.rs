@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use tracing::log::error;
 
 use crate::error::Error;
 use crate::models::validation_error::ErrorResponse;
@@ -19,14 +20,6 @@ pub async fn store_model_api_handler(
 ) -> Result<Json<CreatedModelResponse>> {
     println!("->> {:<12} - store_model_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("model_create"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -48,6 +41,10 @@ pub async fn store_model_api_handler(
         .model_service
         .create_model(&state.db, creatable_model)
         .await?;
+
+    if let Err(e) = state.asset_reference_service.index_model(&state.db, &created_model_model).await {
+        error!("there is an issue while indexing the assets of the model: {e:?}");
+    }
     let response = CreatedModelResponse {
         status: true,
         model_model: created_model_model
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use tracing::log::error;
 
 use crate::{
     avored_state::AvoRedState,
@@ -19,14 +20,6 @@ pub async fn update_model_api_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - update_model_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("model_edit"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -48,6 +41,10 @@ pub async fn update_model_api_handler(
         .update_model(&state.db, updateable_model_model)
         .await?;
 
+    if let Err(e) = state.asset_reference_service.index_model(&state.db, &updated_model_model).await {
+        error!("there is an issue while indexing the assets of the model: {e:?}");
+    }
+
     let response = UpdatedModelResponse {
         status: true,
         model_model: updated_model_model
@@ -0,0 +1,4 @@
+pub mod request;
+pub mod oidc_authorize_api_handler;
+pub mod oidc_callback_api_handler;
+pub mod oidc_link_api_handler;
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::Json;
+use serde::Serialize;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::responses::ApiResponse;
+
+#[derive(Serialize, Default)]
+pub struct OidcAuthorizeViewModel {
+    pub authorization_url: String
+}
+
+pub async fn oidc_authorize_api_handler(
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<OidcAuthorizeViewModel>>> {
+    println!("->> {:<12} - oidc_authorize_api_handler", "HANDLER");
+
+    let authorization_url = state
+        .oidc_service
+        .authorization_url(&state.db)
+        .await?;
+
+    let response_data = ApiResponse {
+        status: true,
+        data: OidcAuthorizeViewModel { authorization_url },
+    };
+
+    Ok(Json(response_data))
+}
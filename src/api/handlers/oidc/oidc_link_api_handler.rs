@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use crate::api::handlers::oidc::request::oidc_callback_request::OidcCallbackRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+/// A signed in admin user posts the code and state of an identity provider redirect
+/// here, instead of to the callback, to link single sign on to the account.
+pub async fn oidc_link_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<OidcCallbackRequest>,
+) -> Result<Json<ApiResponse<bool>>> {
+    println!("->> {:<12} - oidc_link_api_handler", "HANDLER");
+
+    let error_messages = payload.validate()?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let identity = state
+        .oidc_service
+        .authenticate(&state.db, &payload.code, &payload.state)
+        .await?;
+
+    let linked_status = state
+        .oidc_service
+        .link_admin_user(&state.db, &logged_in_user.id, identity)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: linked_status
+    };
+
+    Ok(Json(response))
+}
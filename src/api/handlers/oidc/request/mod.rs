@@ -0,0 +1 @@
+pub mod oidc_callback_request;
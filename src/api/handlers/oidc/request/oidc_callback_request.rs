@@ -0,0 +1,37 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::error::Result;
+use crate::models::validation_error::ErrorMessage;
+use crate::models::validation_error::Validate;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct OidcCallbackRequest {
+    pub code: String,
+    pub state: String,
+}
+
+impl OidcCallbackRequest {
+    pub fn validate(&self) -> Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.code.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("code"),
+                message: t!("validation_required", attribute = t!("code")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if !self.state.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("state"),
+                message: t!("validation_required", attribute = t!("state")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
@@ -1,27 +1,27 @@
 use std::sync::Arc;
 use serde::Serialize;
 use crate::{ error::Result };
-use axum::{Extension, extract::Path};
+use axum::Extension;
+use axum::extract::Path;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use tracing::log::error;
 use crate::avored_state::AvoRedState;
-use crate::error::Error;
+use crate::models::asset_reference_model::AssetReferenceSource;
 use crate::models::token_claim_model::LoggedInUser;
 
 pub async fn delete_page_handler(
-    Extension(logged_in_user): Extension<LoggedInUser>,
     Path(page_id): Path<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
     state: State<Arc<AvoRedState>>
 ) -> Result<impl IntoResponse> {
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("page_delete"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
+    state.page_service.remove_by_id(&state.db, &page_id, &logged_in_user).await?;
+    if let Err(e) = state.asset_reference_service
+        .remove_source(&state.db, AssetReferenceSource::Page, &page_id)
+        .await {
+        error!("there is an issue while removing the asset references of the page: {e:?}");
     }
-    state.page_service.remove_by_id(&state.db, &page_id).await?;
     Ok(StatusCode::OK)
 }
 
@@ -1,14 +1,13 @@
 use std::sync::Arc;
 
 use crate::models::page_model::NewPageModel;
+use crate::models::token_claim_model::LoggedInUser;
 use crate::{
     avored_state::AvoRedState, error::Result
 };
 
 use axum::{Extension, extract::{Path as AxumPath, State}, Json, response::IntoResponse};
 use serde::Serialize;
-use crate::error::Error;
-use crate::models::token_claim_model::LoggedInUser;
 
 pub async fn fetch_page_api_handler(
     AxumPath(page_id): AxumPath<String>,
@@ -17,17 +16,9 @@ pub async fn fetch_page_api_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - fetch_page_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user, String::from("get_page"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let page_model = state
         .page_service
-        .find_by_id(&state.db, page_id)
+        .find_accessible_by_id(&state.db, page_id, &logged_in_user)
         .await?;
     let response = FetchPageResponse {
         status: true,
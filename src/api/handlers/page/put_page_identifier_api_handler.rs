@@ -22,14 +22,6 @@ pub async fn put_page_identifier_api_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - put_page_identifier_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("page_edit"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate(state.clone()).await?;
 
     if !error_messages.is_empty() {
@@ -45,11 +37,11 @@ pub async fn put_page_identifier_api_handler(
     let put_page_identifier = PutPageIdentifierModel {
         id: page_id,
         identifier: payload.identifier,
-        logged_in_username: logged_in_user.email
+        logged_in_username: logged_in_user.email.clone()
     };
     let updated_page_model = state
         .page_service
-        .update_page_identifier(&state.db, put_page_identifier)
+        .update_page_identifier(&state.db, put_page_identifier, &logged_in_user)
         .await?;
 
     let updated_page_response = PutPageIdentifierResponse {
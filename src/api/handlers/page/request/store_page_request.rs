@@ -11,6 +11,8 @@ pub struct StorePageRequest {
     pub identifier: String,
     pub status: PageStatus,
     pub page_fields: Vec<CreatablePageFieldRequest>,
+    #[serde(default)]
+    pub locked_role_ids: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -9,6 +9,8 @@ pub struct UpdatePageRequest {
     pub identifier: String,
     pub status: PageStatus,
     pub page_fields: Vec<UpdatablePageField>,
+    /// Keeps the current locks when it is left out.
+    pub locked_role_ids: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use tracing::log::error;
 
 use crate::error::Error;
 use crate::models::page_model::{CreatablePageField, NewCreatablePageModel, NewPageModel};
@@ -19,14 +20,6 @@ pub async fn store_page_api_handler(
     println!("->> {:<12} - store_page_api_handler", "HANDLER");
     let error_messages = payload.validate(&state).await?;
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("page_create"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     if !error_messages.is_empty() {
         let error_response = ErrorResponse {
             status: false,
@@ -40,8 +33,9 @@ pub async fn store_page_api_handler(
         name: payload.name,
         identifier: payload.identifier,
         status: payload.status,
-        logged_in_username: logged_in_user.name.clone(),
-        page_fields: vec![]
+        logged_in_username: logged_in_user.email.clone(),
+        page_fields: vec![],
+        locked_role_ids: payload.locked_role_ids
     };
 
     for  payload_page_field in  payload.page_fields {
@@ -58,8 +52,12 @@ pub async fn store_page_api_handler(
 
     let created_page_model = state
         .page_service
-        .new_create_page(&state.db, creatable_page)
+        .new_create_page(&state.db, creatable_page, &logged_in_user)
         .await?;
+
+    if let Err(e) = state.asset_reference_service.index_page(&state.db, &created_page_model).await {
+        error!("there is an issue while indexing the assets of the page: {e:?}");
+    }
     // println!("PAge payload: {:?}", payload);
     // let created_page_model = NewPageModel::default();
 
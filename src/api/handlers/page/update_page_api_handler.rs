@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use tracing::log::error;
 
 use crate::error::Error;
 use crate::models::page_model::{NewPageModel, NewUpdatablePageModel, UpdatablePageField};
@@ -20,14 +21,6 @@ pub async fn update_page_api_handler(
 ) -> Result<Json<ApiResponse<NewPageModel>>> {
     println!("->> {:<12} - update_page_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("page_edit"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -41,7 +34,7 @@ pub async fn update_page_api_handler(
 
     let page_model = state
         .page_service
-        .find_by_id(&state.db, page_id)
+        .find_accessible_by_id(&state.db, page_id, &logged_in_user)
         .await?;
 
     let mut updatable_page = NewUpdatablePageModel {
@@ -49,10 +42,11 @@ pub async fn update_page_api_handler(
         name: payload.name,
         identifier: payload.identifier,
         status: payload.status,
-        logged_in_username: logged_in_user.name.clone(),
+        logged_in_username: logged_in_user.email.clone(),
         page_fields: vec![],
         created_at: page_model.created_at,
-        created_by: page_model.created_by
+        created_by: page_model.created_by,
+        locked_role_ids: payload.locked_role_ids.unwrap_or(page_model.locked_role_ids)
     };
 
     for  payload_page_field in  payload.page_fields {
@@ -69,9 +63,13 @@ pub async fn update_page_api_handler(
 
     let created_page_model = state
         .page_service
-        .update_page(&state.db, updatable_page)
+        .update_page(&state.db, updatable_page, &logged_in_user)
         .await?;
 
+    if let Err(e) = state.asset_reference_service.index_page(&state.db, &created_page_model).await {
+        error!("there is an issue while indexing the assets of the page: {e:?}");
+    }
+
     let response = ApiResponse {
         status: true,
         data: created_page_model
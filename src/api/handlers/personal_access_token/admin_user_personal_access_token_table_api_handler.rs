@@ -0,0 +1,26 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::Json;
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::personal_access_token_model::PersonalAccessTokenModel;
+use crate::responses::ApiResponse;
+
+pub async fn admin_user_personal_access_token_table_api_handler(
+    AxumPath(admin_user_id): AxumPath<String>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<Vec<PersonalAccessTokenModel>>>> {
+    println!("->> {:<12} - admin_user_personal_access_token_table_api_handler", "HANDLER");
+
+    let personal_access_tokens = state
+        .personal_access_token_service
+        .all_by_admin_user_id(&state.db, &admin_user_id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: personal_access_tokens
+    };
+
+    Ok(Json(response))
+}
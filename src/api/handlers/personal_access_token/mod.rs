@@ -0,0 +1,6 @@
+pub mod request;
+pub mod personal_access_token_table_api_handler;
+pub mod store_personal_access_token_api_handler;
+pub mod revoke_personal_access_token_api_handler;
+pub mod admin_user_personal_access_token_table_api_handler;
+pub mod revoke_admin_user_personal_access_token_api_handler;
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::personal_access_token_model::PersonalAccessTokenModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn personal_access_token_table_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<Vec<PersonalAccessTokenModel>>>> {
+    println!("->> {:<12} - personal_access_token_table_api_handler", "HANDLER");
+
+    let personal_access_tokens = state
+        .personal_access_token_service
+        .all_by_admin_user_id(&state.db, &logged_in_user.id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: personal_access_tokens
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1 @@
+pub mod store_personal_access_token_request;
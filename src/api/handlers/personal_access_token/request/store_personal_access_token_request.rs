@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use rust_i18n::t;
+use serde::Deserialize;
+use crate::models::admin_user_model::AdminUserModel;
+use crate::models::permission_model::Permission;
+use crate::models::validation_error::{ErrorMessage, Validate};
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct StorePersonalAccessTokenRequest {
+    pub name: String,
+    pub permissions: Vec<String>,
+    /// RFC 3339 date time
+    pub expires_at: String,
+}
+
+impl StorePersonalAccessTokenRequest {
+    /// The permissions of a token have to be a subset of the permissions the admin user
+    /// has through its roles.
+    pub fn validate(&self, admin_user_model: &AdminUserModel) -> crate::error::Result<Vec<ErrorMessage>> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if !self.name.required()? {
+            let error_message = ErrorMessage {
+                key: String::from("name"),
+                message: t!("validation_required", attribute = t!("name")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        if self.permissions.is_empty() {
+            let error_message = ErrorMessage {
+                key: String::from("permissions"),
+                message: t!("validation_required", attribute = t!("permissions")).to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        for permission in self.permissions.iter() {
+            if Permission::from_identifier(permission).is_none() {
+                let error_message = ErrorMessage {
+                    key: String::from("permissions"),
+                    message: t!("permission_invalid", permission = permission).to_string()
+                };
+
+                errors.push(error_message);
+            }
+        }
+
+        if !admin_user_model.is_super_admin {
+            for permission in self.permissions.iter() {
+                let is_granted = admin_user_model
+                    .roles
+                    .iter()
+                    .any(|role| role.permissions.contains(permission));
+
+                if !is_granted {
+                    let error_message = ErrorMessage {
+                        key: String::from("permissions"),
+                        message: t!("personal_access_token_permission_not_allowed", permission = permission).to_string()
+                    };
+
+                    errors.push(error_message);
+                }
+            }
+        }
+
+        let is_expires_at_valid = match DateTime::parse_from_rfc3339(&self.expires_at) {
+            Ok(expires_at) => expires_at.with_timezone(&Utc) > Utc::now(),
+            Err(_) => false
+        };
+        if !is_expires_at_valid {
+            let error_message = ErrorMessage {
+                key: String::from("expires_at"),
+                message: t!("personal_access_token_expires_at_invalid").to_string()
+            };
+
+            errors.push(error_message);
+        }
+
+        Ok(errors)
+    }
+}
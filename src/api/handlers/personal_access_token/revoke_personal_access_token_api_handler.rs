@@ -0,0 +1,27 @@
+use std::sync::Arc;
+use axum::extract::{Path as AxumPath, State};
+use axum::{Extension, Json};
+use crate::avored_state::AvoRedState;
+use crate::error::Result;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::responses::ApiResponse;
+
+pub async fn revoke_personal_access_token_api_handler(
+    AxumPath(personal_access_token_id): AxumPath<String>,
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+) -> Result<Json<ApiResponse<bool>>> {
+    println!("->> {:<12} - revoke_personal_access_token_api_handler", "HANDLER");
+
+    let revoked_status = state
+        .personal_access_token_service
+        .revoke_personal_access_token(&state.db, &personal_access_token_id, &logged_in_user.id)
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: revoked_status
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use surrealdb::sql::Datetime;
+use crate::api::handlers::personal_access_token::request::store_personal_access_token_request::StorePersonalAccessTokenRequest;
+use crate::avored_state::AvoRedState;
+use crate::error::{Error, Result};
+use crate::models::personal_access_token_model::IssuedPersonalAccessTokenModel;
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::ErrorResponse;
+use crate::responses::ApiResponse;
+
+pub async fn store_personal_access_token_api_handler(
+    Extension(logged_in_user): Extension<LoggedInUser>,
+    state: State<Arc<AvoRedState>>,
+    Json(payload): Json<StorePersonalAccessTokenRequest>,
+) -> Result<Json<ApiResponse<IssuedPersonalAccessTokenModel>>> {
+    println!("->> {:<12} - store_personal_access_token_api_handler", "HANDLER");
+
+    // the roles in the login token could be outdated
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_id(&state.db, logged_in_user.id.clone())
+        .await?;
+
+    let error_messages = payload.validate(&admin_user_model)?;
+
+    if !error_messages.is_empty() {
+        let error_response = ErrorResponse {
+            status: false,
+            errors: error_messages
+        };
+
+        return Err(Error::BadRequest(error_response));
+    }
+
+    let expires_at = DateTime::parse_from_rfc3339(&payload.expires_at)
+        .map_err(|_| Error::Generic(String::from("invalid expires at")))?;
+
+    let issued_personal_access_token = state
+        .personal_access_token_service
+        .create_personal_access_token(
+            &state.db,
+            admin_user_model.id,
+            payload.name,
+            payload.permissions,
+            Datetime::from(expires_at.with_timezone(&Utc))
+        )
+        .await?;
+
+    let response = ApiResponse {
+        status: true,
+        data: issued_personal_access_token
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_authenticated_post_request, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_store_personal_access_token_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let expires_at = (chrono::Utc::now() + chrono::Duration::days(30)).to_rfc3339();
+        let payload = Body::from(format!(r#"{{"name": "CI", "permissions": ["role_table"], "expires_at": "{expires_at}"}}"#));
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/personal-access-token", payload, token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let personal_access_token = body["data"]["token"].as_str().unwrap().to_string();
+        let personal_access_token_id = body["data"]["personal_access_token"]["id"].as_str().unwrap().to_string();
+
+        let response = app.clone()
+            .oneshot(send_get_request("/api/role", personal_access_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // capped by the token permissions even though the admin user is a super admin
+        let response = app.clone()
+            .oneshot(send_get_request("/api/admin-user", personal_access_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let payload = Body::from(format!(r#"{{"name": "CI", "permissions": ["role_table"], "expires_at": "{expires_at}"}}"#));
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/personal-access-token", payload, personal_access_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // routes without a registry permission only take the token of a login
+        for uri in [
+            "/api/change-password",
+            "/api/two-factor/enrol",
+            "/api/two-factor/confirm",
+            "/api/two-factor/disable",
+            "/api/oidc/link",
+            "/api/install-demo-data",
+            "/api/delete-demo-data",
+            "/graphql",
+        ] {
+            let response = app.clone()
+                .oneshot(send_authenticated_post_request(uri, Body::from("{}"), personal_access_token.clone()))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{uri}");
+        }
+
+        let revoke_request = Request::builder()
+            .uri(format!("/api/personal-access-token/{personal_access_token_id}"))
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .method("DELETE")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(revoke_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(send_get_request("/api/role", personal_access_token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+}
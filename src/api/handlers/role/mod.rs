@@ -4,4 +4,5 @@ pub mod fetch_role_api_handler;
 pub mod store_role_api_handler;
 pub mod update_role_api_handler;
 pub mod role_option_api_handler;
-pub mod put_role_identifier_api_handler;
\ No newline at end of file
+pub mod put_role_identifier_api_handler;
+pub mod permission_all_api_handler;
@@ -0,0 +1,57 @@
+use axum::Json;
+use crate::error::Result;
+use crate::models::permission_model::PermissionGroupModel;
+use crate::responses::ApiResponse;
+
+pub async fn permission_all_api_handler() -> Result<Json<ApiResponse<Vec<PermissionGroupModel>>>> {
+    println!("->> {:<12} - permission_all_api_handler", "HANDLER");
+
+    let response = ApiResponse {
+        status: true,
+        data: PermissionGroupModel::all()
+    };
+
+    Ok(Json(response))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_permission_all_api_handler() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await.unwrap();
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let response = app
+            .oneshot(send_get_request("/api/permissions", token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let asset_group = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|group| group["identifier"] == "asset")
+            .unwrap();
+        let asset_permissions: Vec<&str> = asset_group["permissions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|permission| permission["identifier"].as_str().unwrap())
+            .collect();
+        assert_eq!(asset_permissions, vec!["asset_table", "asset_create", "asset_edit", "asset_delete"]);
+
+        Ok(())
+    }
+}
@@ -22,14 +22,6 @@ pub async fn put_role_identifier_api_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - put_role_identifier_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("role_edit"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate(state.clone()).await?;
 
     if !error_messages.is_empty() {
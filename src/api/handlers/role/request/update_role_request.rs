@@ -1,12 +1,19 @@
 use rust_i18n::t;
 use serde::Deserialize;
 
+use crate::models::permission_model::Permission;
 use crate::models::validation_error::{ErrorMessage, Validate};
 
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct UpdateRoleRequest {
     pub name: String,
     pub permissions: Vec<String>,
+    #[serde(default)]
+    pub own_pages_only: bool,
+    #[serde(default)]
+    pub page_identifier_prefixes: Vec<String>,
+    #[serde(default)]
+    pub asset_folder_ids: Vec<String>,
 }
 
 impl UpdateRoleRequest {
@@ -22,6 +29,17 @@ impl UpdateRoleRequest {
             errors.push(error_message);
         }
 
+        for permission in &self.permissions {
+            if Permission::from_identifier(permission).is_none() {
+                let error_message = ErrorMessage {
+                    key: String::from("permissions"),
+                    message: t!("permission_invalid", permission = permission).to_string()
+                };
+
+                errors.push(error_message);
+            }
+        }
+
         Ok(errors)
     }
 }
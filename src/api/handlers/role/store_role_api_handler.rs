@@ -19,14 +19,6 @@ pub async fn store_role_api_handler(
 ) -> Result<Json<CreatedRoleResponse>> {
     println!("->> {:<12} - store_role_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("role_create"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -43,6 +35,9 @@ pub async fn store_role_api_handler(
         identifier: payload.identifier,
         logged_in_username: logged_in_user.email,
         permissions: payload.permissions,
+        own_pages_only: payload.own_pages_only,
+        page_identifier_prefixes: payload.page_identifier_prefixes,
+        asset_folder_ids: payload.asset_folder_ids,
     };
 
     let created_role_model = state
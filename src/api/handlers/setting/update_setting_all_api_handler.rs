@@ -1,9 +1,11 @@
 use std::sync::Arc;
 use axum::extract::State;
 use axum::{Extension, Json};
+use tracing::log::error;
 use crate::api::handlers::setting::request::update_setting_request::UpdateSettingRequest;
 use crate::avored_state::AvoRedState;
 use crate::error::{Error, Result};
+use crate::models::permission_model::Permission;
 use crate::models::setting_model::{SettingModel, UpdatableSettingModel};
 use crate::models::token_claim_model::LoggedInUser;
 use crate::models::validation_error::ErrorResponse;
@@ -15,14 +17,6 @@ pub async fn update_setting_all_api_handler(
 ) -> Result<Json<Vec<SettingModel>>> {
     println!("->> {:<12} - update_setting_all_api_handler", "HANDLER");
 
-    let has_permission_bool = state
-        .admin_user_service
-        .has_permission(logged_in_user.clone(), String::from("save_setting"))
-        .await?;
-    if !has_permission_bool {
-        return Err(Error::Forbidden);
-    }
-
     let error_messages = payload.validate()?;
 
     if !error_messages.is_empty() {
@@ -34,6 +28,17 @@ pub async fn update_setting_all_api_handler(
         return Err(Error::BadRequest(error_response));
     }
 
+    // turning the two factor enforcement on or off changes the sign in of all the admin users.
+    // The settings are updated by id, so the stored identifier is checked and not the sent one.
+    let stored_setting_models = state.setting_service.all(&state.db).await?;
+    let is_two_factor_setting_changed = stored_setting_models
+        .iter()
+        .filter(|setting_model| setting_model.identifier.eq("auth_enforce_two_factor"))
+        .any(|setting_model| payload.settings.iter().any(|setting| setting.id.eq(&setting_model.id)));
+    if is_two_factor_setting_changed && !logged_in_user.has_permission(Permission::SettingSecurityEdit) {
+        return Err(Error::Forbidden);
+    }
+
     for updatable_setting in payload.settings {
         let updatable_setting_model = UpdatableSettingModel {
             id: updatable_setting.id,
@@ -43,6 +48,80 @@ pub async fn update_setting_all_api_handler(
         state.setting_service.update_setting(&state.db, updatable_setting_model).await?;
     }
 
+    let setting_models = state.setting_service.all(&state.db).await?;
+    for setting_model in &setting_models {
+        if let Err(e) = state.asset_reference_service.index_setting(&state.db, setting_model).await {
+            error!("there is an issue while indexing the assets of the setting {}: {e:?}", setting_model.identifier);
+        }
+    }
+
+    Ok(Json(setting_models))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_authenticated_post_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_update_setting_all_api_handler_two_factor_enforcement() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let setting_model = state
+            .setting_service
+            .find_by_identifier(&state.db, String::from("auth_enforce_two_factor"))
+            .await?;
+        let settings_payload = || Body::from(format!(
+            r#"{{"settings": [{{"id": "{}", "identifier": "auth_enforce_two_factor", "value": "false"}}]}}"#,
+            setting_model.id
+        ));
+
+        let expires_at = (chrono::Utc::now() + chrono::Duration::days(30)).to_rfc3339();
+        let mut personal_access_tokens = vec![];
+        for permissions in [r#"["setting_edit"]"#, r#"["setting_edit", "setting_security_edit"]"#] {
+            let payload = Body::from(format!(r#"{{"name": "CI", "permissions": {permissions}, "expires_at": "{expires_at}"}}"#));
+            let response = app.clone()
+                .oneshot(send_authenticated_post_request("/api/personal-access-token", payload, token.clone()))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+            personal_access_tokens.push(body["data"]["token"].as_str().unwrap().to_string());
+        }
 
-    Ok(Json(state.setting_service.all(&state.db).await?))
+        // the super admin behind the token does not lift the limit of the token
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/setting", settings_payload(), personal_access_tokens[0].clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // sending the record id under another identifier does not get around the check
+        let disguised_payload = Body::from(format!(
+            r#"{{"settings": [{{"id": "{}", "identifier": "general_site_name", "value": "false"}}]}}"#,
+            setting_model.id
+        ));
+        let response = app.clone()
+            .oneshot(send_authenticated_post_request("/api/setting", disguised_payload, personal_access_tokens[0].clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .oneshot(send_authenticated_post_request("/api/setting", settings_payload(), personal_access_tokens[1].clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
 }
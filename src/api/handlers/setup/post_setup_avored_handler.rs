@@ -4,10 +4,6 @@ use crate::{
     avored_state::AvoRedState,
     error::Result,
 };
-use argon2::{
-    password_hash::SaltString,
-    Argon2, PasswordHasher,
-};
 use axum::{extract::State, Json, response::IntoResponse};
 use email_address::EmailAddress;
 use serde::{Deserialize, Serialize};
@@ -21,7 +17,7 @@ pub async fn post_setup_avored_handler(
 ) -> Result<impl IntoResponse> {
     println!("->> {:<12} - post_setup_avored_handler", "HANDLER");
 
-    let error_messages = payload.validate()?;
+    let error_messages = payload.validate(&state)?;
 
     if !error_messages.is_empty() {
         let error_response = ErrorResponse {
@@ -55,8 +51,8 @@ pub async fn post_setup_avored_handler(
         };
 
         CREATE settings CONTENT {
-            identifier: 'auth_cms_token',
-            value: '',
+            identifier: 'auth_enforce_two_factor',
+            value: 'false',
             created_by: $email,
             updated_by: $email,
             created_at: time::now(),
@@ -72,6 +68,8 @@ pub async fn post_setup_avored_handler(
         DEFINE FIELD password ON TABLE admin_users TYPE string;
         DEFINE FIELD profile_image ON TABLE admin_users TYPE string;
         DEFINE FIELD is_super_admin ON TABLE admin_users TYPE bool;
+        DEFINE FIELD is_two_factor_enabled ON TABLE admin_users TYPE bool DEFAULT false;
+        DEFINE FIELD is_active ON TABLE admin_users TYPE bool DEFAULT true;
         DEFINE FIELD created_by ON TABLE admin_users TYPE string;
         DEFINE FIELD updated_by ON TABLE admin_users TYPE string;
         DEFINE FIELD created_at ON TABLE admin_users TYPE datetime;
@@ -84,6 +82,8 @@ pub async fn post_setup_avored_handler(
             password: $password,
             profile_image: $profile_image,
             is_super_admin: $is_super_admin,
+            is_two_factor_enabled: false,
+            is_active: true,
             created_by: $email,
             updated_by: $email,
             created_at: time::now(),
@@ -91,11 +91,128 @@ pub async fn post_setup_avored_handler(
         };
 
         REMOVE TABLE password_rest;
-        DEFINE TABLE password_rest;
+        REMOVE TABLE password_reset;
+        DEFINE TABLE password_reset;
+
+        DEFINE FIELD email ON TABLE password_reset TYPE string;
+        DEFINE FIELD token_hash ON TABLE password_reset TYPE string;
+        DEFINE FIELD status ON TABLE password_reset TYPE string;
+        DEFINE FIELD expires_at ON TABLE password_reset TYPE datetime;
+        DEFINE FIELD created_at ON TABLE password_reset TYPE datetime;
+        DEFINE INDEX password_reset_email_index ON TABLE password_reset COLUMNS email;
+
+
+        REMOVE TABLE admin_user_two_factors;
+        DEFINE TABLE admin_user_two_factors;
+
+        DEFINE FIELD admin_user_id ON TABLE admin_user_two_factors TYPE string;
+        DEFINE FIELD secret ON TABLE admin_user_two_factors TYPE string;
+        DEFINE FIELD recovery_codes ON TABLE admin_user_two_factors TYPE array<string>;
+        DEFINE FIELD status ON TABLE admin_user_two_factors TYPE string;
+        DEFINE FIELD created_at ON TABLE admin_user_two_factors TYPE datetime;
+        DEFINE FIELD updated_at ON TABLE admin_user_two_factors TYPE datetime;
+        DEFINE INDEX admin_user_two_factors_admin_user_id_index ON TABLE admin_user_two_factors COLUMNS admin_user_id UNIQUE;
+
+
+        REMOVE TABLE login_attempts;
+        DEFINE TABLE login_attempts;
+
+        DEFINE FIELD action ON TABLE login_attempts TYPE string;
+        DEFINE FIELD key ON TABLE login_attempts TYPE string;
+        DEFINE FIELD created_at ON TABLE login_attempts TYPE datetime;
+        DEFINE INDEX login_attempts_action_key_index ON TABLE login_attempts COLUMNS action, key;
+
+
+        REMOVE TABLE admin_user_lockouts;
+        DEFINE TABLE admin_user_lockouts;
+
+        DEFINE FIELD email ON TABLE admin_user_lockouts TYPE string;
+        DEFINE FIELD token ON TABLE admin_user_lockouts TYPE string;
+        DEFINE FIELD locked_until ON TABLE admin_user_lockouts TYPE datetime;
+        DEFINE FIELD created_at ON TABLE admin_user_lockouts TYPE datetime;
+        DEFINE INDEX admin_user_lockouts_email_index ON TABLE admin_user_lockouts COLUMNS email UNIQUE;
+
+
+        REMOVE TABLE cms_tokens;
+        DEFINE TABLE cms_tokens;
+
+        DEFINE FIELD name ON TABLE cms_tokens TYPE string;
+        DEFINE FIELD token_prefix ON TABLE cms_tokens TYPE string;
+        DEFINE FIELD token_hash ON TABLE cms_tokens TYPE string;
+        DEFINE FIELD scopes ON TABLE cms_tokens TYPE array<string>;
+        DEFINE FIELD allowed_origins ON TABLE cms_tokens TYPE array<string>;
+        DEFINE FIELD expires_at ON TABLE cms_tokens TYPE option<datetime>;
+        DEFINE FIELD last_used_at ON TABLE cms_tokens TYPE option<datetime>;
+        DEFINE FIELD created_by ON TABLE cms_tokens TYPE string;
+        DEFINE FIELD updated_by ON TABLE cms_tokens TYPE string;
+        DEFINE FIELD created_at ON TABLE cms_tokens TYPE datetime;
+        DEFINE FIELD updated_at ON TABLE cms_tokens TYPE datetime;
+        DEFINE INDEX cms_tokens_token_hash_index ON TABLE cms_tokens COLUMNS token_hash UNIQUE;
+
+
+        REMOVE TABLE personal_access_tokens;
+        DEFINE TABLE personal_access_tokens;
+
+        DEFINE FIELD admin_user_id ON TABLE personal_access_tokens TYPE string;
+        DEFINE FIELD name ON TABLE personal_access_tokens TYPE string;
+        DEFINE FIELD token_prefix ON TABLE personal_access_tokens TYPE string;
+        DEFINE FIELD token_hash ON TABLE personal_access_tokens TYPE string;
+        DEFINE FIELD permissions ON TABLE personal_access_tokens TYPE array<string>;
+        DEFINE FIELD expires_at ON TABLE personal_access_tokens TYPE datetime;
+        DEFINE FIELD last_used_at ON TABLE personal_access_tokens TYPE option<datetime>;
+        DEFINE FIELD created_at ON TABLE personal_access_tokens TYPE datetime;
+        DEFINE FIELD updated_at ON TABLE personal_access_tokens TYPE datetime;
+        DEFINE INDEX personal_access_tokens_token_hash_index ON TABLE personal_access_tokens COLUMNS token_hash UNIQUE;
+        DEFINE INDEX personal_access_tokens_admin_user_id_index ON TABLE personal_access_tokens COLUMNS admin_user_id;
+
+
+        REMOVE TABLE oidc_login_states;
+        DEFINE TABLE oidc_login_states;
+
+        DEFINE FIELD state ON TABLE oidc_login_states TYPE string;
+        DEFINE FIELD nonce ON TABLE oidc_login_states TYPE string;
+        DEFINE FIELD code_verifier ON TABLE oidc_login_states TYPE string;
+        DEFINE FIELD expires_at ON TABLE oidc_login_states TYPE datetime;
+        DEFINE FIELD created_at ON TABLE oidc_login_states TYPE datetime;
+        DEFINE INDEX oidc_login_states_state_index ON TABLE oidc_login_states COLUMNS state UNIQUE;
+
+
+        REMOVE TABLE admin_user_identities;
+        DEFINE TABLE admin_user_identities;
+
+        DEFINE FIELD admin_user_id ON TABLE admin_user_identities TYPE string;
+        DEFINE FIELD issuer ON TABLE admin_user_identities TYPE string;
+        DEFINE FIELD subject ON TABLE admin_user_identities TYPE string;
+        DEFINE FIELD created_at ON TABLE admin_user_identities TYPE datetime;
+        DEFINE FIELD updated_at ON TABLE admin_user_identities TYPE datetime;
+        DEFINE INDEX admin_user_identities_issuer_subject_index ON TABLE admin_user_identities COLUMNS issuer, subject UNIQUE;
+
+
+        REMOVE TABLE admin_user_invitations;
+        DEFINE TABLE admin_user_invitations;
+
+        DEFINE FIELD email ON TABLE admin_user_invitations TYPE string;
+        DEFINE FIELD role_ids ON TABLE admin_user_invitations TYPE array<string>;
+        DEFINE FIELD token_hash ON TABLE admin_user_invitations TYPE string;
+        DEFINE FIELD expires_at ON TABLE admin_user_invitations TYPE datetime;
+        DEFINE FIELD created_by ON TABLE admin_user_invitations TYPE string;
+        DEFINE FIELD updated_by ON TABLE admin_user_invitations TYPE string;
+        DEFINE FIELD created_at ON TABLE admin_user_invitations TYPE datetime;
+        DEFINE FIELD updated_at ON TABLE admin_user_invitations TYPE datetime;
+        DEFINE INDEX admin_user_invitations_email_index ON TABLE admin_user_invitations COLUMNS email UNIQUE;
+
+
+        REMOVE TABLE asset_references;
+        DEFINE TABLE asset_references;
 
-        DEFINE FIELD email ON TABLE password_rest TYPE string;
-        DEFINE FIELD token ON TABLE password_rest TYPE string;
-        DEFINE FIELD created_at ON TABLE password_rest TYPE datetime;
+        DEFINE FIELD asset_id ON TABLE asset_references TYPE string;
+        DEFINE FIELD source_type ON TABLE asset_references TYPE string;
+        DEFINE FIELD source_id ON TABLE asset_references TYPE string;
+        DEFINE FIELD source_name ON TABLE asset_references TYPE string;
+        DEFINE FIELD field ON TABLE asset_references TYPE string;
+        DEFINE FIELD created_at ON TABLE asset_references TYPE datetime;
+        DEFINE INDEX asset_references_asset_id_index ON TABLE asset_references COLUMNS asset_id;
+        DEFINE INDEX asset_references_source_index ON TABLE asset_references COLUMNS source_type, source_id;
 
 
         REMOVE TABLE roles;
@@ -107,6 +224,9 @@ pub async fn post_setup_avored_handler(
         DEFINE FIELD updated_by ON TABLE roles TYPE string;
         DEFINE FIELD created_at ON TABLE roles TYPE datetime;
         DEFINE FIELD updated_at ON TABLE roles TYPE datetime;
+        DEFINE FIELD own_pages_only ON TABLE roles TYPE bool DEFAULT false;
+        DEFINE FIELD page_identifier_prefixes ON TABLE roles TYPE array<string> DEFAULT [];
+        DEFINE FIELD asset_folder_ids ON TABLE roles TYPE array<string> DEFAULT [];
         DEFINE INDEX roles_identifier_index ON TABLE roles COLUMNS identifier UNIQUE;
 
         CREATE roles CONTENT {
@@ -149,6 +269,7 @@ pub async fn post_setup_avored_handler(
         DEFINE FIELD updated_by ON TABLE pages TYPE string;
         DEFINE FIELD created_at ON TABLE pages TYPE datetime;
         DEFINE FIELD updated_at ON TABLE pages TYPE datetime;
+        DEFINE FIELD locked_role_ids ON TABLE pages TYPE array<string> DEFAULT [];
         DEFINE INDEX pages_identifier_index ON TABLE pages COLUMNS identifier UNIQUE;
 
         REMOVE TABLE assets;
@@ -159,14 +280,9 @@ pub async fn post_setup_avored_handler(
 
     ";
 
-    let password = payload.password.as_bytes();
-    let salt = SaltString::from_b64(&state.config.password_salt)?;
-
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(password, &salt)
-        .expect("Error occurred while encrypted password")
-        .to_string();
+    let password_hash = state
+        .password_service
+        .hash_password(&payload.password)?;
 
     let vars = BTreeMap::from([
         ("full_name".into(), "Admin".into()),
@@ -208,7 +324,7 @@ pub struct SetupAvoRedRequest {
 }
 
 impl SetupAvoRedRequest {
-    fn validate(&self) -> Result<Vec<ErrorMessage>> {
+    fn validate(&self, state: &AvoRedState) -> Result<Vec<ErrorMessage>> {
         let mut errors: Vec<ErrorMessage> = vec![];
 
         if self.email.is_empty() {
@@ -238,6 +354,35 @@ impl SetupAvoRedRequest {
             errors.push(error_message);
         }
 
+        // the super admin is created with the full name "Admin", see the vars below
+        errors.append(&mut state.password_service.validate_password(&self.password, &self.email, "Admin"));
+
         Ok(errors)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, send_post_request};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_post_setup_avored_handler_password_policy() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+
+        let payload = Body::from(
+            r#"{
+                    "email": "admin@admin.com",
+                    "password": "admin123"
+                }"#,
+        );
+        let response = app.oneshot(send_post_request("/api/setup", payload)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
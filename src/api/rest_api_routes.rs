@@ -1,27 +1,44 @@
 use std::sync::Arc;
 use axum::{middleware, routing::get, Extension, Router};
+use axum::extract::DefaultBodyLimit;
 use axum::routing::{delete, on, post, put, MethodFilter};
 use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
 use axum::http::HeaderValue;
 use juniper::{EmptyMutation, EmptySubscription};
 use crate::avored_state::AvoRedState;
 use crate::middleware::require_jwt_authentication::require_jwt_authentication;
+use crate::middleware::require_permission::RequirePermission;
+use crate::middleware::require_session_token::RequireSessionToken;
+use crate::middleware::static_file_cache::static_file_cache;
+use crate::models::permission_model::Permission;
 use tower_http::cors::CorsLayer;
+use tower_http::services::ServeDir;
 use crate::api::handlers::{
 
     page::delete_page_handler::delete_page_handler,
     admin_user::admin_user_forgot_password_api_handler::admin_user_forgot_password_api_handler,
     admin_user::admin_user_login_api_handler::admin_user_login_api_handler,
+    admin_user::admin_user_two_factor_login_api_handler::admin_user_two_factor_login_api_handler,
+    admin_user::admin_user_two_factor_enrol_login_api_handler::admin_user_two_factor_enrol_login_api_handler,
+    admin_user::two_factor_enrol_api_handler::two_factor_enrol_api_handler,
+    admin_user::two_factor_confirm_api_handler::two_factor_confirm_api_handler,
+    admin_user::two_factor_disable_api_handler::two_factor_disable_api_handler,
     admin_user::admin_user_reset_password_api_handler::admin_user_reset_password_api_handler,
+    admin_user::unlock_account_api_handler::unlock_account_api_handler,
     admin_user::admin_user_table_api_handler::admin_user_table_api_handler,
     admin_user::change_password_api_handler::change_password_api_handler,
     admin_user::fetch_admin_user_api_handler::fetch_admin_user_api_handler,
     admin_user::logged_in_user_api_handler::logged_in_user_api_handler,
     admin_user::store_admin_user_api_handler::store_admin_user_api_handler,
     admin_user::update_admin_user_api_handler::update_admin_user_api_handler,
+    admin_user::deactivate_admin_user_api_handler::deactivate_admin_user_api_handler,
+    admin_user::reactivate_admin_user_api_handler::reactivate_admin_user_api_handler,
+    admin_user::update_admin_user_avatar_api_handler::update_admin_user_avatar_api_handler,
     asset::asset_table_api_handler::asset_table_api_handler,
     asset::store_asset_api_handler::store_asset_api_handler,
     cms::fetch_page_cms_api_handler::fetch_page_cms_api_handler,
+    cms::fetch_asset_cms_api_handler::fetch_asset_cms_api_handler,
+    cms::signed_asset_url_cms_api_handler::signed_asset_url_cms_api_handler,
     component::component_table_api_handler::component_table_api_handler,
     component::fetch_component_api_handler::fetch_component_api_handler,
     component::store_component_api_handler::store_component_api_handler,
@@ -53,8 +70,42 @@ use crate::api::handlers::{
     asset::delete_folder_api_handler::delete_folder_api_handler,
     asset::rename_asset_api_handler::rename_asset_api_handler,
     asset::delete_asset_api_handler::delete_asset_api_handler,
+    asset::image_rendition_api_handler::image_rendition_api_handler,
+    asset::update_asset_api_handler::update_asset_api_handler,
+    asset::move_asset_api_handler::move_asset_api_handler,
+    asset::bulk_delete_asset_api_handler::bulk_delete_asset_api_handler,
+    asset::bulk_move_asset_api_handler::bulk_move_asset_api_handler,
+    asset::bulk_tag_asset_api_handler::bulk_tag_asset_api_handler,
+    asset::asset_usage_api_handler::asset_usage_api_handler,
+    asset::asset_duplicate_api_handler::asset_duplicate_api_handler,
+    asset::store_asset_archive_api_handler::store_asset_archive_api_handler,
+    asset::download_asset_archive_api_handler::download_asset_archive_api_handler,
+    asset::asset_search_api_handler::asset_search_api_handler,
+    asset::update_asset_visibility_api_handler::update_asset_visibility_api_handler,
+    asset::fetch_private_asset_api_handler::fetch_private_asset_api_handler,
+    asset::fetch_signed_asset_api_handler::fetch_signed_asset_api_handler,
 };
 use crate::api::handlers::cms::all_pages_cms_api_handler::all_pages_cms_api_handler;
+use crate::api::handlers::cms::all_models_cms_api_handler::all_models_cms_api_handler;
+use crate::api::handlers::cms::fetch_model_cms_api_handler::fetch_model_cms_api_handler;
+use crate::api::handlers::cms_token::cms_token_table_api_handler::cms_token_table_api_handler;
+use crate::api::handlers::cms_token::revoke_cms_token_api_handler::revoke_cms_token_api_handler;
+use crate::api::handlers::cms_token::rotate_cms_token_api_handler::rotate_cms_token_api_handler;
+use crate::api::handlers::cms_token::store_cms_token_api_handler::store_cms_token_api_handler;
+use crate::api::handlers::personal_access_token::admin_user_personal_access_token_table_api_handler::admin_user_personal_access_token_table_api_handler;
+use crate::api::handlers::personal_access_token::personal_access_token_table_api_handler::personal_access_token_table_api_handler;
+use crate::api::handlers::personal_access_token::revoke_admin_user_personal_access_token_api_handler::revoke_admin_user_personal_access_token_api_handler;
+use crate::api::handlers::personal_access_token::revoke_personal_access_token_api_handler::revoke_personal_access_token_api_handler;
+use crate::api::handlers::personal_access_token::store_personal_access_token_api_handler::store_personal_access_token_api_handler;
+use crate::api::handlers::role::permission_all_api_handler::permission_all_api_handler;
+use crate::api::handlers::admin_user_invitation::accept_admin_user_invitation_api_handler::accept_admin_user_invitation_api_handler;
+use crate::api::handlers::admin_user_invitation::admin_user_invitation_table_api_handler::admin_user_invitation_table_api_handler;
+use crate::api::handlers::admin_user_invitation::resend_admin_user_invitation_api_handler::resend_admin_user_invitation_api_handler;
+use crate::api::handlers::admin_user_invitation::revoke_admin_user_invitation_api_handler::revoke_admin_user_invitation_api_handler;
+use crate::api::handlers::admin_user_invitation::store_admin_user_invitation_api_handler::store_admin_user_invitation_api_handler;
+use crate::api::handlers::oidc::oidc_authorize_api_handler::oidc_authorize_api_handler;
+use crate::api::handlers::oidc::oidc_callback_api_handler::oidc_callback_api_handler;
+use crate::api::handlers::oidc::oidc_link_api_handler::oidc_link_api_handler;
 use crate::api::handlers::cms::sent_contact_us_email_handler::sent_contact_us_email_handler;
 use crate::api::handlers::graphql::graphql_api_handler::graphql_api_handler;
 use crate::api::handlers::misc::delete_demo_data_api_handler::delete_demo_data_api_handler;
@@ -69,6 +120,7 @@ pub fn rest_api_routes(state: Arc<AvoRedState>) -> Router {
     Router::new()
         .merge(admin_api_routes(state.clone()))
         .merge(cms_api_routes(state.clone()))
+        .merge(public_file_routes())
 
 }
 
@@ -79,6 +131,10 @@ fn cms_api_routes(state: Arc<AvoRedState>) -> Router {
     Router::new()
         .route("/cms/page/:page_id", get(fetch_page_cms_api_handler))
         .route("/cms/page", get(all_pages_cms_api_handler))
+        .route("/cms/model/:model_id", get(fetch_model_cms_api_handler))
+        .route("/cms/model", get(all_models_cms_api_handler))
+        .route("/cms/asset/:asset_id", get(fetch_asset_cms_api_handler))
+        .route("/cms/asset/:asset_id/signed-url", get(signed_asset_url_cms_api_handler))
         .route("/cms/sent-contact-us-email", post(sent_contact_us_email_handler))
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
@@ -88,6 +144,14 @@ fn cms_api_routes(state: Arc<AvoRedState>) -> Router {
         .layer(cors)
 }
 
+/// Only these folders of `public` are served, the logs next to them are not.
+fn public_file_routes() -> Router {
+    Router::new()
+        .nest_service("/public/upload", ServeDir::new("public/upload"))
+        .nest_service("/public/images", ServeDir::new("public/images"))
+        .layer(middleware::from_fn(static_file_cache))
+}
+
 fn admin_api_routes(state: Arc<AvoRedState>) -> Router {
     let cors = get_cors_urls(state.clone());
     let schema = AvoRedGraphqlSchema::new(
@@ -95,63 +159,107 @@ fn admin_api_routes(state: Arc<AvoRedState>) -> Router {
         EmptyMutation::new(),
         EmptySubscription::new()
     );
+    let asset_body_limit = DefaultBodyLimit::max(state.asset_upload_service.max_request_size());
+    let asset_archive_body_limit = DefaultBodyLimit::max(state.asset_archive_service.max_request_size());
+    let avatar_body_limit = DefaultBodyLimit::max(state.avatar_service.max_request_size());
 
     Router::new()
-        .route("/api/component", get(component_table_api_handler))
-        .route("/api/component", post(store_component_api_handler))
-        .route("/api/component/:component_id", get(fetch_component_api_handler))
-        .route("/api/component/:component_id", put(update_component_api_handler))
-        .route("/api/put-component-identifier/:page_id", put(put_component_identifier_api_handler))
-        .route("/api/asset", get(asset_table_api_handler))
-        .route("/api/asset", post(store_asset_api_handler))
-        .route("/api/rename-asset/:asset_id", post(rename_asset_api_handler))
-        .route("/api/create-folder", post(create_folder_api_handler))
-        .route("/api/delete-folder/:asset_id", delete(delete_folder_api_handler))
-        .route("/api/delete-asset/:asset_id", delete(delete_asset_api_handler))
+        .route("/api/component", get(component_table_api_handler).require_permission(Permission::ComponentTable))
+        .route("/api/component", post(store_component_api_handler).require_permission(Permission::ComponentCreate))
+        .route("/api/component/:component_id", get(fetch_component_api_handler).require_permission(Permission::ComponentShow))
+        .route("/api/component/:component_id", put(update_component_api_handler).require_permission(Permission::ComponentEdit))
+        .route("/api/put-component-identifier/:page_id", put(put_component_identifier_api_handler).require_permission(Permission::ComponentEdit))
+        .route("/api/asset", get(asset_table_api_handler).require_permission(Permission::AssetTable))
+        .route("/api/asset", post(store_asset_api_handler).require_permission(Permission::AssetCreate).layer(asset_body_limit))
+        .route("/api/asset/:asset_id", put(update_asset_api_handler).require_permission(Permission::AssetEdit))
+        .route("/api/rename-asset/:asset_id", post(rename_asset_api_handler).require_permission(Permission::AssetEdit))
+        .route("/api/move-asset/:asset_id", post(move_asset_api_handler).require_permission(Permission::AssetEdit))
+        .route("/api/asset-visibility/:asset_id", post(update_asset_visibility_api_handler).require_permission(Permission::AssetEdit))
+        .route("/api/private-asset/:asset_id", get(fetch_private_asset_api_handler).require_permission(Permission::AssetTable))
+        .route("/api/create-folder", post(create_folder_api_handler).require_permission(Permission::AssetCreate))
+        .route("/api/delete-folder/:asset_id", delete(delete_folder_api_handler).require_permission(Permission::AssetDelete))
+        .route("/api/delete-asset/:asset_id", delete(delete_asset_api_handler).require_permission(Permission::AssetDelete))
+        .route("/api/asset-search", get(asset_search_api_handler).require_permission(Permission::AssetTable))
+        .route("/api/asset-duplicates", get(asset_duplicate_api_handler).require_permission(Permission::AssetTable))
+        .route("/api/asset-usage/:asset_id", get(asset_usage_api_handler).require_permission(Permission::AssetTable))
+        .route("/api/asset-archive", post(store_asset_archive_api_handler).require_permission(Permission::AssetCreate).layer(asset_archive_body_limit))
+        .route("/api/asset-archive/:asset_id", get(download_asset_archive_api_handler).require_permission(Permission::AssetTable))
+        .route("/api/bulk-delete-asset", post(bulk_delete_asset_api_handler).require_permission(Permission::AssetDelete))
+        .route("/api/bulk-move-asset", post(bulk_move_asset_api_handler).require_permission(Permission::AssetEdit))
+        .route("/api/bulk-tag-asset", post(bulk_tag_asset_api_handler).require_permission(Permission::AssetEdit))
         .route("/api/role-options", get(role_option_api_handler))
-        .route("/api/role", get(role_table_api_handler))
-        .route("/api/role", post(store_role_api_handler))
-        .route("/api/role/:role_id", get(fetch_role_api_handler))
-        .route("/api/put-role-identifier/:role_id", put(put_role_identifier_api_handler))
-        .route("/api/role/:role_id", put(update_role_api_handler))
-        .route("/api/admin-user", get(admin_user_table_api_handler))
-        .route("/api/admin-user", post(store_admin_user_api_handler))
-        .route("/api/change-password", post(change_password_api_handler))
-        .route("/api/admin-user/:admin_user_id", put(update_admin_user_api_handler))
+        .route("/api/permissions", get(permission_all_api_handler))
+        .route("/api/role", get(role_table_api_handler).require_permission(Permission::RoleTable))
+        .route("/api/role", post(store_role_api_handler).require_permission(Permission::RoleCreate))
+        .route("/api/role/:role_id", get(fetch_role_api_handler).require_permission(Permission::RoleShow))
+        .route("/api/put-role-identifier/:role_id", put(put_role_identifier_api_handler).require_permission(Permission::RoleEdit))
+        .route("/api/role/:role_id", put(update_role_api_handler).require_permission(Permission::RoleEdit))
+        .route("/api/admin-user", get(admin_user_table_api_handler).require_permission(Permission::AdminUserTable))
+        .route("/api/admin-user", post(store_admin_user_api_handler).require_permission(Permission::AdminUserCreate).layer(avatar_body_limit))
+        .route("/api/change-password", post(change_password_api_handler).require_session_token())
+        .route("/api/two-factor/enrol", post(two_factor_enrol_api_handler).require_session_token())
+        .route("/api/two-factor/confirm", post(two_factor_confirm_api_handler).require_session_token())
+        .route("/api/two-factor/disable", post(two_factor_disable_api_handler).require_session_token())
+        .route("/api/oidc/link", post(oidc_link_api_handler).require_session_token())
+        .route("/api/admin-user/:admin_user_id", put(update_admin_user_api_handler).require_permission(Permission::AdminUserEdit).layer(avatar_body_limit))
         .route("/api/logged-in-user", get(logged_in_user_api_handler))
-        .route("/api/admin-user/:admin_user_id", get(fetch_admin_user_api_handler))
-        .route("/api/model", get(model_table_api_handler))
-        .route("/api/model", post(store_model_api_handler))
-        .route("/api/model/:model_id", put(update_model_api_handler))
-        .route("/api/model/:model_id", get(fetch_model_api_handler))
-        .route("/api/put-model-identifier/:model_id", put(put_model_identifier_api_handler))
-        .route("/api/page", get(page_table_api_handler))
-        .route("/api/page", post(store_page_api_handler))
-        .route("/api/page/:page_id", put(update_page_api_handler))
-        .route("/api/page/:page_id", get(fetch_page_api_handler))
-        .route("/api/page/:page_id", delete(delete_page_handler))
-        .route("/api/put-page-identifier/:page_id", put(put_page_identifier_api_handler))
+        .route("/api/admin-user/:admin_user_id", get(fetch_admin_user_api_handler).require_permission(Permission::AdminUserShow))
+        .route("/api/admin-user/:admin_user_id/deactivate", post(deactivate_admin_user_api_handler).require_permission(Permission::AdminUserEdit))
+        .route("/api/admin-user/:admin_user_id/reactivate", post(reactivate_admin_user_api_handler).require_permission(Permission::AdminUserEdit))
+        .route("/api/admin-user/:admin_user_id/avatar", post(update_admin_user_avatar_api_handler).layer(avatar_body_limit))
+        .route("/api/admin-user/:admin_user_id/personal-access-token", get(admin_user_personal_access_token_table_api_handler).require_permission(Permission::PersonalAccessTokenTable))
+        .route("/api/admin-user/:admin_user_id/personal-access-token/:personal_access_token_id", delete(revoke_admin_user_personal_access_token_api_handler).require_permission(Permission::PersonalAccessTokenDelete))
+        .route("/api/admin-user-invitation", get(admin_user_invitation_table_api_handler).require_permission(Permission::AdminUserTable))
+        .route("/api/admin-user-invitation", post(store_admin_user_invitation_api_handler).require_permission(Permission::AdminUserCreate))
+        .route("/api/admin-user-invitation/:admin_user_invitation_id/resend", post(resend_admin_user_invitation_api_handler).require_permission(Permission::AdminUserCreate))
+        .route("/api/admin-user-invitation/:admin_user_invitation_id", delete(revoke_admin_user_invitation_api_handler).require_permission(Permission::AdminUserDelete))
+        .route("/api/personal-access-token", get(personal_access_token_table_api_handler))
+        .route("/api/personal-access-token", post(store_personal_access_token_api_handler).require_session_token())
+        .route("/api/personal-access-token/:personal_access_token_id", delete(revoke_personal_access_token_api_handler))
+        .route("/api/model", get(model_table_api_handler).require_permission(Permission::ModelTable))
+        .route("/api/model", post(store_model_api_handler).require_permission(Permission::ModelCreate))
+        .route("/api/model/:model_id", put(update_model_api_handler).require_permission(Permission::ModelEdit))
+        .route("/api/model/:model_id", get(fetch_model_api_handler).require_permission(Permission::ModelShow))
+        .route("/api/put-model-identifier/:model_id", put(put_model_identifier_api_handler).require_permission(Permission::ModelEdit))
+        .route("/api/page", get(page_table_api_handler).require_permission(Permission::PageTable))
+        .route("/api/page", post(store_page_api_handler).require_permission(Permission::PageCreate))
+        .route("/api/page/:page_id", put(update_page_api_handler).require_permission(Permission::PageEdit))
+        .route("/api/page/:page_id", get(fetch_page_api_handler).require_permission(Permission::PageShow))
+        .route("/api/page/:page_id", delete(delete_page_handler).require_permission(Permission::PageDelete))
+        .route("/api/put-page-identifier/:page_id", put(put_page_identifier_api_handler).require_permission(Permission::PageEdit))
         .route("/api/component-all", get(component_all_api_handler))
         .route("/api/openapi.json", get(openapi_api_handler))
-        .route("/api/setting", get(setting_all_api_handler))
-        .route("/api/setting", post(update_setting_all_api_handler))
-        .route("/api/install-demo-data", post(install_demo_data_api_handler))
-        .route("/api/delete-demo-data", post(delete_demo_data_api_handler))
+        .route("/api/setting", get(setting_all_api_handler).require_permission(Permission::SettingShow))
+        .route("/api/setting", post(update_setting_all_api_handler).require_permission(Permission::SettingEdit))
+        .route("/api/cms-token", get(cms_token_table_api_handler).require_permission(Permission::CmsTokenTable))
+        .route("/api/cms-token", post(store_cms_token_api_handler).require_permission(Permission::CmsTokenCreate))
+        .route("/api/cms-token/:cms_token_id/rotate", post(rotate_cms_token_api_handler).require_permission(Permission::CmsTokenEdit))
+        .route("/api/cms-token/:cms_token_id", delete(revoke_cms_token_api_handler).require_permission(Permission::CmsTokenDelete))
+        .route("/api/install-demo-data", post(install_demo_data_api_handler).require_session_token())
+        .route("/api/delete-demo-data", post(delete_demo_data_api_handler).require_session_token())
         // .route("/test", get(test_handler))
         .route("/graphql", on(
             MethodFilter::GET.or(MethodFilter::POST),
             graphql_api_handler,
-        ),)
+        ).require_session_token())
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             require_jwt_authentication,
         ))
         .route("/api/health-check", get(health_check_api_handler))
         .route("/api/setup", post(post_setup_avored_handler))
+        .route("/public/image/:asset_id", get(image_rendition_api_handler).layer(middleware::from_fn(static_file_cache)))
+        .route("/signed-asset/:asset_id", get(fetch_signed_asset_api_handler))
         .route("/api/login", post(admin_user_login_api_handler))
+        .route("/api/login/two-factor", post(admin_user_two_factor_login_api_handler))
+        .route("/api/login/two-factor/enrol", post(admin_user_two_factor_enrol_login_api_handler))
         .route("/api/testing", post(testing_api_handler))
         .route("/api/reset-password", post(admin_user_reset_password_api_handler))
         .route("/api/forgot-password", post(admin_user_forgot_password_api_handler))
+        .route("/api/unlock-account", post(unlock_account_api_handler))
+        .route("/api/accept-invitation", post(accept_admin_user_invitation_api_handler))
+        .route("/api/oidc/authorize", get(oidc_authorize_api_handler))
+        .route("/api/oidc/callback", post(oidc_callback_api_handler))
         .with_state(state)
         .layer(cors)
         .layer(Extension(Arc::new(schema)))
@@ -183,14 +291,13 @@ pub mod tests {
     use axum::body::Body;
     use axum::http::{self, header, Request, StatusCode};
     use axum::Router;
-    use jsonwebtoken::{encode, EncodingKey, Header};
     use tower::ServiceExt;
     use crate::api::handlers::admin_user::admin_user_login_api_handler::LoginResponseData;
     use crate::api::handlers::setup::post_setup_avored_handler::SetupViewModel;
     use crate::avored_state::AvoRedState;
-    use crate::error::Result;
+    use crate::error::{Error, Result};
     use crate::models::admin_user_model::AdminUserModel;
-    use crate::models::token_claim_model::TokenClaims;
+    use crate::repositories::into_iter_objects;
 
     use super::rest_api_routes;
 
@@ -214,11 +321,38 @@ pub mod tests {
             .unwrap()
     }
 
+    pub fn send_authenticated_post_request(uri: &str, body: Body, token: String) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .method("POST")
+            .body(body)
+            .unwrap()
+    }
+
+    pub fn send_asset_upload_request(file_name: &str, content_type: &str, data: Vec<u8>, token: String) -> Request<Body> {
+        let boundary = "AvoRedAssetBoundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\nContent-Type: {content_type}\r\n\r\n"
+        ).into_bytes();
+        body.extend(data);
+        body.extend(format!("\r\n--{boundary}--\r\n").into_bytes());
+
+        Request::builder()
+            .uri("/api/asset")
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={boundary}"))
+            .method("POST")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
     pub async fn setup_avored_db(app: Router)  {
         let payload = Body::from(
             r#"{
                     "email": "admin@admin.com",
-                    "password": "admin123"
+                    "password": "Tr0ub4dor&Horse"
                 }"#,
         );
         let expected_response = SetupViewModel {
@@ -245,7 +379,7 @@ pub mod tests {
         let payload = Body::from(
             r#"{
                     "email": "admin@admin.com",
-                    "password": "admin123"
+                    "password": "Tr0ub4dor&Horse"
                 }"#,
         );
 
@@ -264,28 +398,40 @@ pub mod tests {
         Ok(body)
     }
 
-    pub fn get_auth_token(state: Arc<AvoRedState>) -> Result<String> {
-        let now = chrono::Utc::now();
-        let iat = now.timestamp() as usize;
-        let exp = (now + chrono::Duration::minutes(60)).timestamp() as usize;
-        let admin_user_model = AdminUserModel::default();
-
-        let claims: TokenClaims = TokenClaims {
-            sub: admin_user_model.clone().id,
-            name: admin_user_model.clone().full_name,
-            email:admin_user_model.clone().email,
-            admin_user_model: admin_user_model.clone(),
-            exp,
-            iat,
+    /// The jwt middleware only accepts tokens of an existing and active admin user.
+    pub async fn get_auth_token(state: Arc<AvoRedState>) -> Result<String> {
+        let (datastore, database_session) = &state.db;
+        let sql = "
+            CREATE admin_users CONTENT {
+                full_name: 'Unit Test',
+                email: 'unittest@avored.com',
+                is_super_admin: true,
+                is_active: true
+            };";
+
+        let responses = datastore.execute(sql, database_session, None).await?;
+        let admin_user_model: AdminUserModel = match into_iter_objects(responses)?.next() {
+            Some(object) => object?.try_into()?,
+            None => return Err(Error::Generic("no record found".to_string())),
         };
-        Ok(encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(state.config.jwt_secret_key.as_ref()),
-        ).unwrap())
+
+        state
+            .admin_user_service
+            .create_login_token(admin_user_model, &state.config.jwt_secret_key)
     }
 
     pub async fn get_axum_app() -> Result<(Router, Arc<AvoRedState>)>
+    {
+        let state = Arc::new(get_avored_state().await?);
+
+        let app = rest_api_routes(state.clone());
+
+        Ok((app, state))
+    }
+
+    /// The state behind get_axum_app, for tests that need to swap a service before the
+    /// routes are built.
+    pub async fn get_avored_state() -> Result<AvoRedState>
     {
         env::set_var("AVORED_DATABASE_NAMESPACE", "public_test");
         env::set_var("AVORED_DATABASE_NAME", "avored_cms_test");
@@ -293,8 +439,18 @@ pub mod tests {
 
 
         env::set_var("AVORED_PASSWORD_SALT", "UnitTestUnitTestUnitTestUnitTestUnitTestUnitTestUnitTestUnitTest");
+        env::set_var("AVORED_PASSWORD_MIN_LENGTH", "10");
+        env::set_var("AVORED_PASSWORD_REQUIRE_UPPERCASE", "true");
+        env::set_var("AVORED_PASSWORD_REQUIRE_LOWERCASE", "true");
+        env::set_var("AVORED_PASSWORD_REQUIRE_NUMBER", "true");
+        env::set_var("AVORED_PASSWORD_REQUIRE_SYMBOL", "false");
+        env::set_var("AVORED_PASSWORD_BREACHED_LIST_PATH", "resources/passwords/breached-passwords.txt");
+        env::set_var("AVORED_ARGON2_MEMORY_KIB", "19456");
+        env::set_var("AVORED_ARGON2_ITERATIONS", "2");
+        env::set_var("AVORED_ARGON2_PARALLELISM", "1");
 
         env::set_var("AVORED_JWT_SECRET", "UnitTestUnitTestUnitTestUnitTestUnitTestUnitTestUnitTestUnitTest");
+        env::set_var("AVORED_ASSET_URL_SIGNING_SECRET", "UnitTestUnitTestUnitTestUnitTestUnitTestUnitTestUnitTestUnitTest");
         env::set_var("AVORED_JWT_EXPIRED_IN", "60");
         env::set_var("AVORED_JWT_MAXAGE", "60");
 
@@ -307,10 +463,15 @@ pub mod tests {
         env::set_var("SMTP_PASSWORD", "smtp_password");
         env::set_var("SMTP_PORT", "587");
 
-        let state = Arc::new(AvoRedState::new().await?);
+        env::set_var("AVORED_LOGIN_MAX_ATTEMPTS_PER_IP", "20");
+        env::set_var("AVORED_LOGIN_MAX_ATTEMPTS_PER_EMAIL", "5");
+        env::set_var("AVORED_LOGIN_ATTEMPT_WINDOW_SECONDS", "900");
+        env::set_var("AVORED_LOGIN_LOCKOUT_SECONDS", "900");
+        env::set_var("AVORED_FORGOT_PASSWORD_MAX_ATTEMPTS_PER_IP", "10");
+        env::set_var("AVORED_FORGOT_PASSWORD_MAX_ATTEMPTS_PER_EMAIL", "3");
+        env::set_var("AVORED_FORGOT_PASSWORD_ATTEMPT_WINDOW_SECONDS", "3600");
+        env::set_var("AVORED_TRUST_PROXY_HEADERS", "false");
 
-        let app = rest_api_routes(state.clone());
-
-        Ok((app, state))
+        AvoRedState::new().await
     }
 }
@@ -2,7 +2,10 @@ use crate::error::Result;
 use crate::providers::avored_config_provider::AvoRedConfigProvider;
 use crate::providers::avored_database_provider::{AvoRedDatabaseProvider, DB};
 use crate::providers::avored_template_provider::AvoRedTemplateProvider;
+use crate::providers::avored_storage_provider::AvoRedStorageProvider;
 use crate::repositories::admin_user_repository::AdminUserRepository;
+use crate::repositories::admin_user_two_factor_repository::AdminUserTwoFactorRepository;
+use crate::repositories::admin_user_lockout_repository::AdminUserLockoutRepository;
 use crate::repositories::component_repository::ComponentRepository;
 use crate::repositories::page_repository::PageRepository;
 use crate::repositories::role_repository::RoleRepository;
@@ -11,11 +14,29 @@ use crate::services::component_service::ComponentService;
 use crate::services::page_service::PageService;
 use crate::services::role_service::RoleService;
 use crate::repositories::asset_repository::AssetRepository;
+use crate::repositories::asset_reference_repository::AssetReferenceRepository;
 use crate::repositories::model_repository::ModelRepository;
 use crate::repositories::password_reset_repository::PasswordResetRepository;
 use crate::repositories::setting_repository::SettingRepository;
+use crate::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::repositories::cms_token_repository::CmsTokenRepository;
+use crate::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+use crate::repositories::oidc_login_state_repository::OidcLoginStateRepository;
+use crate::repositories::admin_user_identity_repository::AdminUserIdentityRepository;
+use crate::repositories::admin_user_invitation_repository::AdminUserInvitationRepository;
 use crate::services::asset_service::AssetService;
+use crate::services::asset_reference_service::AssetReferenceService;
 use crate::services::cms_service::CmsService;
+use crate::services::login_throttle_service::LoginThrottleService;
+use crate::services::password_service::PasswordService;
+use crate::services::cms_token_service::CmsTokenService;
+use crate::services::personal_access_token_service::PersonalAccessTokenService;
+use crate::services::oidc_service::OidcService;
+use crate::services::admin_user_invitation_service::AdminUserInvitationService;
+use crate::services::avatar_service::AvatarService;
+use crate::services::image_service::ImageService;
+use crate::services::asset_upload_service::AssetUploadService;
+use crate::services::asset_archive_service::AssetArchiveService;
 use crate::services::model_service::ModelService;
 use crate::services::setting_service::SettingService;
 
@@ -30,7 +51,18 @@ pub struct AvoRedState {
     pub asset_service: AssetService,
     pub setting_service: SettingService,
     pub model_service: ModelService,
-    pub cms_service: CmsService
+    pub cms_service: CmsService,
+    pub login_throttle_service: LoginThrottleService,
+    pub password_service: PasswordService,
+    pub cms_token_service: CmsTokenService,
+    pub personal_access_token_service: PersonalAccessTokenService,
+    pub oidc_service: OidcService,
+    pub admin_user_invitation_service: AdminUserInvitationService,
+    pub avatar_service: AvatarService,
+    pub image_service: ImageService,
+    pub asset_upload_service: AssetUploadService,
+    pub asset_reference_service: AssetReferenceService,
+    pub asset_archive_service: AssetArchiveService
 }
 
 impl juniper::Context for AvoRedState{}
@@ -44,6 +76,7 @@ impl AvoRedState {
         let avored_template_provider = AvoRedTemplateProvider::register(
             avored_config_provider.clone()
         ).await?;
+        let avored_storage_provider = AvoRedStorageProvider::register(&avored_config_provider)?;
 
         let model_repository = ModelRepository::new();
         let admin_user_repository = AdminUserRepository::new();
@@ -51,17 +84,73 @@ impl AvoRedState {
         let component_repository = ComponentRepository::new();
         let page_repository = PageRepository::new();
         let asset_repository = AssetRepository::new();
+        let asset_reference_repository = AssetReferenceRepository::new();
         let password_reset_repository = PasswordResetRepository::new();
         let setting_repository = SettingRepository::new();
+        let admin_user_two_factor_repository = AdminUserTwoFactorRepository::new();
+        let login_attempt_repository = LoginAttemptRepository::new();
+        let admin_user_lockout_repository = AdminUserLockoutRepository::new();
+        let cms_token_repository = CmsTokenRepository::new();
+        let personal_access_token_repository = PersonalAccessTokenRepository::new();
+        let oidc_login_state_repository = OidcLoginStateRepository::new();
+        let admin_user_identity_repository = AdminUserIdentityRepository::new();
+        let admin_user_invitation_repository = AdminUserInvitationRepository::new();
 
-        let admin_user_service = AdminUserService::new(admin_user_repository, role_repository.clone(), password_reset_repository.clone())?;
-        let role_service = RoleService::new(role_repository)?;
+        let admin_user_service = AdminUserService::new(
+            admin_user_repository.clone(),
+            role_repository.clone(),
+            password_reset_repository.clone(),
+            admin_user_two_factor_repository
+        )?;
+        let role_service = RoleService::new(role_repository.clone())?;
+        let asset_reference_service = AssetReferenceService::new(
+            asset_reference_repository.clone(),
+            asset_repository.clone(),
+            page_repository.clone(),
+            component_repository.clone(),
+            model_repository.clone(),
+            setting_repository.clone(),
+            avored_storage_provider.storage.clone()
+        )?;
         let component_service = ComponentService::new(component_repository)?;
         let page_service = PageService::new(page_repository)?;
-        let asset_service = AssetService::new(asset_repository)?;
+        let asset_service = AssetService::new(
+            &avored_config_provider,
+            asset_repository,
+            asset_reference_repository,
+            avored_storage_provider.storage.clone(),
+            avored_storage_provider.private_storage.clone()
+        )?;
         let setting_service = SettingService::new(setting_repository)?;
         let model_service = ModelService::new(model_repository)?;
         let cms_service = CmsService::new()?;
+        let login_throttle_service = LoginThrottleService::new(
+            login_attempt_repository,
+            admin_user_lockout_repository
+        )?;
+        let password_service = PasswordService::new(&avored_config_provider)?;
+        let cms_token_service = CmsTokenService::new(cms_token_repository)?;
+        let personal_access_token_service = PersonalAccessTokenService::new(personal_access_token_repository)?;
+        let oidc_service = OidcService::new(
+            &avored_config_provider,
+            oidc_login_state_repository,
+            admin_user_identity_repository,
+            admin_user_repository.clone(),
+            role_repository
+        )?;
+        let admin_user_invitation_service = AdminUserInvitationService::new(
+            &avored_config_provider,
+            admin_user_invitation_repository,
+            admin_user_repository
+        )?;
+        let avatar_service = AvatarService::new(&avored_config_provider, avored_storage_provider.storage.clone())?;
+        let asset_archive_service = AssetArchiveService::new(
+            &avored_config_provider,
+            avored_storage_provider.storage.clone(),
+            avored_storage_provider.private_storage
+        )?;
+        let image_service = ImageService::new(&avored_config_provider, avored_storage_provider.storage)?;
+        let asset_upload_service = AssetUploadService::new(&avored_config_provider)?;
 
         Ok(AvoRedState {
             config: avored_config_provider,
@@ -74,7 +163,18 @@ impl AvoRedState {
             asset_service,
             setting_service,
             model_service,
-            cms_service
+            cms_service,
+            login_throttle_service,
+            password_service,
+            cms_token_service,
+            personal_access_token_service,
+            oidc_service,
+            admin_user_invitation_service,
+            avatar_service,
+            image_service,
+            asset_upload_service,
+            asset_reference_service,
+            asset_archive_service
         })
     }
 }
@@ -1,6 +1,7 @@
 use std::num::ParseIntError;
+use std::time::SystemTimeError;
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
 };
 use axum::extract::multipart::MultipartError;
@@ -8,6 +9,7 @@ use handlebars::{RenderError, TemplateError};
 use lettre::address::AddressError;
 use rust_i18n::t;
 use serde::Serialize;
+use totp_rs::{SecretParseError, TotpUrlError};
 use tracing::log::error;
 use crate::models::validation_error::{ErrorMessage, ErrorResponse};
 
@@ -27,7 +29,13 @@ pub enum Error {
 
     NotFound(String),
 
-    Forbidden
+    Forbidden,
+
+    /// The admin user is deactivated and can not sign in.
+    AccountDeactivated,
+
+    /// Seconds until the client may retry, sent back as the Retry-After header.
+    TooManyRequests(i64)
 }
 
 impl core::fmt::Display for Error {
@@ -55,6 +63,16 @@ impl From<jsonwebtoken::errors::Error> for Error {
 
 impl From<MultipartError> for Error {
     fn from(val: MultipartError) -> Self {
+        if val.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            return Error::BadRequest(ErrorResponse {
+                status: false,
+                errors: vec![ErrorMessage {
+                    key: String::from("file"),
+                    message: t!("request_too_large").to_string()
+                }]
+            });
+        }
+
         error!("there is an issue with multipart error: {val:?}");
         Error::Generic("multipart can not find next field".to_string())
     }
@@ -87,6 +105,13 @@ impl From<argon2::password_hash::Error> for Error {
         Error::Generic("Password hasher error".to_string())
     }
 }
+impl From<argon2::Error> for Error {
+    fn from(val: argon2::Error) -> Self {
+        error!("there is an issue with the argon2 params: {val:?}");
+        Error::Generic("Password hasher params error".to_string())
+    }
+}
+
 impl From<RenderError> for Error {
     fn from(actual_error: RenderError) -> Self {
         error!("there is an issue while rendering the handlebar template: {actual_error:?}");
@@ -125,6 +150,48 @@ impl From<lettre::error::Error> for Error {
     }
 }
 
+impl From<TotpUrlError> for Error {
+    fn from(actual_error: TotpUrlError) -> Self {
+        error!("there is an issue while creating the totp instance: {actual_error:?}");
+        Error::Generic("totp error".to_string())
+    }
+}
+
+impl From<SecretParseError> for Error {
+    fn from(actual_error: SecretParseError) -> Self {
+        error!("there is an issue while parsing the two factor secret: {actual_error:?}");
+        Error::Generic("two factor secret parse error".to_string())
+    }
+}
+
+impl From<SystemTimeError> for Error {
+    fn from(actual_error: SystemTimeError) -> Self {
+        error!("there is an issue with the system time: {actual_error:?}");
+        Error::Generic("system time error".to_string())
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(actual_error: reqwest::Error) -> Self {
+        error!("there is an issue while calling the identity provider: {actual_error:?}");
+        Error::Generic("identity provider request error".to_string())
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(actual_error: url::ParseError) -> Self {
+        error!("there is an issue while parsing an url: {actual_error:?}");
+        Error::Generic("url parse error".to_string())
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(actual_error: image::ImageError) -> Self {
+        error!("there is an issue while processing an image: {actual_error:?}");
+        Error::Generic("image processing error".to_string())
+    }
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         
@@ -169,6 +236,38 @@ impl IntoResponse for Error {
                 };
                 (StatusCode::FORBIDDEN, error_response).into_response()
             },
+            Error::AccountDeactivated => {
+                let mut errors: Vec<ErrorMessage> = vec![];
+                let error_message = ErrorMessage {
+                    key: String::from("email"),
+                    message: String::from(t!("admin_user_deactivated"))
+                };
+
+                errors.push(error_message);
+                let error_response = ErrorResponse {
+                    status: false,
+                    errors
+                };
+                (StatusCode::FORBIDDEN, error_response).into_response()
+            },
+            Error::TooManyRequests(retry_after_seconds) => {
+                let mut errors: Vec<ErrorMessage> = vec![];
+                let error_message = ErrorMessage {
+                    key: String::from("email"),
+                    message: String::from(t!("too_many_attempts", seconds = retry_after_seconds))
+                };
+
+                errors.push(error_message);
+                let error_response = ErrorResponse {
+                    status: false,
+                    errors
+                };
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after_seconds.to_string())],
+                    error_response
+                ).into_response()
+            },
             Error::NotFound(msg) => {
                 (StatusCode::NOT_FOUND, msg).into_response()
             },
@@ -1,10 +1,8 @@
 extern crate core;
 use axum::Router;
-use std::{fs::File, path::Path, sync::Arc};
-use axum::extract::DefaultBodyLimit;
+use std::{fs::File, net::SocketAddr, path::Path, sync::Arc};
 use tokio::net::TcpListener;
-use tower_http::services::ServeDir;
-use tracing::info;
+use tracing::{error, info};
 use tracing_subscriber::{
     filter, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, Layer,
 };
@@ -32,12 +30,14 @@ rust_i18n::i18n!("resources/locales");
 async fn main() -> Result<()> {
     init_log();
     let state = Arc::new(AvoRedState::new().await?);
-    let static_routing_service = ServeDir::new("public");
-
+    state.role_service.validate_role_permissions(&state.db).await?;
+    state.admin_user_service.upgrade_legacy_profile_images(&state.db, &state.config.back_end_app_url).await?;
+    state.cms_token_service.migrate_legacy_setting_token(&state.db, &state.setting_service).await?;
+    if let Err(e) = state.asset_reference_service.rebuild(&state.db).await {
+        error!("there is an issue while indexing the asset references: {e:?}");
+    }
     let app = Router::new()
         .merge(rest_api_routes(state.clone()))
-        .nest_service("/public", static_routing_service)
-        .layer(DefaultBodyLimit::max(104857600))
     ;
 
     println!(r"     _             ____          _ ");
@@ -54,7 +54,7 @@ async fn main() -> Result<()> {
     // let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
     let listener = TcpListener::bind("0.0.0.0:8081").await.unwrap();
     info!("{:<12} - on {:?}\n", "LISTENING", listener.local_addr());
-    axum::serve(listener , app.into_make_service())
+    axum::serve(listener , app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
     // endregion: --- Start Server
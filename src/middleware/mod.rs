@@ -1,2 +1,5 @@
 pub mod require_jwt_authentication;
 pub mod validate_cms_authentication;
+pub mod require_permission;
+pub mod require_session_token;
+pub mod static_file_cache;
@@ -10,6 +10,7 @@ use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::Serialize;
 use crate::avored_state::AvoRedState;
 use crate::models::token_claim_model::{LoggedInUser, TokenClaims};
+use crate::services::personal_access_token_service::PERSONAL_ACCESS_TOKEN_PREFIX;
 
 #[derive(Debug, Serialize, Default)]
 pub struct ErrorResponse {
@@ -53,6 +54,45 @@ pub async fn require_jwt_authentication (
         (StatusCode::UNAUTHORIZED, Json(json_error))
     })?;
 
+    let file_exist = tokio::fs::try_exists("public/install_demo").await.unwrap_or(false);
+
+    if token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX) {
+        let invalid_token_error = || {
+            let json_error = ErrorResponse {
+                status: false,
+                message: "Invalid token".to_string(),
+            };
+            (StatusCode::UNAUTHORIZED, Json(json_error))
+        };
+
+        let personal_access_token_model = state
+            .personal_access_token_service
+            .authenticate(&state.db, &token)
+            .await
+            .map_err(|_| invalid_token_error())?;
+        let admin_user_model = state
+            .admin_user_service
+            .find_by_id(&state.db, personal_access_token_model.admin_user_id)
+            .await
+            .map_err(|_| invalid_token_error())?;
+        if !admin_user_model.is_active {
+            return Err(deactivated_error());
+        }
+
+        let logged_in_user = LoggedInUser {
+            id: admin_user_model.id.clone(),
+            name: admin_user_model.full_name.clone(),
+            email: admin_user_model.email.clone(),
+            demo_data_status: file_exist,
+            admin_user_model,
+            token_permissions: Some(personal_access_token_model.permissions)
+        };
+
+        req.extensions_mut().insert(logged_in_user);
+
+        return Ok(next.run(req).await);
+    }
+
     let secret = state.config.jwt_secret_key.clone();
 
     let claims = decode::<TokenClaims>(
@@ -68,17 +108,36 @@ pub async fn require_jwt_authentication (
             (StatusCode::UNAUTHORIZED, Json(json_error))
         })?
         .claims;
-    let file_exist = tokio::fs::try_exists("public/install_demo").await.unwrap_or(false);
+
+    // the token outlives a deactivation, so the current state is checked on every request
+    let is_active = state
+        .admin_user_service
+        .find_by_id(&state.db, claims.sub.clone())
+        .await
+        .map(|admin_user_model| admin_user_model.is_active)
+        .unwrap_or(false);
+    if !is_active {
+        return Err(deactivated_error());
+    }
 
     let logged_in_user = LoggedInUser {
         id: claims.sub,
         name: claims.name,
         email: claims.email,
         demo_data_status: file_exist,
-        admin_user_model: claims.admin_user_model
+        admin_user_model: claims.admin_user_model,
+        token_permissions: None
     };
 
     req.extensions_mut().insert(logged_in_user);
 
     Ok(next.run(req).await)
 }
+
+fn deactivated_error() -> (StatusCode, Json<ErrorResponse>) {
+    let json_error = ErrorResponse {
+        status: false,
+        message: "Your account has been deactivated".to_string(),
+    };
+    (StatusCode::UNAUTHORIZED, Json(json_error))
+}
@@ -0,0 +1,43 @@
+use axum::body::Body;
+use axum::http::Request;
+use axum::middleware::{self, Next};
+use axum::response::IntoResponse;
+use axum::routing::MethodRouter;
+use crate::error::{Error, Result};
+use crate::models::permission_model::Permission;
+use crate::models::token_claim_model::LoggedInUser;
+
+/// Lets a route declare the permission it needs, e.g.
+/// `get(page_table_api_handler).require_permission(Permission::PageTable)`.
+/// It has to sit inside the jwt authentication layer, as it reads the logged in user.
+pub trait RequirePermission {
+    fn require_permission(self, permission: Permission) -> Self;
+}
+
+impl<S> RequirePermission for MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static
+{
+    fn require_permission(self, permission: Permission) -> Self {
+        self.route_layer(middleware::from_fn(move |req: Request<Body>, next: Next| {
+            check_permission(permission, req, next)
+        }))
+    }
+}
+
+async fn check_permission(
+    permission: Permission,
+    req: Request<Body>,
+    next: Next,
+) -> Result<impl IntoResponse> {
+    let logged_in_user = req
+        .extensions()
+        .get::<LoggedInUser>()
+        .ok_or(Error::Authentication)?;
+
+    if !logged_in_user.has_permission(permission) {
+        return Err(Error::Forbidden);
+    }
+
+    Ok(next.run(req).await)
+}
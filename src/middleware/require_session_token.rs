@@ -0,0 +1,39 @@
+use axum::body::Body;
+use axum::http::Request;
+use axum::middleware::{self, Next};
+use axum::response::IntoResponse;
+use axum::routing::MethodRouter;
+use crate::error::{Error, Result};
+use crate::models::token_claim_model::LoggedInUser;
+
+/// Keeps personal access tokens away from routes which are not covered by a registry permission,
+/// e.g. `post(change_password_api_handler).require_session_token()`. Only the token handed out by a
+/// login gets through. It has to sit inside the jwt authentication layer, as it reads the logged in user.
+pub trait RequireSessionToken {
+    fn require_session_token(self) -> Self;
+}
+
+impl<S> RequireSessionToken for MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static
+{
+    fn require_session_token(self) -> Self {
+        self.route_layer(middleware::from_fn(check_session_token))
+    }
+}
+
+async fn check_session_token(
+    req: Request<Body>,
+    next: Next,
+) -> Result<impl IntoResponse> {
+    let logged_in_user = req
+        .extensions()
+        .get::<LoggedInUser>()
+        .ok_or(Error::Authentication)?;
+
+    if logged_in_user.token_permissions.is_some() {
+        return Err(Error::Forbidden);
+    }
+
+    Ok(next.run(req).await)
+}
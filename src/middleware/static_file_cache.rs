@@ -0,0 +1,158 @@
+use axum::body::Body;
+use axum::http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::DateTime;
+
+/// Versioned urls carry the checksum of the content, so they never change.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+/// Everything else may be stored but has to be revalidated with the `ETag`.
+const REVALIDATE_CACHE_CONTROL: &str = "public, no-cache";
+
+/// Adds `Cache-Control` and an `ETag` to served files and answers a matching `If-None-Match`
+/// with 304. Files without an `ETag` of their own get a weak one from their size and age.
+pub async fn static_file_cache(req: Request<Body>, next: Next) -> Response {
+    let is_versioned = req
+        .uri()
+        .query()
+        .is_some_and(|query| query.split('&').any(|parameter| parameter.starts_with("v=")));
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+
+    let mut response = next.run(req).await;
+    if !matches!(response.status(), StatusCode::OK | StatusCode::PARTIAL_CONTENT | StatusCode::NOT_MODIFIED) {
+        return response;
+    }
+
+    let cache_control = if is_versioned { IMMUTABLE_CACHE_CONTROL } else { REVALIDATE_CACHE_CONTROL };
+    response.headers_mut().insert(header::CACHE_CONTROL, HeaderValue::from_static(cache_control));
+
+    let Some(etag) = response.headers().get(header::ETAG).cloned().or_else(|| file_etag(response.headers())) else {
+        return response;
+    };
+    if if_none_match.is_some_and(|if_none_match| etag_matches(&if_none_match, &etag)) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        for name in [header::CACHE_CONTROL, header::LAST_MODIFIED] {
+            if let Some(value) = response.headers().get(&name) {
+                not_modified.headers_mut().insert(name, value.clone());
+            }
+        }
+        not_modified.headers_mut().insert(header::ETAG, etag);
+
+        return not_modified;
+    }
+
+    response.headers_mut().insert(header::ETAG, etag);
+
+    response
+}
+
+fn file_etag(headers: &HeaderMap) -> Option<HeaderValue> {
+    let last_modified = headers.get(header::LAST_MODIFIED)?.to_str().ok()?;
+    let modified_at = DateTime::parse_from_rfc2822(last_modified).ok()?.timestamp();
+    // a range response carries the size of the whole file after the slash
+    let size = match headers.get(header::CONTENT_RANGE) {
+        Some(content_range) => content_range.to_str().ok()?.rsplit_once('/')?.1,
+        None => headers.get(header::CONTENT_LENGTH)?.to_str().ok()?,
+    };
+    let size = size.parse::<u64>().ok()?;
+
+    HeaderValue::from_str(&format!("W/\"{size:x}-{modified_at:x}\"")).ok()
+}
+
+/// Weak comparison, as `If-None-Match` asks for.
+fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(if_none_match), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+    let etag = etag.trim_start_matches("W/");
+
+    if_none_match
+        .split(',')
+        .map(|candidate| candidate.trim())
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, get_login_response, send_asset_upload_request, setup_avored_db};
+    use crate::error::Result;
+    use super::{etag_matches, file_etag};
+
+    fn send_public_request(uri: &str, headers: &[(header::HeaderName, &str)]) -> Request<Body> {
+        let mut request = Request::builder().uri(uri).method("GET");
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_file_etag() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::LAST_MODIFIED, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("1234"));
+        let etag = file_etag(&headers).unwrap();
+        assert_eq!(etag, "W/\"4d2-56273e80\"");
+
+        // a part of the file has the etag of the whole file
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("100"));
+        headers.insert(header::CONTENT_RANGE, HeaderValue::from_static("bytes 0-99/1234"));
+        assert_eq!(file_etag(&headers).unwrap(), etag);
+
+        assert!(etag_matches(&HeaderValue::from_static("\"other\", \"4d2-56273e80\""), &etag));
+        assert!(etag_matches(&HeaderValue::from_static("*"), &etag));
+        assert!(!etag_matches(&HeaderValue::from_static("W/\"4d2-0\""), &etag));
+    }
+
+    #[tokio::test]
+    async fn test_static_file_cache() -> Result<()>
+    {
+        let (app, _state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+        let token = get_login_response(app.clone()).await?.data;
+
+        let response = app.clone()
+            .oneshot(send_asset_upload_request("manual.txt", "text/plain", b"0123456789".to_vec(), token))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).expect("Failed to parse JSON");
+        let url = body["asset_model"]["url"].as_str().unwrap();
+        let versioned_uri = &url[url.find("/public/upload/").unwrap()..];
+        let (uri, _) = versioned_uri.split_once('?').unwrap();
+
+        let response = app.clone().oneshot(send_public_request(versioned_uri, &[])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=31536000, immutable");
+        let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let response = app.clone()
+            .oneshot(send_public_request(uri, &[(header::IF_NONE_MATCH, &etag)]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, no-cache");
+
+        let response = app.clone()
+            .oneshot(send_public_request(uri, &[(header::RANGE, "bytes=2-5")]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"2345");
+
+        let response = app
+            .oneshot(send_public_request("/public/log/avored.log", &[]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
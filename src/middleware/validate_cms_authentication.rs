@@ -7,13 +7,15 @@ use axum::middleware::Next;
 use axum::response::IntoResponse;
 use axum_extra::extract::CookieJar;
 use crate::avored_state::AvoRedState;
+use crate::error::Error;
 use crate::middleware::require_jwt_authentication::ErrorResponse;
-use crate::models::setting_model::SettingModel;
+use crate::models::cms_token_model::CmsTokenScope;
+use crate::models::token_claim_model::LoggedInUser;
 
 pub async fn validate_cms_authentication (
     state: State<Arc<AvoRedState>>,
     cookie_jar: CookieJar,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
 
@@ -44,20 +46,68 @@ pub async fn validate_cms_authentication (
         };
         (StatusCode::UNAUTHORIZED, Json(json_error))
     })?;
-    let cms_token_setting_model = state
-        .setting_service
-        .find_by_identifier(&state.db, String::from("auth_cms_token"))
-        .await.unwrap_or_else(|_err|  {
-            SettingModel::default()
-        });
 
-    if cms_token_setting_model.value.is_empty() | cms_token_setting_model.value.ne(&token) {
-        let json_error = ErrorResponse {
-            status: false,
-            message: "please provide valid token".to_string(),
-        };
-        return Err((StatusCode::UNAUTHORIZED, Json(json_error)))
+    let origin = req
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|origin| origin.to_str().ok());
+
+    let cms_token_model = state
+        .cms_token_service
+        .authenticate(&state.db, &token, origin, required_scope(req.uri().path()))
+        .await
+        .map_err(|e| {
+            let (status_code, message) = match e {
+                Error::Forbidden => (StatusCode::FORBIDDEN, "token is not allowed to perform this request"),
+                _ => (StatusCode::UNAUTHORIZED, "please provide valid token")
+            };
+            let json_error = ErrorResponse {
+                status: false,
+                message: message.to_string(),
+            };
+            (status_code, Json(json_error))
+        })?;
+
+    // a token never reaches pages and assets the admin user who issued it can not see
+    let admin_user_model = state
+        .admin_user_service
+        .find_by_email(&state.db, cms_token_model.created_by.clone())
+        .await
+        .ok()
+        .filter(|admin_user_model| admin_user_model.is_active)
+        .ok_or_else(|| {
+            let json_error = ErrorResponse {
+                status: false,
+                message: "token is not allowed to perform this request".to_string(),
+            };
+            (StatusCode::FORBIDDEN, Json(json_error))
+        })?;
+    let issuer = LoggedInUser {
+        id: admin_user_model.id.clone(),
+        name: admin_user_model.full_name.clone(),
+        email: admin_user_model.email.clone(),
+        demo_data_status: false,
+        admin_user_model,
+        // the token carries none of the admin permissions, only the record rules apply
+        token_permissions: Some(vec![])
     };
 
+    req.extensions_mut().insert(cms_token_model);
+    req.extensions_mut().insert(issuer);
+
     Ok(next.run(req).await)
-}
\ No newline at end of file
+}
+
+fn required_scope(path: &str) -> CmsTokenScope {
+    if path.starts_with("/cms/sent-contact-us-email") {
+        return CmsTokenScope::SubmitForms;
+    }
+    if path.starts_with("/cms/asset") {
+        return CmsTokenScope::ReadAssets;
+    }
+    if path.starts_with("/cms/model") {
+        return CmsTokenScope::ReadModels;
+    }
+
+    CmsTokenScope::ReadPages
+}
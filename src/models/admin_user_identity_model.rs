@@ -0,0 +1,43 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object};
+use crate::models::BaseModel;
+
+/// Links an admin user to the subject of an external identity provider.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct AdminUserIdentityModel {
+    pub id: String,
+    pub admin_user_id: String,
+    pub issuer: String,
+    pub subject: String,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+impl TryFrom<Object> for AdminUserIdentityModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<AdminUserIdentityModel> {
+        let id = val.get("id").get_id()?;
+        let admin_user_id = val.get("admin_user_id").get_string()?;
+        let issuer = val.get("issuer").get_string()?;
+        let subject = val.get("subject").get_string()?;
+        let created_at = val.get("created_at").get_datetime()?;
+        let updated_at = val.get("updated_at").get_datetime()?;
+
+        Ok(AdminUserIdentityModel {
+            id,
+            admin_user_id,
+            issuer,
+            subject,
+            created_at,
+            updated_at
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableAdminUserIdentityModel {
+    pub admin_user_id: String,
+    pub issuer: String,
+    pub subject: String,
+}
@@ -0,0 +1,72 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object};
+use crate::models::BaseModel;
+
+/// A pending invitation, it is removed once the invitee has set a password or it is revoked.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct AdminUserInvitationModel {
+    pub id: String,
+    pub email: String,
+    pub role_ids: Vec<String>,
+    pub expires_at: Datetime,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+    pub created_by: String,
+    pub updated_by: String,
+}
+
+impl AdminUserInvitationModel {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.0 <= chrono::Utc::now()
+    }
+}
+
+impl TryFrom<Object> for AdminUserInvitationModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<AdminUserInvitationModel> {
+        let id = val.get("id").get_id()?;
+        let email = val.get("email").get_string()?;
+        let role_ids = val.get("role_ids").get_string_array()?;
+        let expires_at = val.get("expires_at").get_datetime()?;
+        let created_at = val.get("created_at").get_datetime()?;
+        let updated_at = val.get("updated_at").get_datetime()?;
+        let created_by = val.get("created_by").get_string()?;
+        let updated_by = val.get("updated_by").get_string()?;
+
+        Ok(AdminUserInvitationModel {
+            id,
+            email,
+            role_ids,
+            expires_at,
+            created_at,
+            updated_at,
+            created_by,
+            updated_by,
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableAdminUserInvitationModel {
+    pub email: String,
+    pub role_ids: Vec<String>,
+    pub logged_in_username: String,
+}
+
+/// Claims of the link sent to the invitee, `jti` is what the stored hash is made of so a
+/// resent invitation invalidates the previous link.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminUserInvitationClaims {
+    pub sub: String,
+    pub jti: String,
+    pub purpose: String,
+    pub iat: usize,
+    pub exp: usize,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct IssuedAdminUserInvitationModel {
+    pub token: String,
+    pub invitation: AdminUserInvitationModel,
+}
@@ -0,0 +1,39 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object};
+use crate::models::BaseModel;
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct AdminUserLockoutModel {
+    pub id: String,
+    pub email: String,
+    pub token: String,
+    pub locked_until: Datetime,
+    pub created_at: Datetime,
+}
+
+impl TryFrom<Object> for AdminUserLockoutModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<AdminUserLockoutModel> {
+        let id = val.get("id").get_id()?;
+        let email = val.get("email").get_string()?;
+        let token = val.get("token").get_string()?;
+        let locked_until = val.get("locked_until").get_datetime()?;
+        let created_at = val.get("created_at").get_datetime()?;
+
+        Ok(AdminUserLockoutModel {
+            id,
+            email,
+            token,
+            locked_until,
+            created_at
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableAdminUserLockoutModel {
+    pub email: String,
+    pub token: String,
+    pub lockout_seconds: i64,
+}
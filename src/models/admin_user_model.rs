@@ -1,3 +1,5 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::{Datetime, Object, Value};
@@ -14,6 +16,8 @@ pub struct AdminUserModel {
     pub password: String,
     pub profile_image: String,
     pub is_super_admin: bool,
+    pub is_two_factor_enabled: bool,
+    pub is_active: bool,
     #[schema(value_type=String)]
     pub created_at: Datetime,
     #[schema(value_type=String)]
@@ -32,13 +36,12 @@ impl TryFrom<Object> for AdminUserModel {
         let password = val.get("password").get_string()?;
         let mut profile_image = val.get("profile_image").get_string()?;
         if profile_image.is_empty() {
-            profile_image = String::from("https://place-hold.it/250x250");
-        } else {
-            //@todo fix this
-            profile_image = format!("http://localhost:8080/public/{}", profile_image);
+            profile_image = initials_avatar(&full_name);
         }
 
         let is_super_admin = val.get("is_super_admin").get_bool()?;
+        let is_two_factor_enabled = val.get("is_two_factor_enabled").get_bool()?;
+        let is_active = val.get("is_active").get_bool()?;
 
         let roles = match val.get("roles") {
             Some(val) => {
@@ -78,6 +81,8 @@ impl TryFrom<Object> for AdminUserModel {
             password,
             profile_image,
             is_super_admin,
+            is_two_factor_enabled,
+            is_active,
             created_at,
             updated_at,
             created_by,
@@ -87,6 +92,24 @@ impl TryFrom<Object> for AdminUserModel {
     }
 }
 
+/// A svg with the initials of the admin user, used until an avatar is uploaded.
+fn initials_avatar(full_name: &str) -> String {
+    let initials: String = full_name
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+    let colors = ["#0f766e", "#1d4ed8", "#7c3aed", "#be123c", "#b45309", "#15803d"];
+    let color = colors[full_name.bytes().map(usize::from).sum::<usize>() % colors.len()];
+
+    let svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="256" height="256" viewBox="0 0 256 256"><rect width="256" height="256" fill="{color}"/><text x="50%" y="50%" dy=".35em" text-anchor="middle" font-family="sans-serif" font-size="112" fill="#ffffff">{initials}</text></svg>"##
+    );
+
+    format!("data:image/svg+xml;base64,{}", STANDARD.encode(svg))
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone)]
 pub struct CreatableAdminUserModel {
     pub full_name: String,
@@ -102,7 +125,6 @@ pub struct CreatableAdminUserModel {
 pub struct UpdatableAdminUserModel {
     pub id: String,
     pub full_name: String,
-    pub profile_image: String,
     pub is_super_admin: bool,
     pub logged_in_username: String,
     pub role_ids: Vec<String>
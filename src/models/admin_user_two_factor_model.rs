@@ -0,0 +1,80 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use surrealdb::sql::{Datetime, Object, Value};
+use crate::models::BaseModel;
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct AdminUserTwoFactorModel {
+    pub id: String,
+    pub admin_user_id: String,
+    pub secret: String,
+    pub recovery_codes: Vec<String>,
+    pub status: TwoFactorStatus,
+    pub last_used_step: i64,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Default)]
+pub enum TwoFactorStatus {
+    #[default]
+    Pending,
+    Enabled
+}
+
+impl TryFrom<Object> for AdminUserTwoFactorModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<AdminUserTwoFactorModel> {
+        let id = val.get("id").get_id()?;
+        let admin_user_id = val.get("admin_user_id").get_string()?;
+        let secret = val.get("secret").get_string()?;
+        let status = match val.get("status").get_string()?.as_str() {
+            "Enabled" => TwoFactorStatus::Enabled,
+            _ => TwoFactorStatus::Pending
+        };
+        let recovery_codes = match val.get("recovery_codes") {
+            Some(val) => {
+                match val.clone() {
+                    Value::Array(v) => {
+                        let mut arr = Vec::new();
+
+                        for array in v.into_iter() {
+                            arr.push(array.as_string())
+                        }
+                        arr
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            None => Vec::new(),
+        };
+        // records created before codes were bound to a time step have no value yet
+        let last_used_step = val.get("last_used_step").get_int().unwrap_or_default();
+        let created_at = val.get("created_at").get_datetime()?;
+        let updated_at = val.get("updated_at").get_datetime()?;
+
+        Ok(AdminUserTwoFactorModel {
+            id,
+            admin_user_id,
+            secret,
+            recovery_codes,
+            status,
+            last_used_step,
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableAdminUserTwoFactorModel {
+    pub admin_user_id: String,
+    pub secret: String,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default, ToSchema)]
+pub struct TwoFactorEnrolmentModel {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
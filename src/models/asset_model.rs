@@ -1,7 +1,10 @@
 use crate::error::{Error, Result};
+use rust_i18n::t;
+use tracing::log::error;
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::{Datetime, Object, Value};
 use super::{BaseModel, Pagination};
+use super::image_rendition_model::{FocalPointModel, ImageCropModel};
 
 #[derive(Serialize, Debug, Deserialize, Clone, Default)]
 pub struct NewAssetModel {
@@ -9,8 +12,24 @@ pub struct NewAssetModel {
     pub parent_id: String,
     pub name: String,
     pub path: String,
+    /// Set by the asset service from the storage, it is not stored.
+    pub url: String,
     pub asset_type: String,
     pub metadata: MetaDataType,
+    pub original_name: String,
+    pub file_size: i64,
+    pub width: i64,
+    pub height: i64,
+    pub checksum: String,
+    pub alt_text: String,
+    pub caption: String,
+    pub tags: Vec<String>,
+    /// The file is kept out of the public storage, only admin users and signed urls get it.
+    pub is_private: bool,
+    /// Images only, renditions crop around it. The center when not set.
+    pub focal_point: Option<FocalPointModel>,
+    /// Images only, art directed parts of the image which renditions can be cut to.
+    pub crops: Vec<ImageCropModel>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
     pub created_by: String,
@@ -80,12 +99,37 @@ impl TryFrom<Object> for NewAssetModel {
         let id = val.get("id").get_id()?;
         let parent_id = val.get("parent_id").get_string()?;
         let name = val.get("name").get_string()?;
-        let path = val.get("path").get_string()?;
+        let path = storage_key(&val.get("path").get_string()?);
         let asset_type = val.get("asset_type").get_string()?;
         let created_at = val.get("created_at").get_datetime()?;
         let created_by = val.get("created_by").get_string()?;
         let updated_at = val.get("updated_at").get_datetime()?;
         let updated_by = val.get("updated_by").get_string()?;
+        let original_name = val.get("original_name").get_string()?;
+        let file_size = val.get("file_size").get_int()?;
+        let width = val.get("width").get_int()?;
+        let height = val.get("height").get_int()?;
+        let checksum = val.get("checksum").get_string()?;
+        let alt_text = val.get("alt_text").get_string()?;
+        let caption = val.get("caption").get_string()?;
+        let tags = val.get("tags").get_string_array()?;
+        let is_private = val.get("is_private").get_bool()?;
+        let focal_point = match val.get("focal_point") {
+            Some(Value::Object(object)) => Some(object.clone().try_into()?),
+            _ => None,
+        };
+        let crops = match val.get("crops") {
+            Some(Value::Array(array)) => {
+                let mut crops: Vec<ImageCropModel> = Vec::new();
+                for value in array.iter() {
+                    if let Value::Object(object) = value {
+                        crops.push(object.clone().try_into()?);
+                    }
+                }
+                crops
+            },
+            _ => Vec::new(),
+        };
 
         let metadata = match asset_type.as_str() {
             "FILE" => {
@@ -124,8 +168,20 @@ impl TryFrom<Object> for NewAssetModel {
             parent_id,
             name,
             path,
+            url: String::new(),
             asset_type,
             metadata,
+            original_name,
+            file_size,
+            width,
+            height,
+            checksum,
+            alt_text,
+            caption,
+            tags,
+            is_private,
+            focal_point,
+            crops,
             created_at,
             updated_at,
             created_by,
@@ -134,6 +190,13 @@ impl TryFrom<Object> for NewAssetModel {
     }
 }
 
+/// Paths used to include the local upload folder, the storage key is relative to it.
+fn storage_key(path: &str) -> String {
+    let path = path.trim_start_matches('/');
+
+    path.strip_prefix("public/upload/").unwrap_or(path).to_string()
+}
+
 impl TryFrom<Object> for  FileTypeMetaDataStruct {
     type Error = Error;
     fn try_from(val: Object) -> Result<FileTypeMetaDataStruct> {
@@ -162,6 +225,33 @@ pub struct AssetPagination {
 }
 
 
+/// Filters of the asset search, filters which are not set match every asset.
+#[derive(Debug, Clone, Default)]
+pub struct AssetSearchModel {
+    /// Lowercase text found in the name, original name, alt text or caption.
+    pub query: Option<String>,
+    pub tag: Option<String>,
+    /// A full MIME type or a group like `image/*`.
+    pub file_type: Option<String>,
+    pub created_from: Option<Datetime>,
+    pub created_to: Option<Datetime>,
+    pub created_by: Option<String>,
+    pub min_width: Option<i64>,
+    pub max_width: Option<i64>,
+    pub min_height: Option<i64>,
+    pub max_height: Option<i64>,
+    pub order_column: String,
+    pub order_type: String,
+}
+
+/// A url which hands out the file of an asset until it expires.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct SignedAssetUrlModel {
+    pub url: String,
+    /// Unix timestamp in seconds.
+    pub expires_at: i64,
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone, Default)]
 pub struct CreatableAssetModelNew {
     pub logged_in_username: String,
@@ -170,5 +260,125 @@ pub struct CreatableAssetModelNew {
     pub path: String,
     pub asset_type: String,
     pub metadata: MetaDataType,
+    pub original_name: String,
+    pub file_size: i64,
+    pub width: i64,
+    pub height: i64,
+    pub checksum: String,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct UpdatableAssetModel {
+    pub id: String,
+    pub alt_text: String,
+    pub caption: String,
+    pub tags: Vec<String>,
+    pub focal_point: Option<FocalPointModel>,
+    pub crops: Vec<ImageCropModel>,
+    pub logged_in_username: String,
+}
+
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct AllowedAssetTypeModel {
+    pub mime_type: String,
+    pub max_file_size_kb: usize,
+}
+
+impl AllowedAssetTypeModel {
+    /// Parses `mime/type:MAX_KB`, e.g. `image/png:10240`.
+    pub fn from_definition(definition: &str) -> Option<AllowedAssetTypeModel> {
+        let (mime_type, max_file_size_kb) = definition.trim().rsplit_once(':')?;
+        if !mime_type.contains('/') {
+            return None;
+        }
+
+        Some(AllowedAssetTypeModel {
+            mime_type: mime_type.trim().to_string(),
+            max_file_size_kb: max_file_size_kb.trim().parse().ok()?,
+        })
+    }
 }
 
+/// Files with the same content, the oldest comes first.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct DuplicateAssetGroupModel {
+    pub checksum: String,
+    pub file_size: i64,
+    /// Storage which is freed by keeping a single copy.
+    pub wasted_size: i64,
+    pub assets: Vec<NewAssetModel>,
+}
+
+/// Outcome of one asset in a bulk operation.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct AssetOperationResultModel {
+    pub asset_id: String,
+    pub success: bool,
+    pub message: String,
+}
+
+impl AssetOperationResultModel {
+    pub fn succeeded(asset_id: &str) -> Self {
+        AssetOperationResultModel {
+            asset_id: asset_id.to_string(),
+            success: true,
+            message: String::new(),
+        }
+    }
+
+    pub fn failed(asset_id: &str, e: &Error) -> Self {
+        AssetOperationResultModel {
+            asset_id: asset_id.to_string(),
+            success: false,
+            message: operation_error_message(asset_id, e),
+        }
+    }
+}
+
+/// Outcome of one entry of an uploaded archive.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct ArchiveEntryResultModel {
+    pub path: String,
+    pub success: bool,
+    pub message: String,
+    /// The created file, entries which failed have none.
+    pub asset_model: Option<NewAssetModel>,
+}
+
+impl ArchiveEntryResultModel {
+    pub fn succeeded(path: &str, asset_model: NewAssetModel) -> Self {
+        ArchiveEntryResultModel {
+            path: path.to_string(),
+            success: true,
+            message: String::new(),
+            asset_model: Some(asset_model),
+        }
+    }
+
+    pub fn failed(path: &str, e: &Error) -> Self {
+        ArchiveEntryResultModel {
+            path: path.to_string(),
+            success: false,
+            message: operation_error_message(path, e),
+            asset_model: None,
+        }
+    }
+}
+
+/// Validation messages are passed on, other errors get a general message.
+fn operation_error_message(subject: &str, e: &Error) -> String {
+    match e {
+        Error::BadRequest(error_response) => error_response.errors
+            .iter()
+            .map(|error_message| error_message.message.clone())
+            .collect::<Vec<String>>()
+            .join(" "),
+        Error::NotFound(_) => t!("asset_not_found").to_string(),
+        Error::Forbidden => t!("admin_user_forbidden").to_string(),
+        _ => {
+            error!("there is an issue with {subject} in a bulk operation: {e:?}");
+            t!("asset_operation_failed").to_string()
+        },
+    }
+}
@@ -0,0 +1,65 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object};
+use crate::models::BaseModel;
+
+/// The kind of record an asset is used in.
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AssetReferenceSource {
+    Page,
+    Component,
+    Model,
+    Setting,
+}
+
+impl AssetReferenceSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssetReferenceSource::Page => "page",
+            AssetReferenceSource::Component => "component",
+            AssetReferenceSource::Model => "model",
+            AssetReferenceSource::Setting => "setting",
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct AssetReferenceModel {
+    pub id: String,
+    pub asset_id: String,
+    pub source_type: String,
+    pub source_id: String,
+    pub source_name: String,
+    /// Identifier of the field of the source the asset is used in.
+    pub field: String,
+    pub created_at: Datetime,
+}
+
+impl TryFrom<Object> for AssetReferenceModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<AssetReferenceModel> {
+        let id = val.get("id").get_id()?;
+        let asset_id = val.get("asset_id").get_string()?;
+        let source_type = val.get("source_type").get_string()?;
+        let source_id = val.get("source_id").get_string()?;
+        let source_name = val.get("source_name").get_string()?;
+        let field = val.get("field").get_string()?;
+        let created_at = val.get("created_at").get_datetime()?;
+
+        Ok(AssetReferenceModel {
+            id,
+            asset_id,
+            source_type,
+            source_id,
+            source_name,
+            field,
+            created_at,
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableAssetReferenceModel {
+    pub asset_id: String,
+    pub field: String,
+}
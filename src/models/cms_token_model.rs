@@ -0,0 +1,132 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object, Value};
+use crate::models::BaseModel;
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CmsTokenModel {
+    pub id: String,
+    pub name: String,
+    /// First characters of the plain token, so admins can tell tokens apart.
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub allowed_origins: Vec<String>,
+    pub expires_at: Option<Datetime>,
+    pub last_used_at: Option<Datetime>,
+    pub created_by: String,
+    pub updated_by: String,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum CmsTokenScope {
+    ReadPages,
+    ReadModels,
+    ReadAssets,
+    SubmitForms
+}
+
+impl CmsTokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CmsTokenScope::ReadPages => "read_pages",
+            CmsTokenScope::ReadModels => "read_models",
+            CmsTokenScope::ReadAssets => "read_assets",
+            CmsTokenScope::SubmitForms => "submit_forms",
+        }
+    }
+
+    pub fn all() -> Vec<CmsTokenScope> {
+        vec![CmsTokenScope::ReadPages, CmsTokenScope::ReadModels, CmsTokenScope::ReadAssets, CmsTokenScope::SubmitForms]
+    }
+}
+
+impl CmsTokenModel {
+    pub fn has_scope(&self, scope: CmsTokenScope) -> bool {
+        self.scopes.iter().any(|s| s == scope.as_str())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        match &self.expires_at {
+            Some(expires_at) => expires_at.0 <= chrono::Utc::now(),
+            None => false
+        }
+    }
+
+    /// A token without allowed origins can be used from anywhere.
+    pub fn is_origin_allowed(&self, origin: Option<&str>) -> bool {
+        if self.allowed_origins.is_empty() {
+            return true;
+        }
+
+        match origin {
+            Some(origin) => self.allowed_origins
+                .iter()
+                .any(|allowed| allowed.trim_end_matches('/') == origin.trim_end_matches('/')),
+            None => false
+        }
+    }
+}
+
+impl TryFrom<Object> for CmsTokenModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<CmsTokenModel> {
+        let id = val.get("id").get_id()?;
+        let name = val.get("name").get_string()?;
+        let token_prefix = val.get("token_prefix").get_string()?;
+        let scopes = get_string_array(val.get("scopes"));
+        let allowed_origins = get_string_array(val.get("allowed_origins"));
+        let expires_at = match val.get("expires_at") {
+            Some(Value::Datetime(v)) => Some(v.clone()),
+            _ => None
+        };
+        let last_used_at = match val.get("last_used_at") {
+            Some(Value::Datetime(v)) => Some(v.clone()),
+            _ => None
+        };
+        let created_by = val.get("created_by").get_string()?;
+        let updated_by = val.get("updated_by").get_string()?;
+        let created_at = val.get("created_at").get_datetime()?;
+        let updated_at = val.get("updated_at").get_datetime()?;
+
+        Ok(CmsTokenModel {
+            id,
+            name,
+            token_prefix,
+            scopes,
+            allowed_origins,
+            expires_at,
+            last_used_at,
+            created_by,
+            updated_by,
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+fn get_string_array(val: Option<&Value>) -> Vec<String> {
+    match val {
+        Some(Value::Array(v)) => v.iter().map(|value| value.clone().as_string()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableCmsTokenModel {
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub allowed_origins: Vec<String>,
+    pub expires_at: Option<Datetime>,
+    pub logged_in_username: String,
+}
+
+/// The plain token is only ever returned once, right after it was created or rotated.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct IssuedCmsTokenModel {
+    pub token: String,
+    pub cms_token: CmsTokenModel,
+}
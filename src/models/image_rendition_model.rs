@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use image::ImageFormat;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Object, Value};
+use crate::error::{Error, Result};
+use super::BaseModel;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFit {
+    /// Scales the image down to fit inside the box, keeping the aspect ratio.
+    #[default]
+    Contain,
+    /// Fills the box and crops what is left over, keeping the aspect ratio.
+    Cover,
+    /// Stretches the image to the exact box.
+    Fill,
+}
+
+impl ImageFit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageFit::Contain => "contain",
+            ImageFit::Cover => "cover",
+            ImageFit::Fill => "fill",
+        }
+    }
+
+    pub fn from_identifier(identifier: &str) -> Option<ImageFit> {
+        [ImageFit::Contain, ImageFit::Cover, ImageFit::Fill]
+            .into_iter()
+            .find(|fit| fit.as_str() == identifier)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageOutputFormat {
+    Webp,
+    Png,
+    Jpeg,
+}
+
+impl ImageOutputFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageOutputFormat::Webp => "webp",
+            ImageOutputFormat::Png => "png",
+            ImageOutputFormat::Jpeg => "jpeg",
+        }
+    }
+
+    pub fn from_identifier(identifier: &str) -> Option<ImageOutputFormat> {
+        match identifier {
+            "jpg" => Some(ImageOutputFormat::Jpeg),
+            _ => [ImageOutputFormat::Webp, ImageOutputFormat::Png, ImageOutputFormat::Jpeg]
+                .into_iter()
+                .find(|format| format.as_str() == identifier)
+        }
+    }
+
+    /// Formats without an encoder here fall back to png.
+    pub fn from_image_format(format: ImageFormat) -> ImageOutputFormat {
+        match format {
+            ImageFormat::Jpeg => ImageOutputFormat::Jpeg,
+            ImageFormat::WebP => ImageOutputFormat::Webp,
+            _ => ImageOutputFormat::Png,
+        }
+    }
+
+    pub fn image_format(&self) -> ImageFormat {
+        match self {
+            ImageOutputFormat::Webp => ImageFormat::WebP,
+            ImageOutputFormat::Png => ImageFormat::Png,
+            ImageOutputFormat::Jpeg => ImageFormat::Jpeg,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        self.image_format().to_mime_type()
+    }
+}
+
+/// A derived version of an image asset. A missing width or height follows the aspect ratio
+/// of the original, a missing format keeps the one of the original.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ImageRenditionModel {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fit: ImageFit,
+    pub format: Option<ImageOutputFormat>,
+    /// Name of a crop of the asset, the image is cut to it before resizing. Unknown names
+    /// leave the image as it is.
+    pub crop: Option<String>,
+}
+
+impl ImageRenditionModel {
+    /// Name of the cached file, without the extension.
+    pub fn cache_key(&self) -> String {
+        let size = |value: Option<u32>| value.map(|value| value.to_string()).unwrap_or_else(|| String::from("auto"));
+
+        format!("{}x{}-{}", size(self.width), size(self.height), self.fit.as_str())
+    }
+}
+
+/// The part of an image which stays in view when it is cropped, relative to its size, so
+/// `0.5, 0.5` is the center.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FocalPointModel {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Default for FocalPointModel {
+    fn default() -> FocalPointModel {
+        FocalPointModel { x: 0.5, y: 0.5 }
+    }
+}
+
+impl TryFrom<Object> for FocalPointModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<FocalPointModel> {
+        let x = val.get("x").get_float()?;
+        let y = val.get("y").get_float()?;
+
+        Ok(FocalPointModel { x, y })
+    }
+}
+
+impl From<FocalPointModel> for Value {
+    fn from(focal_point: FocalPointModel) -> Value {
+        let object: BTreeMap<String, Value> = [
+            ("x".into(), focal_point.x.into()),
+            ("y".into(), focal_point.y.into()),
+        ].into();
+
+        object.into()
+    }
+}
+
+/// A named rectangle of an image in pixels, picked by editors for a layout like a wide hero.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ImageCropModel {
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageCropModel {
+    pub fn cache_key(&self) -> String {
+        format!("{}-{}-{}-{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+impl TryFrom<Object> for ImageCropModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<ImageCropModel> {
+        let name = val.get("name").get_string()?;
+        let size = |key: &str| u32::try_from(val.get(key).get_int().unwrap_or_default()).unwrap_or_default();
+
+        Ok(ImageCropModel {
+            name,
+            x: size("x"),
+            y: size("y"),
+            width: size("width"),
+            height: size("height"),
+        })
+    }
+}
+
+impl From<ImageCropModel> for Value {
+    fn from(crop: ImageCropModel) -> Value {
+        let object: BTreeMap<String, Value> = [
+            ("name".into(), crop.name.into()),
+            ("x".into(), i64::from(crop.x).into()),
+            ("y".into(), i64::from(crop.y).into()),
+            ("width".into(), i64::from(crop.width).into()),
+            ("height".into(), i64::from(crop.height).into()),
+        ].into();
+
+        object.into()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ImagePresetModel {
+    pub name: String,
+    pub rendition: ImageRenditionModel,
+}
+
+impl ImagePresetModel {
+    /// Parses `name:WIDTHxHEIGHT:fit`, the fit is optional.
+    pub fn from_definition(definition: &str) -> Option<ImagePresetModel> {
+        let mut parts = definition.trim().split(':');
+        let name = parts.next().filter(|name| !name.is_empty())?.to_string();
+        let (width, height) = parts.next()?.split_once('x')?;
+        let fit = match parts.next() {
+            Some(fit) => ImageFit::from_identifier(fit)?,
+            None => ImageFit::default(),
+        };
+
+        Some(ImagePresetModel {
+            name,
+            rendition: ImageRenditionModel {
+                width: Some(width.parse().ok()?),
+                height: Some(height.parse().ok()?),
+                fit,
+                format: None,
+                crop: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageFit, ImagePresetModel};
+
+    #[test]
+    fn test_image_preset_from_definition() {
+        let preset = ImagePresetModel::from_definition("thumbnail:150x100:cover").unwrap();
+        assert_eq!(preset.name, "thumbnail");
+        assert_eq!(preset.rendition.width, Some(150));
+        assert_eq!(preset.rendition.height, Some(100));
+        assert_eq!(preset.rendition.fit, ImageFit::Cover);
+        assert_eq!(preset.rendition.cache_key(), "150x100-cover");
+
+        assert_eq!(ImagePresetModel::from_definition("small:480x480").unwrap().rendition.fit, ImageFit::Contain);
+        assert!(ImagePresetModel::from_definition("broken:480").is_none());
+        assert!(ImagePresetModel::from_definition("broken:480x480:stretch").is_none());
+    }
+}
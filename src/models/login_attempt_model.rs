@@ -0,0 +1,46 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object};
+use crate::models::BaseModel;
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct LoginAttemptModel {
+    pub id: String,
+    pub action: String,
+    pub key: String,
+    pub created_at: Datetime,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum LoginAttemptAction {
+    Login,
+    ForgotPassword,
+    TwoFactor
+}
+
+impl LoginAttemptAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginAttemptAction::Login => "login",
+            LoginAttemptAction::ForgotPassword => "forgot_password",
+            LoginAttemptAction::TwoFactor => "two_factor",
+        }
+    }
+}
+
+impl TryFrom<Object> for LoginAttemptModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<LoginAttemptModel> {
+        let id = val.get("id").get_id()?;
+        let action = val.get("action").get_string()?;
+        let key = val.get("key").get_string()?;
+        let created_at = val.get("created_at").get_datetime()?;
+
+        Ok(LoginAttemptModel {
+            id,
+            action,
+            key,
+            created_at
+        })
+    }
+}
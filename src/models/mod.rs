@@ -13,6 +13,17 @@ pub mod validation_error;
 pub mod password_rest_model;
 pub mod setting_model;
 pub mod model_model;
+pub mod admin_user_two_factor_model;
+pub mod login_attempt_model;
+pub mod admin_user_lockout_model;
+pub mod cms_token_model;
+pub mod personal_access_token_model;
+pub mod oidc_login_state_model;
+pub mod admin_user_identity_model;
+pub mod permission_model;
+pub mod admin_user_invitation_model;
+pub mod image_rendition_model;
+pub mod asset_reference_model;
 
 #[derive(Serialize, Debug, Deserialize, Clone, Default)]
 pub struct Pagination {
@@ -38,6 +49,8 @@ pub trait  BaseModel {
     fn get_datetime(&self) -> Result<Datetime>;
     fn get_bool(&self) -> Result<bool>;
     fn get_int(&self) -> Result<i64>;
+    fn get_float(&self) -> Result<f64>;
+    fn get_string_array(&self) -> Result<Vec<String>>;
 
     // fn get_array<T>(&self) -> Result<Vec<T>>;
 }
@@ -106,6 +119,27 @@ impl BaseModel for Option<&Value> {
         Ok(value)
     }
 
+    fn get_float(&self) -> Result<f64> {
+        let value = match self.to_owned() {
+            Some(val) => match val.clone() {
+                Number(v) => v.as_float(),
+                _ => 0.0,
+            },
+            None => 0.0,
+        };
+
+        Ok(value)
+    }
+
+    fn get_string_array(&self) -> Result<Vec<String>> {
+        let value = match self.to_owned() {
+            Some(Value::Array(v)) => v.iter().map(|val| val.clone().as_string()).collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(value)
+    }
+
     // fn get_array<T>(&self) -> Result<Vec<T>> where T : TryFrom<Object> {
     //     let value = match self.to_owned() {
     //         Some(val) => match val.clone() {
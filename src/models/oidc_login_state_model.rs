@@ -0,0 +1,60 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object};
+use crate::models::BaseModel;
+
+/// Kept between the redirect to the identity provider and the callback.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct OidcLoginStateModel {
+    pub id: String,
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub expires_at: Datetime,
+    pub created_at: Datetime,
+}
+
+impl OidcLoginStateModel {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.0 <= chrono::Utc::now()
+    }
+}
+
+impl TryFrom<Object> for OidcLoginStateModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<OidcLoginStateModel> {
+        let id = val.get("id").get_id()?;
+        let state = val.get("state").get_string()?;
+        let nonce = val.get("nonce").get_string()?;
+        let code_verifier = val.get("code_verifier").get_string()?;
+        let expires_at = val.get("expires_at").get_datetime()?;
+        let created_at = val.get("created_at").get_datetime()?;
+
+        Ok(OidcLoginStateModel {
+            id,
+            state,
+            nonce,
+            code_verifier,
+            expires_at,
+            created_at
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatableOidcLoginStateModel {
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub expires_in_seconds: i64,
+}
+
+/// The claims of a validated id token that AvoRed cares about.
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct OidcIdentityModel {
+    pub issuer: String,
+    pub subject: String,
+    pub email: String,
+    pub name: String,
+    pub groups: Vec<String>,
+}
@@ -14,6 +14,8 @@ pub struct NewPageModel {
     pub identifier: String,
     pub status: PageStatus,
     pub page_fields: Vec<PageFieldModel>,
+    /// Ids of the roles the page is locked to, an empty list leaves the page open to every role.
+    pub locked_role_ids: Vec<String>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
     pub created_by: String,
@@ -289,6 +291,7 @@ impl TryFrom<Object> for NewPageModel {
         };
 
 
+        let locked_role_ids = val.get("locked_role_ids").get_string_array()?;
         let created_at = val.get("created_at").get_datetime()?;
         let updated_at = val.get("updated_at").get_datetime()?;
         let created_by = val.get("created_by").get_string()?;
@@ -300,6 +303,7 @@ impl TryFrom<Object> for NewPageModel {
             identifier,
             status,
             page_fields,
+            locked_role_ids,
             created_at,
             updated_at,
             created_by,
@@ -640,7 +644,8 @@ pub struct NewCreatablePageModel {
     pub identifier: String,
     pub status: PageStatus,
     pub logged_in_username: String,
-    pub page_fields: Vec<CreatablePageField>
+    pub page_fields: Vec<CreatablePageField>,
+    pub locked_role_ids: Vec<String>
 }
 
 #[derive(Serialize, Debug, Deserialize, Clone)]
@@ -662,7 +667,8 @@ pub struct NewUpdatablePageModel {
     pub logged_in_username: String,
     pub created_at: Datetime,
     pub created_by: String,
-    pub page_fields: Vec<UpdatablePageField>
+    pub page_fields: Vec<UpdatablePageField>,
+    pub locked_role_ids: Vec<String>
 }
 
 #[derive(Serialize, Debug, Deserialize, Clone)]
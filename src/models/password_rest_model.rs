@@ -3,12 +3,14 @@ use serde::{Deserialize, Serialize};
 use surrealdb::sql::{Datetime, Object};
 use crate::models::BaseModel;
 
+/// Only the sha256 hash of the token sent in the email is stored.
 #[derive(Serialize, Debug, Deserialize, Clone, Default, PartialEq)]
 pub struct PasswordResetModel {
     pub id: String,
     pub email: String,
-    pub token: String,
+    pub token_hash: String,
     pub status: PasswordResetTokenStatus,
+    pub expires_at: Datetime,
     pub created_at: Datetime,
 }
 
@@ -19,25 +21,32 @@ pub enum PasswordResetTokenStatus {
     Expire
 }
 
+impl PasswordResetModel {
+    pub fn is_expired(&self) -> bool {
+        self.status == PasswordResetTokenStatus::Expire || self.expires_at.0 <= chrono::Utc::now()
+    }
+}
+
 impl TryFrom<Object> for PasswordResetModel {
     type Error = Error;
     fn try_from(val: Object) -> Result<PasswordResetModel> {
         let id = val.get("id").get_id()?;
         let email = val.get("email").get_string()?;
-        let token = val.get("token").get_string()?;
+        let token_hash = val.get("token_hash").get_string()?;
+        let expires_at = val.get("expires_at").get_datetime()?;
         let created_at = val.get("created_at").get_datetime()?;
         let status = match val.get("status").get_string()?.as_str() {
             "Active" => PasswordResetTokenStatus::Active,
-            "Expire" => PasswordResetTokenStatus::Active,
             _ => PasswordResetTokenStatus::Expire
         };
 
         Ok(PasswordResetModel {
             id,
             email,
-            token,
-            created_at,
-            status
+            token_hash,
+            status,
+            expires_at,
+            created_at
         })
     }
 }
@@ -47,5 +56,5 @@ impl TryFrom<Object> for PasswordResetModel {
 #[derive(Serialize, Debug, Deserialize, Clone, Default)]
 pub struct CreatablePasswordResetModel {
     pub email: String,
-    pub token: String,
+    pub token_hash: String,
 }
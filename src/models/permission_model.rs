@@ -0,0 +1,354 @@
+use serde::{Deserialize, Serialize};
+
+/// Every permission a role can grant. The identifiers are what gets stored on
+/// `RoleModel.permissions` and personal access tokens.
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    Dashboard,
+    SettingShow,
+    SettingEdit,
+    /// Settings which apply to how every admin user signs in, like enforcing two factor.
+    SettingSecurityEdit,
+    PageTable,
+    PageShow,
+    PageCreate,
+    PageEdit,
+    PageDelete,
+    /// Locking pages to roles and changing those locks.
+    PageLock,
+    ComponentTable,
+    ComponentShow,
+    ComponentCreate,
+    ComponentEdit,
+    ComponentDelete,
+    ModelTable,
+    ModelShow,
+    ModelCreate,
+    ModelEdit,
+    AssetTable,
+    AssetCreate,
+    AssetEdit,
+    AssetDelete,
+    AdminUserTable,
+    AdminUserShow,
+    AdminUserCreate,
+    AdminUserEdit,
+    AdminUserDelete,
+    RoleTable,
+    RoleShow,
+    RoleCreate,
+    RoleEdit,
+    RoleDelete,
+    CmsTokenTable,
+    CmsTokenCreate,
+    CmsTokenEdit,
+    CmsTokenDelete,
+    PersonalAccessTokenTable,
+    PersonalAccessTokenDelete,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionGroup {
+    Generic,
+    Page,
+    Component,
+    Model,
+    Asset,
+    AdminUser,
+    Role,
+    CmsToken,
+    PersonalAccessToken,
+}
+
+impl PermissionGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionGroup::Generic => "generic",
+            PermissionGroup::Page => "page",
+            PermissionGroup::Component => "component",
+            PermissionGroup::Model => "model",
+            PermissionGroup::Asset => "asset",
+            PermissionGroup::AdminUser => "admin_user",
+            PermissionGroup::Role => "role",
+            PermissionGroup::CmsToken => "cms_token",
+            PermissionGroup::PersonalAccessToken => "personal_access_token",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PermissionGroup::Generic => "Generics",
+            PermissionGroup::Page => "Page",
+            PermissionGroup::Component => "Component",
+            PermissionGroup::Model => "Model",
+            PermissionGroup::Asset => "Asset",
+            PermissionGroup::AdminUser => "Admin user",
+            PermissionGroup::Role => "Role",
+            PermissionGroup::CmsToken => "Cms token",
+            PermissionGroup::PersonalAccessToken => "Personal access token",
+        }
+    }
+
+    pub fn all() -> Vec<PermissionGroup> {
+        vec![
+            PermissionGroup::Generic,
+            PermissionGroup::Page,
+            PermissionGroup::Component,
+            PermissionGroup::Model,
+            PermissionGroup::Asset,
+            PermissionGroup::AdminUser,
+            PermissionGroup::Role,
+            PermissionGroup::CmsToken,
+            PermissionGroup::PersonalAccessToken,
+        ]
+    }
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::Dashboard => "dashboard",
+            Permission::SettingShow => "setting_show",
+            Permission::SettingEdit => "setting_edit",
+            Permission::SettingSecurityEdit => "setting_security_edit",
+            Permission::PageTable => "page_table",
+            Permission::PageShow => "page_show",
+            Permission::PageCreate => "page_create",
+            Permission::PageEdit => "page_edit",
+            Permission::PageDelete => "page_delete",
+            Permission::PageLock => "page_lock",
+            Permission::ComponentTable => "component_table",
+            Permission::ComponentShow => "component_show",
+            Permission::ComponentCreate => "component_create",
+            Permission::ComponentEdit => "component_edit",
+            Permission::ComponentDelete => "component_delete",
+            Permission::ModelTable => "model_table",
+            Permission::ModelShow => "model_show",
+            Permission::ModelCreate => "model_create",
+            Permission::ModelEdit => "model_edit",
+            Permission::AssetTable => "asset_table",
+            Permission::AssetCreate => "asset_create",
+            Permission::AssetEdit => "asset_edit",
+            Permission::AssetDelete => "asset_delete",
+            Permission::AdminUserTable => "admin_user_table",
+            Permission::AdminUserShow => "admin_user_show",
+            Permission::AdminUserCreate => "admin_user_create",
+            Permission::AdminUserEdit => "admin_user_edit",
+            Permission::AdminUserDelete => "admin_user_delete",
+            Permission::RoleTable => "role_table",
+            Permission::RoleShow => "role_show",
+            Permission::RoleCreate => "role_create",
+            Permission::RoleEdit => "role_edit",
+            Permission::RoleDelete => "role_delete",
+            Permission::CmsTokenTable => "cms_token_table",
+            Permission::CmsTokenCreate => "cms_token_create",
+            Permission::CmsTokenEdit => "cms_token_edit",
+            Permission::CmsTokenDelete => "cms_token_delete",
+            Permission::PersonalAccessTokenTable => "personal_access_token_table",
+            Permission::PersonalAccessTokenDelete => "personal_access_token_delete",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Permission::Dashboard => "Dashboard",
+            Permission::SettingShow => "Get settings",
+            Permission::SettingEdit => "Save settings",
+            Permission::SettingSecurityEdit => "Save security settings",
+            Permission::PageTable => "Page table",
+            Permission::PageShow => "Get page",
+            Permission::PageCreate => "Page create",
+            Permission::PageEdit => "Page edit",
+            Permission::PageDelete => "Page delete",
+            Permission::PageLock => "Lock pages to roles",
+            Permission::ComponentTable => "Component table",
+            Permission::ComponentShow => "Get component",
+            Permission::ComponentCreate => "Component create",
+            Permission::ComponentEdit => "Component edit",
+            Permission::ComponentDelete => "Component delete",
+            Permission::ModelTable => "Model table",
+            Permission::ModelShow => "Get model",
+            Permission::ModelCreate => "Model create",
+            Permission::ModelEdit => "Model edit",
+            Permission::AssetTable => "Asset table",
+            Permission::AssetCreate => "Asset and folder create",
+            Permission::AssetEdit => "Asset edit",
+            Permission::AssetDelete => "Asset and folder delete",
+            Permission::AdminUserTable => "Admin user table",
+            Permission::AdminUserShow => "Get admin user",
+            Permission::AdminUserCreate => "Admin user create",
+            Permission::AdminUserEdit => "Admin user edit",
+            Permission::AdminUserDelete => "Admin user delete",
+            Permission::RoleTable => "Role table",
+            Permission::RoleShow => "Get role",
+            Permission::RoleCreate => "Role create",
+            Permission::RoleEdit => "Role edit",
+            Permission::RoleDelete => "Role delete",
+            Permission::CmsTokenTable => "Cms token table",
+            Permission::CmsTokenCreate => "Cms token create",
+            Permission::CmsTokenEdit => "Cms token rotate",
+            Permission::CmsTokenDelete => "Cms token revoke",
+            Permission::PersonalAccessTokenTable => "Personal access token table",
+            Permission::PersonalAccessTokenDelete => "Personal access token revoke",
+        }
+    }
+
+    pub fn group(&self) -> PermissionGroup {
+        match self {
+            Permission::Dashboard
+            | Permission::SettingShow
+            | Permission::SettingEdit
+            | Permission::SettingSecurityEdit => PermissionGroup::Generic,
+            Permission::PageTable
+            | Permission::PageShow
+            | Permission::PageCreate
+            | Permission::PageEdit
+            | Permission::PageDelete
+            | Permission::PageLock => PermissionGroup::Page,
+            Permission::ComponentTable
+            | Permission::ComponentShow
+            | Permission::ComponentCreate
+            | Permission::ComponentEdit
+            | Permission::ComponentDelete => PermissionGroup::Component,
+            Permission::ModelTable
+            | Permission::ModelShow
+            | Permission::ModelCreate
+            | Permission::ModelEdit => PermissionGroup::Model,
+            Permission::AssetTable
+            | Permission::AssetCreate
+            | Permission::AssetEdit
+            | Permission::AssetDelete => PermissionGroup::Asset,
+            Permission::AdminUserTable
+            | Permission::AdminUserShow
+            | Permission::AdminUserCreate
+            | Permission::AdminUserEdit
+            | Permission::AdminUserDelete => PermissionGroup::AdminUser,
+            Permission::RoleTable
+            | Permission::RoleShow
+            | Permission::RoleCreate
+            | Permission::RoleEdit
+            | Permission::RoleDelete => PermissionGroup::Role,
+            Permission::CmsTokenTable
+            | Permission::CmsTokenCreate
+            | Permission::CmsTokenEdit
+            | Permission::CmsTokenDelete => PermissionGroup::CmsToken,
+            Permission::PersonalAccessTokenTable
+            | Permission::PersonalAccessTokenDelete => PermissionGroup::PersonalAccessToken,
+        }
+    }
+
+    pub fn all() -> Vec<Permission> {
+        vec![
+            Permission::Dashboard,
+            Permission::SettingShow,
+            Permission::SettingEdit,
+            Permission::SettingSecurityEdit,
+            Permission::PageTable,
+            Permission::PageShow,
+            Permission::PageCreate,
+            Permission::PageEdit,
+            Permission::PageDelete,
+            Permission::PageLock,
+            Permission::ComponentTable,
+            Permission::ComponentShow,
+            Permission::ComponentCreate,
+            Permission::ComponentEdit,
+            Permission::ComponentDelete,
+            Permission::ModelTable,
+            Permission::ModelShow,
+            Permission::ModelCreate,
+            Permission::ModelEdit,
+            Permission::AssetTable,
+            Permission::AssetCreate,
+            Permission::AssetEdit,
+            Permission::AssetDelete,
+            Permission::AdminUserTable,
+            Permission::AdminUserShow,
+            Permission::AdminUserCreate,
+            Permission::AdminUserEdit,
+            Permission::AdminUserDelete,
+            Permission::RoleTable,
+            Permission::RoleShow,
+            Permission::RoleCreate,
+            Permission::RoleEdit,
+            Permission::RoleDelete,
+            Permission::CmsTokenTable,
+            Permission::CmsTokenCreate,
+            Permission::CmsTokenEdit,
+            Permission::CmsTokenDelete,
+            Permission::PersonalAccessTokenTable,
+            Permission::PersonalAccessTokenDelete,
+        ]
+    }
+
+    pub fn from_identifier(identifier: &str) -> Option<Permission> {
+        Permission::all()
+            .into_iter()
+            .find(|permission| permission.as_str() == identifier)
+    }
+
+    /// Identifiers the handlers used to check before the registry existed, roles saved
+    /// with them are rewritten on startup.
+    pub fn from_legacy_identifier(identifier: &str) -> Option<Permission> {
+        match identifier {
+            "get_setting" => Some(Permission::SettingShow),
+            "save_setting" => Some(Permission::SettingEdit),
+            "get_page" => Some(Permission::PageShow),
+            "get_component" => Some(Permission::ComponentShow),
+            "get_model" => Some(Permission::ModelShow),
+            "create_folder" => Some(Permission::AssetCreate),
+            "rename_asset" => Some(Permission::AssetEdit),
+            "delete_asset" | "delete_folder" => Some(Permission::AssetDelete),
+            "get_admin_user" => Some(Permission::AdminUserShow),
+            "get_role" => Some(Permission::RoleShow),
+            _ => None
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PermissionOptionModel {
+    pub identifier: String,
+    pub label: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PermissionGroupModel {
+    pub identifier: String,
+    pub label: String,
+    pub permissions: Vec<PermissionOptionModel>,
+}
+
+impl PermissionGroupModel {
+    pub fn all() -> Vec<PermissionGroupModel> {
+        PermissionGroup::all()
+            .into_iter()
+            .map(|group| PermissionGroupModel {
+                identifier: group.as_str().to_string(),
+                label: group.label().to_string(),
+                permissions: Permission::all()
+                    .into_iter()
+                    .filter(|permission| permission.group() == group)
+                    .map(|permission| PermissionOptionModel {
+                        identifier: permission.as_str().to_string(),
+                        label: permission.label().to_string(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Permission;
+
+    #[test]
+    fn test_permission_identifiers_round_trip() {
+        for permission in Permission::all() {
+            assert_eq!(Permission::from_identifier(permission.as_str()), Some(permission));
+        }
+        assert_eq!(Permission::from_identifier("rename_asset"), None);
+        assert_eq!(Permission::from_legacy_identifier("rename_asset"), Some(Permission::AssetEdit));
+    }
+}
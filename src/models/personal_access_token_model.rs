@@ -0,0 +1,73 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Datetime, Object, Value};
+use crate::models::BaseModel;
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct PersonalAccessTokenModel {
+    pub id: String,
+    pub admin_user_id: String,
+    pub name: String,
+    pub token_prefix: String,
+    /// Upper bound of what the token can do, the roles of the admin user still apply.
+    pub permissions: Vec<String>,
+    pub expires_at: Datetime,
+    pub last_used_at: Option<Datetime>,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+impl PersonalAccessTokenModel {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.0 <= chrono::Utc::now()
+    }
+}
+
+impl TryFrom<Object> for PersonalAccessTokenModel {
+    type Error = Error;
+    fn try_from(val: Object) -> Result<PersonalAccessTokenModel> {
+        let id = val.get("id").get_id()?;
+        let admin_user_id = val.get("admin_user_id").get_string()?;
+        let name = val.get("name").get_string()?;
+        let token_prefix = val.get("token_prefix").get_string()?;
+        let permissions = match val.get("permissions") {
+            Some(Value::Array(v)) => v.iter().map(|value| value.clone().as_string()).collect(),
+            _ => Vec::new(),
+        };
+        let expires_at = val.get("expires_at").get_datetime()?;
+        let last_used_at = match val.get("last_used_at") {
+            Some(Value::Datetime(v)) => Some(v.clone()),
+            _ => None
+        };
+        let created_at = val.get("created_at").get_datetime()?;
+        let updated_at = val.get("updated_at").get_datetime()?;
+
+        Ok(PersonalAccessTokenModel {
+            id,
+            admin_user_id,
+            name,
+            token_prefix,
+            permissions,
+            expires_at,
+            last_used_at,
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct CreatablePersonalAccessTokenModel {
+    pub admin_user_id: String,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub permissions: Vec<String>,
+    pub expires_at: Datetime,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+pub struct IssuedPersonalAccessTokenModel {
+    pub token: String,
+    pub personal_access_token: PersonalAccessTokenModel,
+}
@@ -1,6 +1,6 @@
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
-use surrealdb::sql::{Datetime, Object, Value};
+use surrealdb::sql::{Datetime, Object};
 use utoipa::ToSchema;
 
 use super::{BaseModel, Pagination};
@@ -17,6 +17,15 @@ pub struct RoleModel {
     pub created_by: String,
     pub updated_by: String,
     pub permissions: Vec<String>,
+    /// Pages can only be edited or deleted by the admin user who created them.
+    #[serde(default)]
+    pub own_pages_only: bool,
+    /// Restricts the role to pages whose identifier starts with one of the prefixes.
+    #[serde(default)]
+    pub page_identifier_prefixes: Vec<String>,
+    /// Restricts the role to these asset folders and everything below them.
+    #[serde(default)]
+    pub asset_folder_ids: Vec<String>,
 }
 
 #[derive(Serialize, Debug, Deserialize, Clone, Default)]
@@ -36,23 +45,10 @@ impl TryFrom<Object> for RoleModel {
         let updated_at = val.get("updated_at").get_datetime()?;
         let created_by = val.get("created_by").get_string()?;
         let updated_by = val.get("updated_by").get_string()?;
-        let permissions = match val.get("permissions") {
-            Some(val) => {
-                
-                match val.clone() {
-                    Value::Array(v) => {
-                        let mut arr = Vec::new();
-
-                        for array in v.into_iter() {
-                            arr.push(array.as_string())
-                        }
-                        arr
-                    }
-                    _ => Vec::new(),
-                }
-            }
-            None => Vec::new(),
-        };
+        let permissions = val.get("permissions").get_string_array()?;
+        let own_pages_only = val.get("own_pages_only").get_bool()?;
+        let page_identifier_prefixes = val.get("page_identifier_prefixes").get_string_array()?;
+        let asset_folder_ids = val.get("asset_folder_ids").get_string_array()?;
 
         Ok(RoleModel {
             id,
@@ -63,16 +59,32 @@ impl TryFrom<Object> for RoleModel {
             created_by,
             updated_by,
             permissions,
+            own_pages_only,
+            page_identifier_prefixes,
+            asset_folder_ids,
         })
     }
 }
 
+impl RoleModel {
+    pub fn covers_page_identifier(&self, identifier: &str) -> bool {
+        self.page_identifier_prefixes.is_empty()
+            || self
+                .page_identifier_prefixes
+                .iter()
+                .any(|prefix| identifier.starts_with(prefix.as_str()))
+    }
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone)]
 pub struct CreatableRole {
     pub name: String,
     pub identifier: String,
     pub logged_in_username: String,
     pub permissions: Vec<String>,
+    pub own_pages_only: bool,
+    pub page_identifier_prefixes: Vec<String>,
+    pub asset_folder_ids: Vec<String>,
 }
 
 #[derive(Serialize, Debug, Deserialize, Clone)]
@@ -81,6 +93,9 @@ pub struct UpdatableRoleModel {
     pub name: String,
     pub logged_in_username: String,
     pub permissions: Vec<String>,
+    pub own_pages_only: bool,
+    pub page_identifier_prefixes: Vec<String>,
+    pub asset_folder_ids: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
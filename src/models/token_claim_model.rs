@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use crate::models::admin_user_model::AdminUserModel;
+use crate::models::page_model::NewPageModel;
+use crate::models::permission_model::Permission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenClaims {
@@ -17,5 +19,172 @@ pub struct LoggedInUser {
     pub name: String,
     pub email: String,
     pub demo_data_status: bool,
-    pub admin_user_model: AdminUserModel
-}
\ No newline at end of file
+    pub admin_user_model: AdminUserModel,
+    /// Set when the request is authenticated with a personal access token, it caps the
+    /// permissions of the admin user roles.
+    #[serde(default)]
+    pub token_permissions: Option<Vec<String>>
+}
+
+impl LoggedInUser {
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        let identifier = permission.as_str();
+        if let Some(token_permissions) = &self.token_permissions {
+            if !token_permissions.iter().any(|token_permission| token_permission == identifier) {
+                return false;
+            }
+        }
+        if self.admin_user_model.is_super_admin {
+            return true;
+        }
+
+        self.admin_user_model
+            .roles
+            .iter()
+            .any(|role| role.permissions.iter().any(|role_permission| role_permission == identifier))
+    }
+
+    /// Page lists can skip the per record rules when this holds.
+    pub fn can_view_all_pages(&self) -> bool {
+        self.admin_user_model.is_super_admin
+    }
+
+    /// Per record rules of the roles, a role grants access when every one of its rules
+    /// allows the page.
+    pub fn can_view_page(&self, page: &NewPageModel) -> bool {
+        if self.admin_user_model.is_super_admin {
+            return true;
+        }
+
+        self.admin_user_model.roles.iter().any(|role| {
+            role.covers_page_identifier(&page.identifier)
+                && (page.locked_role_ids.is_empty() || page.locked_role_ids.contains(&role.id))
+        })
+    }
+
+    pub fn can_edit_page(&self, page: &NewPageModel) -> bool {
+        if self.admin_user_model.is_super_admin {
+            return true;
+        }
+
+        self.admin_user_model.roles.iter().any(|role| {
+            role.covers_page_identifier(&page.identifier)
+                && (page.locked_role_ids.is_empty() || page.locked_role_ids.contains(&role.id))
+                && (!role.own_pages_only || page.created_by == self.email)
+        })
+    }
+
+    pub fn can_use_page_identifier(&self, identifier: &str) -> bool {
+        self.admin_user_model.is_super_admin
+            || self
+                .admin_user_model
+                .roles
+                .iter()
+                .any(|role| role.covers_page_identifier(identifier))
+    }
+
+    /// `None` when the admin user can reach every asset folder.
+    pub fn asset_folder_restrictions(&self) -> Option<Vec<String>> {
+        let roles = &self.admin_user_model.roles;
+        if self.admin_user_model.is_super_admin
+            || roles.iter().any(|role| role.asset_folder_ids.is_empty())
+        {
+            return None;
+        }
+
+        let mut folder_ids: Vec<String> = roles
+            .iter()
+            .flat_map(|role| role.asset_folder_ids.clone())
+            .collect();
+        folder_ids.sort();
+        folder_ids.dedup();
+
+        Some(folder_ids)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TwoFactorChallengeClaims {
+    pub sub: String,
+    pub purpose: String,
+    pub iat: usize,
+    pub exp: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::admin_user_model::AdminUserModel;
+    use crate::models::page_model::NewPageModel;
+    use crate::models::role_model::RoleModel;
+    use super::LoggedInUser;
+
+    fn logged_in_user(roles: Vec<RoleModel>) -> LoggedInUser {
+        LoggedInUser {
+            id: String::from("editor"),
+            name: String::from("Editor"),
+            email: String::from("editor@avored.com"),
+            demo_data_status: false,
+            admin_user_model: AdminUserModel {
+                roles,
+                ..Default::default()
+            },
+            token_permissions: None,
+        }
+    }
+
+    fn page(identifier: &str, created_by: &str, locked_role_ids: Vec<String>) -> NewPageModel {
+        NewPageModel {
+            identifier: identifier.to_string(),
+            created_by: created_by.to_string(),
+            locked_role_ids,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_page_access_rules() {
+        let editor = logged_in_user(vec![RoleModel {
+            id: String::from("editor"),
+            own_pages_only: true,
+            page_identifier_prefixes: vec![String::from("blog-")],
+            ..Default::default()
+        }]);
+
+        let own_page = page("blog-first", "editor@avored.com", vec![]);
+        assert!(editor.can_view_page(&own_page));
+        assert!(editor.can_edit_page(&own_page));
+
+        let other_page = page("blog-second", "admin@avored.com", vec![]);
+        assert!(editor.can_view_page(&other_page));
+        assert!(!editor.can_edit_page(&other_page));
+
+        let outside_page = page("about-us", "editor@avored.com", vec![]);
+        assert!(!editor.can_view_page(&outside_page));
+        assert!(!editor.can_use_page_identifier("about-us"));
+
+        let locked_page = page("blog-legal", "editor@avored.com", vec![String::from("legal")]);
+        assert!(!editor.can_view_page(&locked_page));
+
+        let mut super_admin = logged_in_user(vec![]);
+        super_admin.admin_user_model.is_super_admin = true;
+        assert!(super_admin.can_edit_page(&locked_page));
+        assert_eq!(super_admin.asset_folder_restrictions(), None);
+    }
+
+    #[test]
+    fn test_asset_folder_restrictions() {
+        let folder_role = RoleModel {
+            asset_folder_ids: vec![String::from("images"), String::from("documents")],
+            ..Default::default()
+        };
+        let restricted = logged_in_user(vec![folder_role.clone()]);
+        assert_eq!(
+            restricted.asset_folder_restrictions(),
+            Some(vec![String::from("documents"), String::from("images")])
+        );
+
+        // a role without folders lifts the restriction of the other roles
+        let unrestricted = logged_in_user(vec![folder_role, RoleModel::default()]);
+        assert_eq!(unrestricted.asset_folder_restrictions(), None);
+    }
+}
@@ -2,6 +2,23 @@ use std::env;
 use dotenvy::dotenv;
 use crate::error::{Error, Result};
 
+/// `mime/type:MAX_KB` pairs, used when AVORED_ASSET_ALLOWED_TYPES is not set.
+const DEFAULT_ASSET_ALLOWED_TYPES: &str = "image/jpeg:10240,\
+    image/png:10240,\
+    image/gif:10240,\
+    image/webp:10240,\
+    image/svg+xml:1024,\
+    application/pdf:20480,\
+    video/mp4:102400,\
+    text/plain:1024,\
+    text/csv:5120,\
+    application/msword:20480,\
+    application/vnd.openxmlformats-officedocument.wordprocessingml.document:20480,\
+    application/vnd.ms-excel:20480,\
+    application/vnd.openxmlformats-officedocument.spreadsheetml.sheet:20480,\
+    application/vnd.ms-powerpoint:51200,\
+    application/vnd.openxmlformats-officedocument.presentationml.presentation:51200";
+
 #[derive(Debug, Clone)]
 pub struct AvoRedConfigProvider {
     pub database_folder_name: String,
@@ -12,11 +29,63 @@ pub struct AvoRedConfigProvider {
     // pub react_frontend_app_url: String,
     pub back_end_app_url: String,
     pub cors_allowed_app_url: Vec<String>,
-    pub password_salt: String,
+    /// Only used to recognise hashes made with the old shared salt, so they get rehashed on login.
+    pub password_salt: Option<String>,
+    pub password_min_length: usize,
+    pub password_require_uppercase: bool,
+    pub password_require_lowercase: bool,
+    pub password_require_number: bool,
+    pub password_require_symbol: bool,
+    pub password_breached_list_path: String,
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
     pub smtp_host: String,
     pub smtp_username: String,
     pub smtp_password: String,
     pub smtp_port: u16,
+    pub login_max_attempts_per_ip: i64,
+    pub login_max_attempts_per_email: i64,
+    pub login_attempt_window_seconds: i64,
+    pub login_lockout_seconds: i64,
+    pub forgot_password_max_attempts_per_ip: i64,
+    pub forgot_password_max_attempts_per_email: i64,
+    pub forgot_password_attempt_window_seconds: i64,
+    pub trust_proxy_headers: bool,
+    /// Single sign on is optional, so none of the oidc settings are required.
+    pub oidc_enabled: bool,
+    pub oidc_issuer_url: String,
+    pub oidc_client_id: String,
+    pub oidc_client_secret: String,
+    pub oidc_redirect_url: String,
+    pub oidc_scopes: Vec<String>,
+    pub oidc_groups_claim: String,
+    /// Pairs of identity provider group and role identifier.
+    pub oidc_group_role_map: Vec<(String, String)>,
+    pub oidc_jwks_cache_seconds: i64,
+    pub admin_user_invitation_expire_in_hours: i64,
+    pub password_reset_expire_in_minutes: i64,
+    pub avatar_max_file_size_kb: usize,
+    pub image_presets: Vec<String>,
+    pub image_max_dimension: u32,
+    /// Sizes asked for outside of the presets have to be a multiple of it.
+    pub image_size_step: u32,
+    pub image_cache_max_kb_per_asset: u64,
+    pub asset_allowed_types: Vec<String>,
+    pub asset_archive_max_size_kb: usize,
+    pub asset_archive_max_entries: usize,
+    pub asset_archive_max_uncompressed_kb: usize,
+    pub asset_url_signing_secret: String,
+    pub asset_signed_url_expires_in: i64,
+    pub asset_signed_url_max_expires_in: i64,
+    pub asset_storage_driver: String,
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_private_bucket: String,
+    pub s3_region: String,
+    pub s3_access_key: String,
+    pub s3_secret_key: String,
+    pub s3_public_url: String,
 }
 
 // pub fn config() -> &'static AvoRedConfigProvider {
@@ -41,6 +110,32 @@ impl AvoRedConfigProvider {
             _ => dotenvy::from_filename_override(".env")?,
         };
 
+        let oidc_scopes = get_env("AVORED_OIDC_SCOPES")
+            .unwrap_or_else(|_| String::from("openid email profile"))
+            .split_whitespace()
+            .map(|scope| scope.to_string())
+            .collect();
+        let oidc_group_role_map = get_env("AVORED_OIDC_GROUP_ROLE_MAP")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|pair| pair.split_once(':'))
+            .map(|(group, role_identifier)| (group.trim().to_string(), role_identifier.trim().to_string()))
+            .collect();
+
+        let image_presets = get_env("AVORED_IMAGE_PRESETS")
+            .unwrap_or_else(|_| String::from("thumbnail:150x150:cover,small:480x480:contain,medium:1024x1024:contain"))
+            .split(',')
+            .map(|definition| definition.trim().to_string())
+            .filter(|definition| !definition.is_empty())
+            .collect();
+
+        let asset_allowed_types = get_env("AVORED_ASSET_ALLOWED_TYPES")
+            .unwrap_or_else(|_| String::from(DEFAULT_ASSET_ALLOWED_TYPES))
+            .split(',')
+            .map(|definition| definition.trim().to_string())
+            .filter(|definition| !definition.is_empty())
+            .collect();
+
         let env_str_allowed_cors = get_env("AVORED_CORS_ALLOWED_APP_URL")?;
         let vec_cors_urls = env_str_allowed_cors.split(',').collect::<Vec<&str>>();
         let cors_urls = vec_cors_urls.iter().map(|url| url.to_string()).collect();
@@ -54,11 +149,84 @@ impl AvoRedConfigProvider {
             // react_frontend_app_url: get_env("AVORED_REACT_FRONTEND_APP_URL")?,
             back_end_app_url: get_env("AVORED_BACK_END_APP_URL")?,
             cors_allowed_app_url: cors_urls,
-            password_salt: get_env("AVORED_PASSWORD_SALT")?,
+            password_salt: get_env("AVORED_PASSWORD_SALT").ok(),
+            password_min_length: get_env("AVORED_PASSWORD_MIN_LENGTH")?.parse::<usize>()?,
+            password_require_uppercase: get_env("AVORED_PASSWORD_REQUIRE_UPPERCASE")?.eq("true"),
+            password_require_lowercase: get_env("AVORED_PASSWORD_REQUIRE_LOWERCASE")?.eq("true"),
+            password_require_number: get_env("AVORED_PASSWORD_REQUIRE_NUMBER")?.eq("true"),
+            password_require_symbol: get_env("AVORED_PASSWORD_REQUIRE_SYMBOL")?.eq("true"),
+            password_breached_list_path: get_env("AVORED_PASSWORD_BREACHED_LIST_PATH")?,
+            argon2_memory_kib: get_env("AVORED_ARGON2_MEMORY_KIB")?.parse::<u32>()?,
+            argon2_iterations: get_env("AVORED_ARGON2_ITERATIONS")?.parse::<u32>()?,
+            argon2_parallelism: get_env("AVORED_ARGON2_PARALLELISM")?.parse::<u32>()?,
             smtp_host: get_env("SMTP_HOST")?,
             smtp_username: get_env("SMTP_USERNAME")?,
             smtp_password: get_env("SMTP_PASSWORD")?,
             smtp_port: get_env("SMTP_PORT")?.parse::<u16>()?,
+            login_max_attempts_per_ip: get_env("AVORED_LOGIN_MAX_ATTEMPTS_PER_IP")?.parse::<i64>()?,
+            login_max_attempts_per_email: get_env("AVORED_LOGIN_MAX_ATTEMPTS_PER_EMAIL")?.parse::<i64>()?,
+            login_attempt_window_seconds: get_env("AVORED_LOGIN_ATTEMPT_WINDOW_SECONDS")?.parse::<i64>()?,
+            login_lockout_seconds: get_env("AVORED_LOGIN_LOCKOUT_SECONDS")?.parse::<i64>()?,
+            forgot_password_max_attempts_per_ip: get_env("AVORED_FORGOT_PASSWORD_MAX_ATTEMPTS_PER_IP")?.parse::<i64>()?,
+            forgot_password_max_attempts_per_email: get_env("AVORED_FORGOT_PASSWORD_MAX_ATTEMPTS_PER_EMAIL")?.parse::<i64>()?,
+            forgot_password_attempt_window_seconds: get_env("AVORED_FORGOT_PASSWORD_ATTEMPT_WINDOW_SECONDS")?.parse::<i64>()?,
+            trust_proxy_headers: get_env("AVORED_TRUST_PROXY_HEADERS")?.eq("true"),
+            oidc_enabled: get_env("AVORED_OIDC_ENABLED").unwrap_or_default().eq("true"),
+            oidc_issuer_url: get_env("AVORED_OIDC_ISSUER_URL").unwrap_or_default(),
+            oidc_client_id: get_env("AVORED_OIDC_CLIENT_ID").unwrap_or_default(),
+            oidc_client_secret: get_env("AVORED_OIDC_CLIENT_SECRET").unwrap_or_default(),
+            oidc_redirect_url: get_env("AVORED_OIDC_REDIRECT_URL").unwrap_or_default(),
+            oidc_scopes,
+            oidc_groups_claim: get_env("AVORED_OIDC_GROUPS_CLAIM").unwrap_or_else(|_| String::from("groups")),
+            oidc_group_role_map,
+            oidc_jwks_cache_seconds: get_env("AVORED_OIDC_JWKS_CACHE_SECONDS")
+                .unwrap_or_else(|_| String::from("3600"))
+                .parse::<i64>()?,
+            admin_user_invitation_expire_in_hours: get_env("AVORED_ADMIN_USER_INVITATION_EXPIRE_IN_HOURS")
+                .unwrap_or_else(|_| String::from("72"))
+                .parse::<i64>()?,
+            password_reset_expire_in_minutes: get_env("AVORED_PASSWORD_RESET_EXPIRE_IN_MINUTES")
+                .unwrap_or_else(|_| String::from("60"))
+                .parse::<i64>()?,
+            avatar_max_file_size_kb: get_env("AVORED_AVATAR_MAX_FILE_SIZE_KB")
+                .unwrap_or_else(|_| String::from("2048"))
+                .parse::<usize>()?,
+            image_presets,
+            image_max_dimension: get_env("AVORED_IMAGE_MAX_DIMENSION")
+                .unwrap_or_else(|_| String::from("4096"))
+                .parse::<u32>()?,
+            image_size_step: get_env("AVORED_IMAGE_SIZE_STEP")
+                .unwrap_or_else(|_| String::from("100"))
+                .parse::<u32>()?
+                .max(1),
+            image_cache_max_kb_per_asset: get_env("AVORED_IMAGE_CACHE_MAX_KB_PER_ASSET")
+                .unwrap_or_else(|_| String::from("51200"))
+                .parse::<u64>()?,
+            asset_allowed_types,
+            asset_archive_max_size_kb: get_env("AVORED_ASSET_ARCHIVE_MAX_SIZE_KB")
+                .unwrap_or_else(|_| String::from("102400"))
+                .parse::<usize>()?,
+            asset_archive_max_entries: get_env("AVORED_ASSET_ARCHIVE_MAX_ENTRIES")
+                .unwrap_or_else(|_| String::from("1000"))
+                .parse::<usize>()?,
+            asset_archive_max_uncompressed_kb: get_env("AVORED_ASSET_ARCHIVE_MAX_UNCOMPRESSED_KB")
+                .unwrap_or_else(|_| String::from("512000"))
+                .parse::<usize>()?,
+            asset_url_signing_secret: get_env("AVORED_ASSET_URL_SIGNING_SECRET")?,
+            asset_signed_url_expires_in: get_env("AVORED_ASSET_SIGNED_URL_EXPIRES_IN")
+                .unwrap_or_else(|_| String::from("3600"))
+                .parse::<i64>()?,
+            asset_signed_url_max_expires_in: get_env("AVORED_ASSET_SIGNED_URL_MAX_EXPIRES_IN")
+                .unwrap_or_else(|_| String::from("604800"))
+                .parse::<i64>()?,
+            asset_storage_driver: get_env("AVORED_ASSET_STORAGE_DRIVER").unwrap_or_else(|_| String::from("local")),
+            s3_endpoint: get_env("AVORED_S3_ENDPOINT").unwrap_or_default(),
+            s3_bucket: get_env("AVORED_S3_BUCKET").unwrap_or_default(),
+            s3_private_bucket: get_env("AVORED_S3_PRIVATE_BUCKET").unwrap_or_default(),
+            s3_region: get_env("AVORED_S3_REGION").unwrap_or_else(|_| String::from("us-east-1")),
+            s3_access_key: get_env("AVORED_S3_ACCESS_KEY").unwrap_or_default(),
+            s3_secret_key: get_env("AVORED_S3_SECRET_KEY").unwrap_or_default(),
+            s3_public_url: get_env("AVORED_S3_PUBLIC_URL").unwrap_or_default(),
         })
     }
 }
@@ -15,6 +15,8 @@ impl AvoRedTemplateProvider {
         let mut reg = Handlebars::new();
         reg.register_template_file("forgot-password", "./resources/mail/forgot-password.hbs")?;
         reg.register_template_file("contact-us-email", "./resources/mail/contact-us-email.hbs")?;
+        reg.register_template_file("unlock-account", "./resources/mail/unlock-account.hbs")?;
+        reg.register_template_file("admin-user-invitation", "./resources/mail/admin-user-invitation.hbs")?;
 
 
         println!("config {:?}", config);
@@ -2,3 +2,4 @@ pub mod avored_config_provider;
 pub mod avored_database_provider;
 pub mod avored_template_provider;
 pub mod avored_graphql_provider;
+pub mod avored_storage_provider;
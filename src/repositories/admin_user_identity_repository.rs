@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::admin_user_identity_model::{AdminUserIdentityModel, CreatableAdminUserIdentityModel};
+use crate::repositories::into_iter_objects;
+
+const ADMIN_USER_IDENTITY_TABLE: &str = "admin_user_identities";
+
+#[derive(Clone)]
+pub struct AdminUserIdentityRepository {}
+
+impl AdminUserIdentityRepository {
+    pub fn new() -> Self {
+        AdminUserIdentityRepository {}
+    }
+
+    pub async fn find_by_issuer_and_subject(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<AdminUserIdentityModel> {
+        let sql = "SELECT * FROM type::table($table) WHERE issuer=$issuer AND subject=$subject;";
+        let vars: BTreeMap<String, Value> = [
+            ("issuer".into(), issuer.into()),
+            ("subject".into(), subject.into()),
+            ("table".into(), ADMIN_USER_IDENTITY_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no admin user identity found for subject {subject}"))),
+        };
+        let identity_model: Result<AdminUserIdentityModel> = result_object?.try_into();
+
+        identity_model
+    }
+
+    pub async fn create_identity(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_identity_model: CreatableAdminUserIdentityModel,
+    ) -> Result<AdminUserIdentityModel> {
+        let sql = "CREATE type::table($table) CONTENT $data";
+
+        let data: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), creatable_identity_model.admin_user_id.into()),
+            ("issuer".into(), creatable_identity_model.issuer.into()),
+            ("subject".into(), creatable_identity_model.subject.into()),
+            ("created_at".into(), Datetime::default().into()),
+            ("updated_at".into(), Datetime::default().into()),
+        ].into();
+        let vars: BTreeMap<String, Value> = [
+            ("data".into(), data.into()),
+            ("table".into(), ADMIN_USER_IDENTITY_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create admin user identity".to_string())),
+        };
+        let identity_model: Result<AdminUserIdentityModel> = result_object?.try_into();
+
+        identity_model
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::admin_user_invitation_model::{AdminUserInvitationModel, CreatableAdminUserInvitationModel};
+use crate::models::ModelCount;
+use crate::repositories::into_iter_objects;
+
+const ADMIN_USER_INVITATION_TABLE: &str = "admin_user_invitations";
+
+#[derive(Clone)]
+pub struct AdminUserInvitationRepository {}
+
+impl AdminUserInvitationRepository {
+    pub fn new() -> Self {
+        AdminUserInvitationRepository {}
+    }
+
+    pub async fn all(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+    ) -> Result<Vec<AdminUserInvitationModel>> {
+        let sql = "SELECT * FROM type::table($table) ORDER BY created_at DESC;";
+        let vars: BTreeMap<String, Value> = [
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut invitation_list: Vec<AdminUserInvitationModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let invitation_model: Result<AdminUserInvitationModel> = object?.try_into();
+            invitation_list.push(invitation_model?);
+        }
+
+        Ok(invitation_list)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        invitation_id: &str,
+    ) -> Result<AdminUserInvitationModel> {
+        let sql = "SELECT * FROM type::thing($table, $id);";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), invitation_id.into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no admin user invitation found with id {invitation_id}"))),
+        };
+        let invitation_model: Result<AdminUserInvitationModel> = result_object?.try_into();
+
+        invitation_model
+    }
+
+    pub async fn find_by_id_and_token_hash(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        invitation_id: &str,
+        token_hash: &str,
+    ) -> Result<AdminUserInvitationModel> {
+        let sql = "SELECT * FROM type::thing($table, $id) WHERE token_hash=$token_hash;";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), invitation_id.into()),
+            ("token_hash".into(), token_hash.into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(String::from("no admin user invitation found for the token"))),
+        };
+        let invitation_model: Result<AdminUserInvitationModel> = result_object?.try_into();
+
+        invitation_model
+    }
+
+    pub async fn count_of_email(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        email: String,
+    ) -> Result<ModelCount> {
+        let sql = "SELECT count(email=$email) FROM type::table($table) GROUP ALL";
+        let vars: BTreeMap<String, Value> = [
+            ("email".into(), email.into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        match result_object_option {
+            Some(object) => object?.try_into(),
+            None => Ok(ModelCount::default()),
+        }
+    }
+
+    pub async fn create_invitation(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_invitation_model: CreatableAdminUserInvitationModel,
+        token_hash: String,
+        expires_in: Duration,
+    ) -> Result<AdminUserInvitationModel> {
+        let sql = "
+            CREATE type::table($table) CONTENT {
+                email: $email,
+                role_ids: $role_ids,
+                token_hash: $token_hash,
+                expires_at: time::now() + $expires_in,
+                created_by: $logged_in_username,
+                updated_by: $logged_in_username,
+                created_at: $now,
+                updated_at: $now
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("email".into(), creatable_invitation_model.email.into()),
+            ("role_ids".into(), creatable_invitation_model.role_ids.into()),
+            ("token_hash".into(), token_hash.into()),
+            ("expires_in".into(), expires_in.into()),
+            ("logged_in_username".into(), creatable_invitation_model.logged_in_username.into()),
+            ("now".into(), Datetime::default().into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create admin user invitation".to_string())),
+        };
+        let invitation_model: Result<AdminUserInvitationModel> = result_object?.try_into();
+
+        invitation_model
+    }
+
+    /// Replaces the token hash, which makes every link sent before unusable.
+    pub async fn refresh_token(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        invitation_id: &str,
+        token_hash: String,
+        expires_in: Duration,
+        logged_in_username: &str,
+    ) -> Result<AdminUserInvitationModel> {
+        let sql = "
+            UPDATE type::thing($table, $id) MERGE {
+                token_hash: $token_hash,
+                expires_at: time::now() + $expires_in,
+                updated_by: $logged_in_username,
+                updated_at: time::now()
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), invitation_id.into()),
+            ("token_hash".into(), token_hash.into()),
+            ("expires_in".into(), expires_in.into()),
+            ("logged_in_username".into(), logged_in_username.into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no admin user invitation found with id {invitation_id}"))),
+        };
+        let invitation_model: Result<AdminUserInvitationModel> = result_object?.try_into();
+
+        invitation_model
+    }
+
+    /// Deletes and returns the invitation when the token hash still matches, so a link can
+    /// only be used once.
+    pub async fn take_by_id_and_token_hash(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        invitation_id: &str,
+        token_hash: &str,
+    ) -> Result<AdminUserInvitationModel> {
+        let sql = "DELETE type::thing($table, $id) WHERE token_hash=$token_hash RETURN BEFORE;";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), invitation_id.into()),
+            ("token_hash".into(), token_hash.into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(String::from("no admin user invitation found for the token"))),
+        };
+        let invitation_model: Result<AdminUserInvitationModel> = result_object?.try_into();
+
+        invitation_model
+    }
+
+    pub async fn delete_by_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        invitation_id: &str,
+    ) -> Result<bool> {
+        let sql = "DELETE type::thing($table, $id) RETURN BEFORE;";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), invitation_id.into()),
+            ("table".into(), ADMIN_USER_INVITATION_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        Ok(into_iter_objects(responses)?.next().is_some())
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::admin_user_lockout_model::{AdminUserLockoutModel, CreatableAdminUserLockoutModel};
+use crate::repositories::into_iter_objects;
+
+const ADMIN_USER_LOCKOUT_TABLE: &str = "admin_user_lockouts";
+
+#[derive(Clone)]
+pub struct AdminUserLockoutRepository {}
+
+impl AdminUserLockoutRepository {
+    pub fn new() -> Self {
+        AdminUserLockoutRepository {}
+    }
+
+    pub async fn create_lockout(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_lockout_model: CreatableAdminUserLockoutModel,
+    ) -> Result<AdminUserLockoutModel> {
+        let sql = "
+            DELETE type::table($table) WHERE email=$email;
+            CREATE type::table($table) CONTENT {
+                email: $email,
+                token: $lockout_token,
+                locked_until: time::now() + $lockout,
+                created_at: $created_at
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("email".into(), creatable_lockout_model.email.into()),
+            ("lockout_token".into(), creatable_lockout_model.token.into()),
+            ("lockout".into(), Duration::from_secs(creatable_lockout_model.lockout_seconds as u64).into()),
+            ("created_at".into(), Datetime::default().into()),
+            ("table".into(), ADMIN_USER_LOCKOUT_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses.into_iter().skip(1).collect())?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create admin user lockout".to_string())),
+        };
+        let lockout_model: Result<AdminUserLockoutModel> = result_object?.try_into();
+
+        lockout_model
+    }
+
+    pub async fn find_active_by_email(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        email: &str,
+    ) -> Result<AdminUserLockoutModel> {
+        let sql = "SELECT * FROM type::table($table) WHERE email=$email AND locked_until > time::now();";
+        let vars: BTreeMap<String, Value> = [
+            ("email".into(), email.into()),
+            ("table".into(), ADMIN_USER_LOCKOUT_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no active lockout found for email {email}"))),
+        };
+        let lockout_model: Result<AdminUserLockoutModel> = result_object?.try_into();
+
+        lockout_model
+    }
+
+    /// Returns false when there is no lockout with the given email and token.
+    pub async fn delete_by_email_and_token(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        email: &str,
+        token: &str,
+    ) -> Result<bool> {
+        let sql = "DELETE type::table($table) WHERE email=$email AND token=$lockout_token RETURN BEFORE;";
+        let vars: BTreeMap<String, Value> = [
+            ("email".into(), email.into()),
+            ("lockout_token".into(), token.into()),
+            ("table".into(), ADMIN_USER_LOCKOUT_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        Ok(into_iter_objects(responses)?.next().is_some())
+    }
+}
@@ -77,6 +77,8 @@ impl AdminUserRepository {
             ("password".into(), creatable_admin_user_model.password.into(),),
             ("profile_image".into(), creatable_admin_user_model.profile_image.into(),),
             ("is_super_admin".into(), creatable_admin_user_model.is_super_admin.into(),),
+            ("is_two_factor_enabled".into(), false.into()),
+            ("is_active".into(), true.into()),
             ("created_by".into(), creatable_admin_user_model.logged_in_username.clone().into(),),
             ("updated_by".into(), creatable_admin_user_model.logged_in_username.into(),),
             ("created_at".into(), Datetime::default().into()),
@@ -107,7 +109,6 @@ impl AdminUserRepository {
         let sql = "
             UPDATE type::thing($table, $id) MERGE {
                 full_name: $full_name,
-                profile_image: $profile_image,
                 is_super_admin: $is_super_admin,
                 updated_by: $logged_in_user_name,
                 updated_at: time::now()
@@ -119,10 +120,6 @@ impl AdminUserRepository {
                 "logged_in_user_name".into(),
                 updatable_admin_user.logged_in_username.into(),
             ),
-            (
-                "profile_image".into(),
-                updatable_admin_user.profile_image.into(),
-            ),
             (
                 "is_super_admin".into(),
                 updatable_admin_user.is_super_admin.into(),
@@ -144,6 +141,118 @@ impl AdminUserRepository {
     }
 
 
+    pub async fn update_profile_image(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        id: String,
+        profile_image: String,
+        logged_in_username: String
+    ) -> Result<AdminUserModel> {
+        let sql = "
+            UPDATE type::thing($table, $id) MERGE {
+                profile_image: $profile_image,
+                updated_by: $logged_in_user_name,
+                updated_at: time::now()
+            };";
+
+        let vars = BTreeMap::from([
+            ("profile_image".into(), profile_image.into()),
+            ("logged_in_user_name".into(), logged_in_username.into()),
+            ("id".into(), id.into()),
+            ("table".into(), "admin_users".into()),
+        ]);
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+        let admin_user_model: Result<AdminUserModel> = result_object?.try_into();
+
+        admin_user_model
+    }
+
+    pub async fn update_active_status(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        id: String,
+        is_active: bool,
+        logged_in_username: String
+    ) -> Result<AdminUserModel> {
+        let sql = "
+            UPDATE type::thing($table, $id) MERGE {
+                is_active: $is_active,
+                updated_by: $logged_in_user_name,
+                updated_at: time::now()
+            };";
+
+        let vars = BTreeMap::from([
+            ("is_active".into(), is_active.into()),
+            ("logged_in_user_name".into(), logged_in_username.into()),
+            ("id".into(), id.into()),
+            ("table".into(), "admin_users".into()),
+        ]);
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+        let admin_user_model: Result<AdminUserModel> = result_object?.try_into();
+
+        admin_user_model
+    }
+
+    pub async fn count_of_active_super_admins(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+    ) -> Result<ModelCount> {
+        let sql = "SELECT count() FROM admin_users WHERE is_super_admin=true AND is_active=true GROUP ALL";
+
+        let responses = datastore.execute(sql, database_session, None).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+
+        match result_object {
+            Ok(obj) => obj.try_into(),
+            Err(_) => Ok(ModelCount::default()),
+        }
+    }
+
+
+    /// Older uploads only stored the path below the public folder, they get the given url
+    /// in front of it.
+    pub async fn prefix_relative_profile_images(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        url_prefix: &str
+    ) -> Result<()> {
+        let sql = "
+            UPDATE type::table($table) SET profile_image=string::concat($url_prefix, profile_image)
+            WHERE profile_image != '' AND !string::starts_with(profile_image, 'http');";
+
+        let vars = BTreeMap::from([
+            ("url_prefix".into(), url_prefix.into()),
+            ("table".into(), "admin_users".into()),
+        ]);
+
+        datastore.execute(sql, database_session, Some(vars)).await?;
+
+        Ok(())
+    }
+
     pub async fn update_password_by_email(
         &self,
         datastore: &Datastore,
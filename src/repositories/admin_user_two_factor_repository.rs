@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::admin_user_two_factor_model::{AdminUserTwoFactorModel, CreatableAdminUserTwoFactorModel};
+use crate::repositories::into_iter_objects;
+
+const ADMIN_USER_TWO_FACTOR_TABLE: &str = "admin_user_two_factors";
+
+#[derive(Clone)]
+pub struct AdminUserTwoFactorRepository {}
+
+impl AdminUserTwoFactorRepository {
+    pub fn new() -> Self {
+        AdminUserTwoFactorRepository {}
+    }
+
+    pub async fn find_by_admin_user_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        admin_user_id: &str,
+    ) -> Result<AdminUserTwoFactorModel> {
+        let sql = "SELECT * FROM type::table($table) WHERE admin_user_id=$admin_user_id;";
+        let vars: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), ADMIN_USER_TWO_FACTOR_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no two factor record found for admin user {admin_user_id}"))),
+        };
+        let two_factor_model: Result<AdminUserTwoFactorModel> = result_object?.try_into();
+
+        two_factor_model
+    }
+
+    /// Any previous (pending or enabled) secret of the admin user is removed, so there is
+    /// always only one two factor record per admin user.
+    pub async fn create_pending_two_factor(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_two_factor_model: CreatableAdminUserTwoFactorModel,
+    ) -> Result<AdminUserTwoFactorModel> {
+        let sql = "
+            DELETE type::table($table) WHERE admin_user_id=$admin_user_id;
+            CREATE type::table($table) CONTENT $data;";
+
+        let data: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), creatable_two_factor_model.admin_user_id.clone().into()),
+            ("secret".into(), creatable_two_factor_model.secret.into()),
+            ("recovery_codes".into(), Vec::<String>::new().into()),
+            ("status".into(), "Pending".into()),
+            ("last_used_step".into(), 0.into()),
+            ("created_at".into(), Datetime::default().into()),
+            ("updated_at".into(), Datetime::default().into()),
+        ].into();
+
+        let vars: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), creatable_two_factor_model.admin_user_id.into()),
+            ("table".into(), ADMIN_USER_TWO_FACTOR_TABLE.into()),
+            ("data".into(), data.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses.into_iter().skip(1).collect())?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create two factor record".to_string())),
+        };
+        let two_factor_model: Result<AdminUserTwoFactorModel> = result_object?.try_into();
+
+        two_factor_model
+    }
+
+    pub async fn enable_two_factor(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        admin_user_id: &str,
+        hashed_recovery_codes: Vec<String>,
+    ) -> Result<bool> {
+        let sql = "
+            UPDATE type::table($table) MERGE {
+                status: 'Enabled',
+                recovery_codes: $recovery_codes,
+                updated_at: time::now()
+            } WHERE admin_user_id=$admin_user_id;
+            UPDATE type::thing($admin_user_table, $admin_user_id) MERGE {
+                is_two_factor_enabled: true,
+                updated_at: time::now()
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("recovery_codes".into(), hashed_recovery_codes.into()),
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), ADMIN_USER_TWO_FACTOR_TABLE.into()),
+            ("admin_user_table".into(), "admin_users".into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+
+        if result_object.is_ok() {
+            return Ok(true);
+        }
+
+        Err(Error::Generic(format!("issue while enabling two factor for admin user: {admin_user_id}")))
+    }
+
+    pub async fn update_recovery_codes(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        admin_user_id: &str,
+        hashed_recovery_codes: Vec<String>,
+    ) -> Result<bool> {
+        let sql = "
+            UPDATE type::table($table) MERGE {
+                recovery_codes: $recovery_codes,
+                updated_at: time::now()
+            } WHERE admin_user_id=$admin_user_id;";
+
+        let vars: BTreeMap<String, Value> = [
+            ("recovery_codes".into(), hashed_recovery_codes.into()),
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), ADMIN_USER_TWO_FACTOR_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+
+        if result_object.is_ok() {
+            return Ok(true);
+        }
+
+        Err(Error::Generic(format!("issue while updating recovery codes for admin user: {admin_user_id}")))
+    }
+
+    /// Only moves the step forward, so two requests with the same code cannot both
+    /// succeed. Returns false when a code of this or a later step was already used.
+    pub async fn update_last_used_step(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        admin_user_id: &str,
+        time_step: i64,
+    ) -> Result<bool> {
+        let sql = "
+            UPDATE type::table($table) MERGE {
+                last_used_step: $time_step,
+                updated_at: time::now()
+            } WHERE admin_user_id=$admin_user_id AND (last_used_step ?? 0) < $time_step;";
+
+        let vars: BTreeMap<String, Value> = [
+            ("time_step".into(), time_step.into()),
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), ADMIN_USER_TWO_FACTOR_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+
+        match result_object_option {
+            Some(result_object) => Ok(result_object.is_ok()),
+            None => Ok(false)
+        }
+    }
+
+    pub async fn delete_by_admin_user_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        admin_user_id: &str,
+    ) -> Result<bool> {
+        let sql = "
+            DELETE type::table($table) WHERE admin_user_id=$admin_user_id;
+            UPDATE type::thing($admin_user_table, $admin_user_id) MERGE {
+                is_two_factor_enabled: false,
+                updated_at: time::now()
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), ADMIN_USER_TWO_FACTOR_TABLE.into()),
+            ("admin_user_table".into(), "admin_users".into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let response = responses
+            .into_iter()
+            .next_back()
+            .map(|rp| rp.output());
+        let query_result = match response {
+            Some(object) => object.is_ok(),
+            None => false
+        };
+
+        Ok(query_result)
+    }
+}
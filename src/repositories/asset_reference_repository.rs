@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::Value;
+use crate::error::Result;
+use crate::models::asset_reference_model::{AssetReferenceModel, AssetReferenceSource, CreatableAssetReferenceModel};
+use crate::repositories::into_iter_objects;
+
+const ASSET_REFERENCE_TABLE: &str = "asset_references";
+
+#[derive(Clone)]
+pub struct AssetReferenceRepository {}
+
+impl AssetReferenceRepository {
+    pub fn new() -> Self {
+        AssetReferenceRepository {}
+    }
+
+    pub async fn all_by_asset_ids(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        asset_ids: Vec<String>,
+    ) -> Result<Vec<AssetReferenceModel>> {
+        let sql = "SELECT * FROM type::table($table) WHERE asset_id IN $asset_ids ORDER BY source_type, source_name;";
+        let vars: BTreeMap<String, Value> = [
+            ("asset_ids".into(), asset_ids.into()),
+            ("table".into(), ASSET_REFERENCE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut asset_reference_list: Vec<AssetReferenceModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let asset_reference_model: Result<AssetReferenceModel> = object?.try_into();
+            asset_reference_list.push(asset_reference_model?);
+        }
+
+        Ok(asset_reference_list)
+    }
+
+    /// Swaps the references of the source for the given ones in one transaction.
+    pub async fn replace_for_source(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        source_type: AssetReferenceSource,
+        source_id: &str,
+        source_name: &str,
+        creatable_asset_references: Vec<CreatableAssetReferenceModel>,
+    ) -> Result<()> {
+        let sql = "
+            BEGIN TRANSACTION;
+            DELETE type::table($table) WHERE source_type = $source_type AND source_id = $source_id;
+            FOR $reference IN $references {
+                CREATE type::table($table) CONTENT {
+                    asset_id: $reference.asset_id,
+                    source_type: $source_type,
+                    source_id: $source_id,
+                    source_name: $source_name,
+                    field: $reference.field,
+                    created_at: time::now()
+                };
+            };
+            COMMIT TRANSACTION;";
+
+        let references: Vec<Value> = creatable_asset_references
+            .into_iter()
+            .map(|creatable_asset_reference| {
+                let reference: BTreeMap<String, Value> = [
+                    ("asset_id".into(), creatable_asset_reference.asset_id.into()),
+                    ("field".into(), creatable_asset_reference.field.into()),
+                ].into();
+
+                reference.into()
+            })
+            .collect();
+        let vars: BTreeMap<String, Value> = [
+            ("source_type".into(), source_type.as_str().into()),
+            ("source_id".into(), source_id.into()),
+            ("source_name".into(), source_name.into()),
+            ("references".into(), references.into()),
+            ("table".into(), ASSET_REFERENCE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+        for response in responses {
+            response.result?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete_by_source(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        source_type: AssetReferenceSource,
+        source_id: &str,
+    ) -> Result<()> {
+        let sql = "DELETE type::table($table) WHERE source_type = $source_type AND source_id = $source_id;";
+        let vars: BTreeMap<String, Value> = [
+            ("source_type".into(), source_type.as_str().into()),
+            ("source_id".into(), source_id.into()),
+            ("table".into(), ASSET_REFERENCE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+        for response in responses {
+            response.result?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete_by_asset_ids(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        asset_ids: Vec<String>,
+    ) -> Result<()> {
+        let sql = "DELETE type::table($table) WHERE asset_id IN $asset_ids;";
+        let vars: BTreeMap<String, Value> = [
+            ("asset_ids".into(), asset_ids.into()),
+            ("table".into(), ASSET_REFERENCE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+        for response in responses {
+            response.result?;
+        }
+
+        Ok(())
+    }
+}
@@ -4,12 +4,13 @@ use surrealdb::kvs::Datastore;
 use surrealdb::sql::{Datetime, Value};
 
 use crate::error::{Error, Result};
-use crate::models::asset_model::{CreatableAssetModelNew, NewAssetModel};
+use crate::models::asset_model::{AssetSearchModel, CreatableAssetModelNew, NewAssetModel, UpdatableAssetModel};
 use crate::models::ModelCount;
 use crate::PER_PAGE;
 
 use super::into_iter_objects;
 const ASSET_TABLE: &str = "assets";
+const ASSET_REFERENCE_TABLE: &str = "asset_references";
 
 #[derive(Clone)]
 pub struct AssetRepository {}
@@ -54,6 +55,30 @@ impl AssetRepository {
         Ok(asset_list)
     }
 
+    pub async fn all_by_parent_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        parent_id: String
+    ) -> Result<Vec<NewAssetModel>> {
+        let sql = "SELECT * FROM type::table($table) WHERE parent_id=$parent_id;";
+        let vars = BTreeMap::from([
+            ("table".into(), ASSET_TABLE.into()),
+            ("parent_id".into(), parent_id.into()),
+        ]);
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut asset_list: Vec<NewAssetModel> = Vec::new();
+
+        for object in into_iter_objects(responses)? {
+            let asset_object = object?;
+
+            let asset_model: Result<NewAssetModel> = asset_object.try_into();
+            asset_list.push(asset_model?);
+        }
+        Ok(asset_list)
+    }
+
     pub async fn get_total_count(
         &self,
         datastore: &Datastore,
@@ -95,6 +120,16 @@ impl AssetRepository {
             ("parent_id".into(), creatable_asset_model.parent_id.into()),
             ("asset_type".into(), creatable_asset_model.asset_type.into()),
             ("metadata".into(), metadata.into()),
+            ("original_name".into(), creatable_asset_model.original_name.into()),
+            ("file_size".into(), creatable_asset_model.file_size.into()),
+            ("width".into(), creatable_asset_model.width.into()),
+            ("height".into(), creatable_asset_model.height.into()),
+            ("checksum".into(), creatable_asset_model.checksum.into()),
+            ("alt_text".into(), "".into()),
+            ("caption".into(), "".into()),
+            ("tags".into(), Vec::<String>::new().into()),
+            ("is_private".into(), false.into()),
+            ("crops".into(), Vec::<Value>::new().into()),
             ("created_by".into(), creatable_asset_model.logged_in_username.clone().into(),),
             ("updated_by".into(), creatable_asset_model.logged_in_username.into(),),
             ("created_at".into(), Datetime::default().into()),
@@ -171,7 +206,7 @@ impl AssetRepository {
         let result_object_option = into_iter_objects(responses)?.next();
         let result_object = match result_object_option {
             Some(object) => object,
-            None => Err(Error::Generic("no record found".to_string())),
+            None => Err(Error::NotFound(format!("no asset found with id {asset_id}"))),
         };
 
         let asset_model: Result<NewAssetModel> = result_object?.try_into();
@@ -207,28 +242,117 @@ impl AssetRepository {
         Ok(query_result)
     }
 
-    pub async fn update_asset_path(
+    /// Updates the location of every asset in one transaction, nothing changes when one of
+    /// them no longer exists.
+    pub async fn update_asset_locations(
         &self,
         datastore: &Datastore,
         database_session: &Session,
-        name: &str,
-        new_path: &str,
-        asset_id: &str,
+        asset_models: &[NewAssetModel],
+        logged_in_username: &str
+    ) -> Result<()> {
+        let sql = "
+            BEGIN TRANSACTION;
+            LET $found = (SELECT VALUE id FROM type::table($table) WHERE meta::id(id) IN $ids);
+            IF array::len($found) != array::len($ids) { THROW 'some of the assets no longer exist' };
+            FOR $asset IN $assets {
+                UPDATE type::thing($table, $asset.id) MERGE {
+                    parent_id: $asset.parent_id,
+                    path: $asset.path,
+                    name: $asset.name,
+                    updated_by: $logged_in_user_name,
+                    updated_at: time::now()
+                };
+            };
+            COMMIT TRANSACTION;";
+
+        let asset_ids: Vec<String> = asset_models.iter().map(|asset_model| asset_model.id.clone()).collect();
+        let assets: Vec<Value> = asset_models
+            .iter()
+            .map(|asset_model| {
+                let location: BTreeMap<String, Value> = [
+                    ("id".into(), asset_model.id.as_str().into()),
+                    ("parent_id".into(), asset_model.parent_id.as_str().into()),
+                    ("path".into(), asset_model.path.as_str().into()),
+                    ("name".into(), asset_model.name.as_str().into()),
+                ].into();
+
+                location.into()
+            })
+            .collect();
+        let vars: BTreeMap<String, Value> = [
+            ("ids".into(), asset_ids.into()),
+            ("assets".into(), assets.into()),
+            ("logged_in_user_name".into(), logged_in_username.into()),
+            ("table".into(), ASSET_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+        for response in responses {
+            response.result?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn update_asset(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        updatable_asset_model: UpdatableAssetModel,
+    ) -> Result<NewAssetModel> {
+        let sql = "
+            UPDATE type::thing($table, $id) MERGE {
+                alt_text: $alt_text,
+                caption: $caption,
+                tags: $tags,
+                focal_point: $focal_point,
+                crops: $crops,
+                updated_by: $logged_in_user_name,
+                updated_at: time::now()
+            };";
+
+        let vars = BTreeMap::from([
+            ("alt_text".into(), updatable_asset_model.alt_text.into()),
+            ("caption".into(), updatable_asset_model.caption.into()),
+            ("tags".into(), updatable_asset_model.tags.into()),
+            ("focal_point".into(), updatable_asset_model.focal_point.map_or(Value::None, Value::from)),
+            ("crops".into(), updatable_asset_model.crops.into_iter().map(Value::from).collect::<Vec<Value>>().into()),
+            ("logged_in_user_name".into(), updatable_asset_model.logged_in_username.into()),
+            ("id".into(), updatable_asset_model.id.into()),
+            ("table".into(), ASSET_TABLE.into()),
+        ]);
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+        let asset_model: Result<NewAssetModel> = result_object?.try_into();
+
+        asset_model
+    }
+
+    pub async fn update_visibility(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        id: &str,
+        is_private: bool,
         logged_in_username: &str
     ) -> Result<NewAssetModel> {
         let sql = "
             UPDATE type::thing($table, $id) MERGE {
-                path: $path,
-                name: $name,
+                is_private: $is_private,
                 updated_by: $logged_in_user_name,
                 updated_at: time::now()
             };";
 
         let vars = BTreeMap::from([
-            ("path".into(), new_path.into()),
-            ("name".into(), name.into()),
+            ("is_private".into(), is_private.into()),
             ("logged_in_user_name".into(), logged_in_username.into()),
-            ("id".into(), asset_id.into()),
+            ("id".into(), id.into()),
             ("table".into(), ASSET_TABLE.into()),
         ]);
         let responses = datastore.execute(sql, database_session, Some(vars)).await?;
@@ -242,4 +366,288 @@ impl AssetRepository {
 
         asset_model
     }
+
+    /// Deletes all the records in one statement, so either all or none of them are gone.
+    /// Deletes the assets with their references in one transaction, nothing is deleted when
+    /// one of them no longer exists.
+    pub async fn delete_by_ids(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        asset_ids: Vec<String>,
+    ) -> Result<bool> {
+        let sql = "
+            BEGIN TRANSACTION;
+            LET $found = (SELECT VALUE id FROM type::table($table) WHERE meta::id(id) IN $ids);
+            IF array::len($found) != array::len($ids) { THROW 'some of the assets no longer exist' };
+            DELETE type::table($table) WHERE meta::id(id) IN $ids;
+            DELETE type::table($reference_table) WHERE asset_id IN $ids;
+            COMMIT TRANSACTION;";
+        let vars: BTreeMap<String, Value> = [
+            ("ids".into(), asset_ids.into()),
+            ("table".into(), ASSET_TABLE.into()),
+            ("reference_table".into(), ASSET_REFERENCE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+        for response in responses {
+            response.result?;
+        }
+
+        Ok(true)
+    }
+
+    pub async fn update_tags_by_ids(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        asset_ids: Vec<String>,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+        logged_in_username: &str
+    ) -> Result<Vec<NewAssetModel>> {
+        let sql = "
+            BEGIN TRANSACTION;
+            LET $found = (SELECT VALUE id FROM type::table($table) WHERE meta::id(id) IN $ids);
+            IF array::len($found) != array::len($ids) { THROW 'some of the assets no longer exist' };
+            UPDATE type::table($table) SET
+                tags = array::union(array::complement(tags ?? [], $remove_tags), $add_tags),
+                updated_by = $logged_in_user_name,
+                updated_at = time::now()
+            WHERE meta::id(id) IN $ids;
+            COMMIT TRANSACTION;";
+        let vars: BTreeMap<String, Value> = [
+            ("ids".into(), asset_ids.into()),
+            ("add_tags".into(), add_tags.into()),
+            ("remove_tags".into(), remove_tags.into()),
+            ("logged_in_user_name".into(), logged_in_username.into()),
+            ("table".into(), ASSET_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        // the update is the third statement, after the existence check
+        let mut asset_list: Vec<NewAssetModel> = Vec::new();
+        for object in into_iter_objects(responses.into_iter().skip(2).collect())? {
+            let asset_model: Result<NewAssetModel> = object?.try_into();
+            asset_list.push(asset_model?);
+        }
+
+        Ok(asset_list)
+    }
+
+    /// Files stored under one of the keys or with one of the ids.
+    pub async fn all_files_by_paths_or_ids(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        paths: Vec<String>,
+        asset_ids: Vec<String>,
+    ) -> Result<Vec<NewAssetModel>> {
+        let sql = "
+            SELECT * FROM type::table($table)
+            WHERE asset_type = 'FILE' AND (path IN $paths OR meta::id(id) IN $ids);";
+        let vars: BTreeMap<String, Value> = [
+            ("paths".into(), paths.into()),
+            ("ids".into(), asset_ids.into()),
+            ("table".into(), ASSET_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut asset_list: Vec<NewAssetModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let asset_model: Result<NewAssetModel> = object?.try_into();
+            asset_list.push(asset_model?);
+        }
+
+        Ok(asset_list)
+    }
+
+    pub async fn all_by_checksum(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        checksum: &str,
+    ) -> Result<Vec<NewAssetModel>> {
+        let sql = "
+            SELECT * FROM type::table($table)
+            WHERE asset_type = 'FILE' AND checksum = $checksum
+            ORDER BY created_at;";
+        let vars: BTreeMap<String, Value> = [
+            ("checksum".into(), checksum.into()),
+            ("table".into(), ASSET_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut asset_list: Vec<NewAssetModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let asset_model: Result<NewAssetModel> = object?.try_into();
+            asset_list.push(asset_model?);
+        }
+
+        Ok(asset_list)
+    }
+
+    /// Files sharing their checksum with at least one other file, ordered by checksum.
+    pub async fn all_duplicates(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+    ) -> Result<Vec<NewAssetModel>> {
+        let sql = "
+            LET $checksums = (
+                SELECT VALUE checksum FROM (
+                    SELECT checksum, count() AS total FROM type::table($table)
+                    WHERE asset_type = 'FILE' AND checksum != NONE AND checksum != ''
+                    GROUP BY checksum
+                ) WHERE total > 1
+            );
+            SELECT * FROM type::table($table)
+            WHERE asset_type = 'FILE' AND checksum IN $checksums
+            ORDER BY checksum, created_at;";
+        let vars: BTreeMap<String, Value> = [
+            ("table".into(), ASSET_TABLE.into()),
+        ].into();
+
+        let mut responses = datastore.execute(sql, database_session, Some(vars)).await?;
+        // the first response belongs to the LET statement
+        let responses = responses.split_off(1);
+
+        let mut asset_list: Vec<NewAssetModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let asset_model: Result<NewAssetModel> = object?.try_into();
+            asset_list.push(asset_model?);
+        }
+
+        Ok(asset_list)
+    }
+
+    /// Assets in all folders matching the search, restricted to the folders when given.
+    pub async fn search(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        search: &AssetSearchModel,
+        folders: Option<&[NewAssetModel]>,
+        start: i64,
+    ) -> Result<Vec<NewAssetModel>> {
+        let (conditions, mut vars) = search_conditions(search, folders);
+        // the order is checked against the searchable columns by the request
+        let sql = format!("\
+            SELECT * \
+            FROM type::table($table) \
+            WHERE {} \
+            ORDER {} {} \
+            LIMIT $limit \
+            START $start;\
+        ", conditions, search.order_column, search.order_type);
+        vars.insert("limit".into(), PER_PAGE.into());
+        vars.insert("start".into(), start.into());
+
+        let responses = datastore.execute(&sql, database_session, Some(vars)).await?;
+
+        let mut asset_list: Vec<NewAssetModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let asset_model: Result<NewAssetModel> = object?.try_into();
+            asset_list.push(asset_model?);
+        }
+
+        Ok(asset_list)
+    }
+
+    pub async fn search_count(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        search: &AssetSearchModel,
+        folders: Option<&[NewAssetModel]>,
+    ) -> Result<ModelCount> {
+        let (conditions, vars) = search_conditions(search, folders);
+        let sql = format!("SELECT count() FROM type::table($table) WHERE {} GROUP ALL;", conditions);
+
+        let responses = datastore.execute(&sql, database_session, Some(vars)).await?;
+
+        match into_iter_objects(responses)?.next() {
+            Some(object) => object?.try_into(),
+            None => Ok(ModelCount::default()),
+        }
+    }
+}
+
+/// The where clause of the search, every value is passed as a variable.
+fn search_conditions(search: &AssetSearchModel, folders: Option<&[NewAssetModel]>) -> (String, BTreeMap<String, Value>) {
+    let mut conditions: Vec<String> = vec![];
+    let mut vars: BTreeMap<String, Value> = [
+        ("table".into(), ASSET_TABLE.into()),
+    ].into();
+
+    if let Some(query) = &search.query {
+        conditions.push(String::from("(\
+            string::contains(string::lowercase(name), $query) \
+            OR string::contains(string::lowercase(original_name ?? ''), $query) \
+            OR string::contains(string::lowercase(alt_text ?? ''), $query) \
+            OR string::contains(string::lowercase(caption ?? ''), $query)\
+        )"));
+        vars.insert("query".into(), query.clone().into());
+    }
+    if let Some(tag) = &search.tag {
+        conditions.push(String::from("$tag IN (tags ?? [])"));
+        vars.insert("tag".into(), tag.clone().into());
+    }
+    if let Some(file_type) = &search.file_type {
+        match file_type.strip_suffix('*') {
+            Some(file_type_group) => {
+                conditions.push(String::from("string::starts_with(metadata.file_type ?? '', $file_type)"));
+                vars.insert("file_type".into(), file_type_group.into());
+            },
+            None => {
+                conditions.push(String::from("metadata.file_type = $file_type"));
+                vars.insert("file_type".into(), file_type.clone().into());
+            },
+        }
+    }
+    if let Some(created_from) = &search.created_from {
+        conditions.push(String::from("created_at >= $created_from"));
+        vars.insert("created_from".into(), created_from.clone().into());
+    }
+    if let Some(created_to) = &search.created_to {
+        conditions.push(String::from("created_at <= $created_to"));
+        vars.insert("created_to".into(), created_to.clone().into());
+    }
+    if let Some(created_by) = &search.created_by {
+        conditions.push(String::from("created_by = $created_by"));
+        vars.insert("created_by".into(), created_by.clone().into());
+    }
+
+    let dimensions = [
+        ("width >= $min_width", "min_width", search.min_width),
+        ("width <= $max_width", "max_width", search.max_width),
+        ("height >= $min_height", "min_height", search.min_height),
+        ("height <= $max_height", "max_height", search.max_height),
+    ];
+    for (condition, name, value) in dimensions {
+        if let Some(value) = value {
+            conditions.push(String::from(condition));
+            vars.insert(name.into(), value.into());
+        }
+    }
+
+    if let Some(folders) = folders {
+        let mut folder_conditions = vec![String::from("meta::id(id) IN $folder_ids")];
+        let folder_ids: Vec<Value> = folders.iter().map(|folder| folder.id.clone().into()).collect();
+        vars.insert("folder_ids".into(), folder_ids.into());
+        for (index, folder) in folders.iter().enumerate() {
+            folder_conditions.push(format!("string::starts_with(path, $folder_path_{index})"));
+            vars.insert(format!("folder_path_{index}"), format!("{}/", folder.path).into());
+        }
+        conditions.push(format!("({})", folder_conditions.join(" OR ")));
+    }
+
+    if conditions.is_empty() {
+        return (String::from("true"), vars);
+    }
+
+    (conditions.join(" AND "), vars)
 }
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::cms_token_model::{CmsTokenModel, CreatableCmsTokenModel};
+use crate::repositories::into_iter_objects;
+
+const CMS_TOKEN_TABLE: &str = "cms_tokens";
+
+#[derive(Clone)]
+pub struct CmsTokenRepository {}
+
+impl CmsTokenRepository {
+    pub fn new() -> Self {
+        CmsTokenRepository {}
+    }
+
+    pub async fn all(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+    ) -> Result<Vec<CmsTokenModel>> {
+        let sql = "SELECT * FROM type::table($table) ORDER BY created_at DESC;";
+        let vars: BTreeMap<String, Value> = [("table".into(), CMS_TOKEN_TABLE.into())].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut cms_token_list: Vec<CmsTokenModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let cms_token_model: Result<CmsTokenModel> = object?.try_into();
+            cms_token_list.push(cms_token_model?);
+        }
+
+        Ok(cms_token_list)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        cms_token_id: &str,
+    ) -> Result<CmsTokenModel> {
+        let sql = "SELECT * FROM type::thing($table, $id);";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), cms_token_id.into()),
+            ("table".into(), CMS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no cms token found with id {cms_token_id}"))),
+        };
+        let cms_token_model: Result<CmsTokenModel> = result_object?.try_into();
+
+        cms_token_model
+    }
+
+    pub async fn find_by_token_hash(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        token_hash: &str,
+    ) -> Result<CmsTokenModel> {
+        let sql = "SELECT * FROM type::table($table) WHERE token_hash=$token_hash;";
+        let vars: BTreeMap<String, Value> = [
+            ("token_hash".into(), token_hash.into()),
+            ("table".into(), CMS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(String::from("no cms token found"))),
+        };
+        let cms_token_model: Result<CmsTokenModel> = result_object?.try_into();
+
+        cms_token_model
+    }
+
+    pub async fn create_cms_token(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_cms_token_model: CreatableCmsTokenModel,
+    ) -> Result<CmsTokenModel> {
+        let sql = "CREATE type::table($table) CONTENT $data;";
+
+        let expires_at: Value = match creatable_cms_token_model.expires_at {
+            Some(expires_at) => expires_at.into(),
+            None => Value::None,
+        };
+        let data: BTreeMap<String, Value> = [
+            ("name".into(), creatable_cms_token_model.name.into()),
+            ("token_prefix".into(), creatable_cms_token_model.token_prefix.into()),
+            ("token_hash".into(), creatable_cms_token_model.token_hash.into()),
+            ("scopes".into(), creatable_cms_token_model.scopes.into()),
+            ("allowed_origins".into(), creatable_cms_token_model.allowed_origins.into()),
+            ("expires_at".into(), expires_at),
+            ("created_by".into(), creatable_cms_token_model.logged_in_username.clone().into()),
+            ("updated_by".into(), creatable_cms_token_model.logged_in_username.into()),
+            ("created_at".into(), Datetime::default().into()),
+            ("updated_at".into(), Datetime::default().into()),
+        ].into();
+
+        let vars: BTreeMap<String, Value> = [
+            ("table".into(), CMS_TOKEN_TABLE.into()),
+            ("data".into(), data.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create cms token".to_string())),
+        };
+        let cms_token_model: Result<CmsTokenModel> = result_object?.try_into();
+
+        cms_token_model
+    }
+
+    pub async fn update_token_hash(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        cms_token_id: &str,
+        token_prefix: String,
+        token_hash: String,
+        logged_in_username: String,
+    ) -> Result<CmsTokenModel> {
+        let sql = "
+            UPDATE type::thing($table, $id) MERGE {
+                token_prefix: $token_prefix,
+                token_hash: $token_hash,
+                last_used_at: NONE,
+                updated_by: $logged_in_username,
+                updated_at: time::now()
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), cms_token_id.into()),
+            ("token_prefix".into(), token_prefix.into()),
+            ("token_hash".into(), token_hash.into()),
+            ("logged_in_username".into(), logged_in_username.into()),
+            ("table".into(), CMS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no cms token found with id {cms_token_id}"))),
+        };
+        let cms_token_model: Result<CmsTokenModel> = result_object?.try_into();
+
+        cms_token_model
+    }
+
+    pub async fn update_last_used_at(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        cms_token_id: &str,
+    ) -> Result<bool> {
+        let sql = "UPDATE type::thing($table, $id) MERGE { last_used_at: time::now() };";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), cms_token_id.into()),
+            ("table".into(), CMS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let response = responses
+            .into_iter()
+            .next()
+            .map(|rp| rp.output());
+        let query_result = match response {
+            Some(object) => object.is_ok(),
+            None => false
+        };
+
+        Ok(query_result)
+    }
+
+    pub async fn delete_by_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        cms_token_id: &str,
+    ) -> Result<bool> {
+        let sql = "DELETE type::thing($table, $id);";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), cms_token_id.into()),
+            ("table".into(), CMS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let response = responses
+            .into_iter()
+            .next()
+            .map(|rp| rp.output());
+        let query_result = match response {
+            Some(object) => object.is_ok(),
+            None => false
+        };
+
+        Ok(query_result)
+    }
+}
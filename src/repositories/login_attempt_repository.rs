@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::login_attempt_model::{LoginAttemptAction, LoginAttemptModel};
+use crate::models::ModelCount;
+use crate::repositories::into_iter_objects;
+
+const LOGIN_ATTEMPT_TABLE: &str = "login_attempts";
+
+#[derive(Clone)]
+pub struct LoginAttemptRepository {}
+
+impl LoginAttemptRepository {
+    pub fn new() -> Self {
+        LoginAttemptRepository {}
+    }
+
+    /// Attempts of the same action which are already outside the window are removed
+    /// while recording, so the table does not keep growing.
+    pub async fn create_login_attempt(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        action: LoginAttemptAction,
+        key: &str,
+        window_seconds: i64,
+    ) -> Result<bool> {
+        let sql = "
+            DELETE type::table($table) WHERE action=$action AND created_at < time::now() - $window;
+            CREATE type::table($table) CONTENT $data;";
+
+        let data: BTreeMap<String, Value> = [
+            ("action".into(), action.as_str().into()),
+            ("key".into(), key.into()),
+            ("created_at".into(), Datetime::default().into()),
+        ].into();
+
+        let vars: BTreeMap<String, Value> = [
+            ("action".into(), action.as_str().into()),
+            ("window".into(), Duration::from_secs(window_seconds as u64).into()),
+            ("table".into(), LOGIN_ATTEMPT_TABLE.into()),
+            ("data".into(), data.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses.into_iter().skip(1).collect())?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create login attempt record".to_string())),
+        };
+
+        Ok(result_object.is_ok())
+    }
+
+    pub async fn count_login_attempts(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        action: LoginAttemptAction,
+        key: &str,
+        window_seconds: i64,
+    ) -> Result<ModelCount> {
+        let sql = "
+            SELECT count() FROM type::table($table)
+            WHERE action=$action AND key=$key AND created_at > time::now() - $window
+            GROUP ALL;";
+
+        let vars: BTreeMap<String, Value> = [
+            ("action".into(), action.as_str().into()),
+            ("key".into(), key.into()),
+            ("window".into(), Duration::from_secs(window_seconds as u64).into()),
+            ("table".into(), LOGIN_ATTEMPT_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        // GROUP ALL returns no row at all when nothing matched
+        match into_iter_objects(responses)?.next() {
+            Some(object) => object?.try_into(),
+            None => Ok(ModelCount::default()),
+        }
+    }
+
+    pub async fn find_oldest_login_attempt(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        action: LoginAttemptAction,
+        key: &str,
+        window_seconds: i64,
+    ) -> Result<LoginAttemptModel> {
+        let sql = "
+            SELECT * FROM type::table($table)
+            WHERE action=$action AND key=$key AND created_at > time::now() - $window
+            ORDER BY created_at ASC
+            LIMIT 1;";
+
+        let vars: BTreeMap<String, Value> = [
+            ("action".into(), action.as_str().into()),
+            ("key".into(), key.into()),
+            ("window".into(), Duration::from_secs(window_seconds as u64).into()),
+            ("table".into(), LOGIN_ATTEMPT_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(format!("no login attempt found for key {key}"))),
+        };
+        let login_attempt_model: Result<LoginAttemptModel> = result_object?.try_into();
+
+        login_attempt_model
+    }
+
+    pub async fn delete_login_attempts(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        action: LoginAttemptAction,
+        key: &str,
+    ) -> Result<bool> {
+        let sql = "DELETE type::table($table) WHERE action=$action AND key=$key;";
+
+        let vars: BTreeMap<String, Value> = [
+            ("action".into(), action.as_str().into()),
+            ("key".into(), key.into()),
+            ("table".into(), LOGIN_ATTEMPT_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let response = responses
+            .into_iter()
+            .next()
+            .map(|rp| rp.output());
+        let query_result = match response {
+            Some(object) => object.is_ok(),
+            None => false
+        };
+
+        Ok(query_result)
+    }
+}
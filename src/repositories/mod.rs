@@ -11,6 +11,15 @@ pub mod role_repository;
 pub mod asset_repository;
 pub mod setting_repository;
 pub mod model_repository;
+pub mod admin_user_two_factor_repository;
+pub mod login_attempt_repository;
+pub mod admin_user_lockout_repository;
+pub mod cms_token_repository;
+pub mod personal_access_token_repository;
+pub mod oidc_login_state_repository;
+pub mod admin_user_identity_repository;
+pub mod admin_user_invitation_repository;
+pub mod asset_reference_repository;
 
 pub fn into_iter_objects(responses: Vec<Response>) -> Result<impl Iterator<Item = Result<Object>>> {
     let response = responses
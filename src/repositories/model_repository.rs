@@ -49,6 +49,23 @@ impl ModelRepository {
         created_model
     }
 
+    pub async fn all(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+    ) -> Result<Vec<ModelModel>> {
+        let sql = "SELECT * FROM models;";
+        let responses = datastore.execute(sql, database_session, None).await?;
+
+        let mut model_list: Vec<ModelModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let model_model: Result<ModelModel> = object?.try_into();
+            model_list.push(model_model?);
+        }
+
+        Ok(model_list)
+    }
+
     pub async fn get_total_count(
         &self,
         datastore: &Datastore,
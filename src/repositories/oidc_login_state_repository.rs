@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::oidc_login_state_model::{CreatableOidcLoginStateModel, OidcLoginStateModel};
+use crate::repositories::into_iter_objects;
+
+const OIDC_LOGIN_STATE_TABLE: &str = "oidc_login_states";
+
+#[derive(Clone)]
+pub struct OidcLoginStateRepository {}
+
+impl OidcLoginStateRepository {
+    pub fn new() -> Self {
+        OidcLoginStateRepository {}
+    }
+
+    /// Also removes the states of logins that were never finished.
+    pub async fn create_login_state(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_login_state_model: CreatableOidcLoginStateModel,
+    ) -> Result<OidcLoginStateModel> {
+        let sql = "
+            DELETE type::table($table) WHERE expires_at < time::now();
+            CREATE type::table($table) CONTENT {
+                state: $state,
+                nonce: $nonce,
+                code_verifier: $code_verifier,
+                expires_at: time::now() + $expires_in,
+                created_at: $created_at
+            };";
+
+        let vars: BTreeMap<String, Value> = [
+            ("state".into(), creatable_login_state_model.state.into()),
+            ("nonce".into(), creatable_login_state_model.nonce.into()),
+            ("code_verifier".into(), creatable_login_state_model.code_verifier.into()),
+            ("expires_in".into(), Duration::from_secs(creatable_login_state_model.expires_in_seconds as u64).into()),
+            ("created_at".into(), Datetime::default().into()),
+            ("table".into(), OIDC_LOGIN_STATE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses.into_iter().skip(1).collect())?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create oidc login state".to_string())),
+        };
+        let login_state_model: Result<OidcLoginStateModel> = result_object?.try_into();
+
+        login_state_model
+    }
+
+    /// Deletes the state while reading it, so a callback can never be replayed.
+    pub async fn take_by_state(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        state: &str,
+    ) -> Result<OidcLoginStateModel> {
+        let sql = "DELETE type::table($table) WHERE state=$state RETURN BEFORE;";
+        let vars: BTreeMap<String, Value> = [
+            ("state".into(), state.into()),
+            ("table".into(), OIDC_LOGIN_STATE_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound("no oidc login state found".to_string())),
+        };
+        let login_state_model: Result<OidcLoginStateModel> = result_object?.try_into();
+
+        login_state_model
+    }
+}
@@ -52,6 +52,30 @@ impl PageRepository {
         Ok(page_list)
     }
 
+    pub async fn all_ordered(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        order_column: String,
+        order_type: String,
+    ) -> Result<Vec<NewPageModel>> {
+        let sql = format!("SELECT * FROM type::table($table) ORDER {} {};", order_column, order_type);
+        let vars = BTreeMap::from([
+            ("table".into(), PAGE_TABLE.into()),
+        ]);
+        let responses = datastore.execute(&sql, database_session, Some(vars)).await?;
+
+        let mut page_list: Vec<NewPageModel> = Vec::new();
+
+        for object in into_iter_objects(responses)? {
+            let page_object = object?;
+
+            let page_model: Result<NewPageModel> = page_object.try_into();
+            page_list.push(page_model?);
+        }
+        Ok(page_list)
+    }
+
     pub async fn get_total_count(
         &self,
         datastore: &Datastore,
@@ -282,6 +306,7 @@ impl PageRepository {
             ("created_by".into(), creatable_page_model.logged_in_username.clone().into()),
             ("updated_by".into(), creatable_page_model.logged_in_username.into()),
             ("page_fields".into(), page_fields.into()),
+            ("locked_role_ids".into(), creatable_page_model.locked_role_ids.into()),
             ("created_at".into(), Datetime::default().into()),
             ("updated_at".into(), Datetime::default().into()),
         ]
@@ -391,6 +416,7 @@ impl PageRepository {
             ("updated_by".into(), updatable_page_model.logged_in_username.clone().into()),
             ("created_by".into(), updatable_page_model.created_by.into()),
             ("page_fields".into(), page_fields.into()),
+            ("locked_role_ids".into(), updatable_page_model.locked_role_ids.into()),
             ("updated_at".into(), Datetime::default().into()),
             ("created_at".into(), updatable_page_model.created_at.into()),
         ]
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 use surrealdb::dbs::Session;
 use surrealdb::kvs::Datastore;
 use surrealdb::sql::{Datetime, Value};
@@ -21,18 +22,25 @@ impl PasswordResetRepository {
         datastore: &Datastore,
         database_session: &Session,
         creatable_password_reset_model: CreatablePasswordResetModel,
+        expires_in: Duration
     ) -> crate::error::Result<PasswordResetModel> {
-        let sql = "CREATE password_reset CONTENT $data";
+        let sql = "
+            CREATE type::table($table) CONTENT {
+                email: $email,
+                token_hash: $token_hash,
+                status: 'Active',
+                expires_at: time::now() + $expires_in,
+                created_at: $now
+            };";
 
-        let data: BTreeMap<String, Value> = [
+        let vars: BTreeMap<String, Value> = [
             ("email".into(), creatable_password_reset_model.email.into()),
-            ("token".into(), creatable_password_reset_model.token.into()),
-            ("status".into(), "Active".into()),
-            ("created_at".into(), Datetime::default().into()),
+            ("token_hash".into(), creatable_password_reset_model.token_hash.into()),
+            ("expires_in".into(), expires_in.into()),
+            ("now".into(), Datetime::default().into()),
+            ("table".into(), PASSWORD_RESET_TABLE.into()),
         ].into();
 
-        let vars: BTreeMap<String, Value> = [("data".into(), data.into())].into();
-
         let responses = datastore.execute(sql, database_session, Some(vars)).await?;
 
         let result_object_option = into_iter_objects(responses)?.next();
@@ -50,11 +58,11 @@ impl PasswordResetRepository {
         datastore: &Datastore,
         database_session: &Session,
         email: String,
-        token: String,
+        token_hash: String,
     ) -> crate::error::Result<PasswordResetModel> {
-        let sql = "SELECT * FROM type::table($table) WHERE email=$email and token=$password_token";
+        let sql = "SELECT * FROM type::table($table) WHERE email=$email and token_hash=$token_hash";
         let vars: BTreeMap<String, Value> = [
-            ("password_token".into(), token.clone().into()),
+            ("token_hash".into(), token_hash.into()),
             ("email".into(), email.clone().into()),
             ("table".into(), PASSWORD_RESET_TABLE.into()),
         ].into();
@@ -71,35 +79,30 @@ impl PasswordResetRepository {
         password_reset_model
     }
 
-    pub async fn expire_password_token_by_email_and_token(
+    pub async fn expire_password_tokens_by_email(
         &self,
         datastore: &Datastore,
         database_session: &Session,
-        email: String,
-        token: String
+        email: String
     ) -> crate::error::Result<bool> {
         let sql = "
-            UPDATE type::table($table) SET status=$status WHERE email=$email and token=$password_token";
+            UPDATE type::table($table) SET status=$status WHERE email=$email and status='Active'";
 
         let vars = BTreeMap::from([
             ("status".into(), "Expire".into()),
             ("email".into(), email.clone().into()),
-            ("password_token".into(), token.into()),
             ("table".into(), PASSWORD_RESET_TABLE.into()),
         ]);
 
         let responses = datastore.execute(sql, database_session, Some(vars)).await?;
 
-        let result_object_option = into_iter_objects(responses)?.next();
-        let result_object = match result_object_option {
-            Some(object) => object,
-            None => Err(Error::Generic("no record found".to_string())),
-        };
-
-        if result_object.is_ok() {
-            return Ok(true);
+        let response = responses
+            .into_iter()
+            .next()
+            .map(|rp| rp.output());
+        match response {
+            Some(Ok(_)) => Ok(true),
+            _ => Err(Error::Generic(format!("issue while expiring password reset tokens by email: {email}")))
         }
-
-        Err(Error::Generic(format!("issue while updating password by email: {email}")))
     }
 }
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Datetime, Value};
+use crate::error::{Error, Result};
+use crate::models::personal_access_token_model::{CreatablePersonalAccessTokenModel, PersonalAccessTokenModel};
+use crate::repositories::into_iter_objects;
+
+const PERSONAL_ACCESS_TOKEN_TABLE: &str = "personal_access_tokens";
+
+#[derive(Clone)]
+pub struct PersonalAccessTokenRepository {}
+
+impl PersonalAccessTokenRepository {
+    pub fn new() -> Self {
+        PersonalAccessTokenRepository {}
+    }
+
+    pub async fn all_by_admin_user_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        admin_user_id: &str,
+    ) -> Result<Vec<PersonalAccessTokenModel>> {
+        let sql = "SELECT * FROM type::table($table) WHERE admin_user_id=$admin_user_id ORDER BY created_at DESC;";
+        let vars: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), PERSONAL_ACCESS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let mut personal_access_token_list: Vec<PersonalAccessTokenModel> = Vec::new();
+        for object in into_iter_objects(responses)? {
+            let personal_access_token_model: Result<PersonalAccessTokenModel> = object?.try_into();
+            personal_access_token_list.push(personal_access_token_model?);
+        }
+
+        Ok(personal_access_token_list)
+    }
+
+    pub async fn find_by_token_hash(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        token_hash: &str,
+    ) -> Result<PersonalAccessTokenModel> {
+        let sql = "SELECT * FROM type::table($table) WHERE token_hash=$token_hash;";
+        let vars: BTreeMap<String, Value> = [
+            ("token_hash".into(), token_hash.into()),
+            ("table".into(), PERSONAL_ACCESS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::NotFound(String::from("no personal access token found"))),
+        };
+        let personal_access_token_model: Result<PersonalAccessTokenModel> = result_object?.try_into();
+
+        personal_access_token_model
+    }
+
+    pub async fn create_personal_access_token(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        creatable_personal_access_token_model: CreatablePersonalAccessTokenModel,
+    ) -> Result<PersonalAccessTokenModel> {
+        let sql = "CREATE type::table($table) CONTENT $data;";
+
+        let data: BTreeMap<String, Value> = [
+            ("admin_user_id".into(), creatable_personal_access_token_model.admin_user_id.into()),
+            ("name".into(), creatable_personal_access_token_model.name.into()),
+            ("token_prefix".into(), creatable_personal_access_token_model.token_prefix.into()),
+            ("token_hash".into(), creatable_personal_access_token_model.token_hash.into()),
+            ("permissions".into(), creatable_personal_access_token_model.permissions.into()),
+            ("expires_at".into(), creatable_personal_access_token_model.expires_at.into()),
+            ("created_at".into(), Datetime::default().into()),
+            ("updated_at".into(), Datetime::default().into()),
+        ].into();
+
+        let vars: BTreeMap<String, Value> = [
+            ("table".into(), PERSONAL_ACCESS_TOKEN_TABLE.into()),
+            ("data".into(), data.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::CreateModel("cannot create personal access token".to_string())),
+        };
+        let personal_access_token_model: Result<PersonalAccessTokenModel> = result_object?.try_into();
+
+        personal_access_token_model
+    }
+
+    pub async fn update_last_used_at(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        personal_access_token_id: &str,
+    ) -> Result<bool> {
+        let sql = "UPDATE type::thing($table, $id) MERGE { last_used_at: time::now() };";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), personal_access_token_id.into()),
+            ("table".into(), PERSONAL_ACCESS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let response = responses
+            .into_iter()
+            .next()
+            .map(|rp| rp.output());
+        let query_result = match response {
+            Some(object) => object.is_ok(),
+            None => false
+        };
+
+        Ok(query_result)
+    }
+
+    /// Only deletes the token when it belongs to the given admin user.
+    pub async fn delete_by_id_and_admin_user_id(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        personal_access_token_id: &str,
+        admin_user_id: &str,
+    ) -> Result<bool> {
+        let sql = "DELETE type::thing($table, $id) WHERE admin_user_id=$admin_user_id RETURN BEFORE;";
+        let vars: BTreeMap<String, Value> = [
+            ("id".into(), personal_access_token_id.into()),
+            ("admin_user_id".into(), admin_user_id.into()),
+            ("table".into(), PERSONAL_ACCESS_TOKEN_TABLE.into()),
+        ].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        Ok(into_iter_objects(responses)?.next().is_some())
+    }
+}
@@ -136,6 +136,9 @@ impl RoleRepository {
             ("name".into(), createable_role_model.name.into()),
             ("identifier".into(), createable_role_model.identifier.into()),
             ("permissions".into(), createable_role_model.permissions.into()),
+            ("own_pages_only".into(), createable_role_model.own_pages_only.into()),
+            ("page_identifier_prefixes".into(), createable_role_model.page_identifier_prefixes.into()),
+            ("asset_folder_ids".into(), createable_role_model.asset_folder_ids.into()),
             ("created_by".into(), createable_role_model.logged_in_username.clone().into()),
             ("updated_by".into(), createable_role_model.logged_in_username.into()),
             ("created_at".into(), Datetime::default().into()),
@@ -192,12 +195,18 @@ impl RoleRepository {
                 name: $name,
                 updated_by: $logged_in_user_name,
                 updated_at: time::now(),
-                permissions: $permissions
+                permissions: $permissions,
+                own_pages_only: $own_pages_only,
+                page_identifier_prefixes: $page_identifier_prefixes,
+                asset_folder_ids: $asset_folder_ids
             };";
 
         let vars = BTreeMap::from([
             ("name".into(), updatable_admin_user.name.into()),
             ("permissions".into(), updatable_admin_user.permissions.into()),
+            ("own_pages_only".into(), updatable_admin_user.own_pages_only.into()),
+            ("page_identifier_prefixes".into(), updatable_admin_user.page_identifier_prefixes.into()),
+            ("asset_folder_ids".into(), updatable_admin_user.asset_folder_ids.into()),
             ("logged_in_user_name".into(), updatable_admin_user.logged_in_username.into()),
             ("id".into(), updatable_admin_user.id.into()),
             ("table".into(), "roles".into()),
@@ -214,6 +223,33 @@ impl RoleRepository {
         role_model
     }
 
+    /// Only replaces the permissions, the role keeps its updated by and updated at.
+    pub async fn update_role_permissions(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        id: String,
+        permissions: Vec<String>,
+    ) -> Result<RoleModel> {
+        let sql = "UPDATE type::thing($table, $id) MERGE { permissions: $permissions };";
+
+        let vars = BTreeMap::from([
+            ("permissions".into(), permissions.into()),
+            ("id".into(), id.into()),
+            ("table".into(), "roles".into()),
+        ]);
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let result_object_option = into_iter_objects(responses)?.next();
+        let result_object = match result_object_option {
+            Some(object) => object,
+            None => Err(Error::Generic("no record found".to_string())),
+        };
+        let role_model: Result<RoleModel> = result_object?.try_into();
+
+        role_model
+    }
+
     pub async fn get_total_count(
         &self,
         datastore: &Datastore,
@@ -90,4 +90,27 @@ impl SettingRepository {
 
     }
 
+    pub async fn delete_by_identifier(
+        &self,
+        datastore: &Datastore,
+        database_session: &Session,
+        identifier: &str
+    ) -> crate::error::Result<bool> {
+        let sql = "DELETE settings WHERE identifier=$identifier;";
+        let vars: BTreeMap<String, Value> = [("identifier".into(), identifier.into())].into();
+
+        let responses = datastore.execute(sql, database_session, Some(vars)).await?;
+
+        let response = responses
+            .into_iter()
+            .next()
+            .map(|rp| rp.output());
+        let query_result = match response {
+            Some(object) => object.is_ok(),
+            None => false
+        };
+
+        Ok(query_result)
+    }
+
 }
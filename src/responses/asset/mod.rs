@@ -0,0 +1,25 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use crate::models::asset_model::NewAssetModel;
+use crate::responses::ApiResponse;
+use crate::error::Result;
+
+#[derive(Serialize)]
+pub struct FetchAssetCmsResponse {
+    pub asset_model: NewAssetModel,
+    /// Rendition urls of the named crops of images, keyed by crop name.
+    pub crop_urls: BTreeMap<String, String>,
+}
+
+impl NewAssetModel {
+    pub fn convert_to_response(&self, crop_urls: BTreeMap<String, String>) -> Result<ApiResponse<FetchAssetCmsResponse>>
+    {
+        Ok(ApiResponse {
+            status: true,
+            data: FetchAssetCmsResponse {
+                asset_model: self.to_owned(),
+                crop_urls,
+            }
+        })
+    }
+}
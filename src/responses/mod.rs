@@ -1,11 +1,15 @@
 use serde::Serialize;
+use utoipa::ToSchema;
+use crate::models::admin_user_two_factor_model::TwoFactorEnrolmentModel;
 
 pub mod role;
 pub mod page;
 pub mod component;
 pub mod model;
+pub mod asset;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[aliases(TwoFactorEnrolmentResponse = ApiResponse<TwoFactorEnrolmentModel>)]
 pub struct ApiResponse<R> {
     pub status: bool,
     pub data: R
@@ -0,0 +1,269 @@
+use std::time::Duration;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use lettre::{AsyncTransport, Message};
+use lettre::message::{header, MultiPart, SinglePart};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use crate::api::handlers::admin_user_invitation::store_admin_user_invitation_api_handler::AdminUserInvitationViewModel;
+use crate::error::{Error, Result};
+use crate::models::admin_user_invitation_model::{AdminUserInvitationClaims, AdminUserInvitationModel, CreatableAdminUserInvitationModel, IssuedAdminUserInvitationModel};
+use crate::models::admin_user_model::{AdminUserModel, CreatableAdminUserModel};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+use crate::providers::avored_database_provider::DB;
+use crate::providers::avored_template_provider::AvoRedTemplateProvider;
+use crate::repositories::admin_user_invitation_repository::AdminUserInvitationRepository;
+use crate::repositories::admin_user_repository::AdminUserRepository;
+
+const ADMIN_USER_INVITATION_PURPOSE: &str = "admin_user_invitation";
+const ADMIN_USER_INVITATION_JTI_LENGTH: usize = 32;
+
+pub struct AdminUserInvitationService {
+    admin_user_invitation_repository: AdminUserInvitationRepository,
+    admin_user_repository: AdminUserRepository,
+    jwt_secret_key: String,
+    expire_in_hours: i64,
+}
+
+impl AdminUserInvitationService {
+    pub fn new(
+        config: &AvoRedConfigProvider,
+        admin_user_invitation_repository: AdminUserInvitationRepository,
+        admin_user_repository: AdminUserRepository
+    ) -> Result<Self> {
+        Ok(AdminUserInvitationService {
+            admin_user_invitation_repository,
+            admin_user_repository,
+            jwt_secret_key: config.jwt_secret_key.clone(),
+            expire_in_hours: config.admin_user_invitation_expire_in_hours,
+        })
+    }
+}
+
+impl AdminUserInvitationService {
+    pub async fn all(&self, (datastore, database_session): &DB) -> Result<Vec<AdminUserInvitationModel>> {
+        self.admin_user_invitation_repository
+            .all(datastore, database_session)
+            .await
+    }
+
+    pub async fn count_of_email(&self, (datastore, database_session): &DB, email: String) -> Result<i64> {
+        let model_count = self.admin_user_invitation_repository
+            .count_of_email(datastore, database_session, email)
+            .await?;
+
+        Ok(model_count.total)
+    }
+
+    pub async fn create_invitation(
+        &self,
+        (datastore, database_session): &DB,
+        creatable_invitation_model: CreatableAdminUserInvitationModel
+    ) -> Result<IssuedAdminUserInvitationModel> {
+        let jti = Alphanumeric.sample_string(&mut rand::thread_rng(), ADMIN_USER_INVITATION_JTI_LENGTH);
+        let invitation = self.admin_user_invitation_repository
+            .create_invitation(
+                datastore,
+                database_session,
+                creatable_invitation_model,
+                hash_invitation_jti(&jti),
+                self.expires_in()
+            )
+            .await?;
+        let token = self.sign_invitation_token(&invitation.id, jti)?;
+
+        Ok(IssuedAdminUserInvitationModel { token, invitation })
+    }
+
+    /// Issues a new link with a fresh expiry, the link sent before stops working.
+    pub async fn reissue_invitation(
+        &self,
+        (datastore, database_session): &DB,
+        invitation_id: &str,
+        logged_in_username: &str
+    ) -> Result<IssuedAdminUserInvitationModel> {
+        self.admin_user_invitation_repository
+            .find_by_id(datastore, database_session, invitation_id)
+            .await?;
+
+        let jti = Alphanumeric.sample_string(&mut rand::thread_rng(), ADMIN_USER_INVITATION_JTI_LENGTH);
+        let invitation = self.admin_user_invitation_repository
+            .refresh_token(
+                datastore,
+                database_session,
+                invitation_id,
+                hash_invitation_jti(&jti),
+                self.expires_in(),
+                logged_in_username
+            )
+            .await?;
+        let token = self.sign_invitation_token(&invitation.id, jti)?;
+
+        Ok(IssuedAdminUserInvitationModel { token, invitation })
+    }
+
+    pub async fn revoke_invitation(
+        &self,
+        (datastore, database_session): &DB,
+        invitation_id: &str
+    ) -> Result<bool> {
+        let is_deleted = self.admin_user_invitation_repository
+            .delete_by_id(datastore, database_session, invitation_id)
+            .await?;
+
+        if !is_deleted {
+            return Err(Error::NotFound(format!("no admin user invitation found with id {invitation_id}")));
+        }
+
+        Ok(true)
+    }
+
+    pub async fn send_invitation_email(
+        &self,
+        template: &AvoRedTemplateProvider,
+        react_admin_url: &str,
+        issued_invitation: &IssuedAdminUserInvitationModel
+    ) -> Result<bool> {
+        let from_address = String::from("info@avored.com");
+        let email_subject = "You have been invited to AvoRed";
+
+        let data = AdminUserInvitationViewModel {
+            link: format!("{react_admin_url}/admin/accept-invitation/{}", issued_invitation.token),
+            hours: self.expire_in_hours
+        };
+        let invitation_email_content = template.handlebars.render("admin-user-invitation", &data)?;
+
+        let message = Message::builder()
+            .from(from_address.parse()?)
+            .to(issued_invitation.invitation.email.parse()?)
+            .subject(email_subject)
+            .multipart(
+                MultiPart::alternative()
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(header::ContentType::TEXT_HTML)
+                            .body(invitation_email_content),
+                    ),
+            )?;
+
+        match template.mailer.send(message).await {
+            Ok(_) => Ok(true),
+            Err(_) => Err(Error::Generic(String::from("error while sending an email"))),
+        }
+    }
+
+    /// Checks the signature and expiry of the link and that it is the latest one sent.
+    pub async fn find_by_token(
+        &self,
+        (datastore, database_session): &DB,
+        token: &str
+    ) -> Result<AdminUserInvitationModel> {
+        let claims = self.decode_invitation_token(token)?;
+        let invitation = match self.admin_user_invitation_repository
+            .find_by_id_and_token_hash(datastore, database_session, &claims.sub, &hash_invitation_jti(&claims.jti))
+            .await {
+            Ok(invitation) => invitation,
+            Err(Error::NotFound(_)) => return Err(Error::Authentication),
+            Err(e) => return Err(e)
+        };
+        if invitation.is_expired() {
+            return Err(Error::Authentication);
+        }
+
+        Ok(invitation)
+    }
+
+    /// Creates the admin user with the roles of the invitation. The invitation is removed in
+    /// the same step, so the link can not be used twice.
+    pub async fn accept_invitation(
+        &self,
+        (datastore, database_session): &DB,
+        token: &str,
+        full_name: String,
+        password_hash: String
+    ) -> Result<AdminUserModel> {
+        let claims = self.decode_invitation_token(token)?;
+        let invitation = match self.admin_user_invitation_repository
+            .take_by_id_and_token_hash(datastore, database_session, &claims.sub, &hash_invitation_jti(&claims.jti))
+            .await {
+            Ok(invitation) => invitation,
+            Err(Error::NotFound(_)) => return Err(Error::Authentication),
+            Err(e) => return Err(e)
+        };
+        if invitation.is_expired() {
+            return Err(Error::Authentication);
+        }
+
+        let creatable_admin_user_model = CreatableAdminUserModel {
+            full_name,
+            email: invitation.email.clone(),
+            password: password_hash,
+            profile_image: String::from(""),
+            is_super_admin: false,
+            logged_in_username: invitation.created_by.clone(),
+            role_ids: invitation.role_ids.clone(),
+        };
+        let admin_user_model = self.admin_user_repository
+            .create_admin_user(datastore, database_session, creatable_admin_user_model)
+            .await?;
+
+        let logged_in_user = LoggedInUser {
+            id: admin_user_model.id.clone(),
+            name: admin_user_model.full_name.clone(),
+            email: invitation.created_by,
+            demo_data_status: false,
+            admin_user_model: admin_user_model.clone(),
+            token_permissions: None
+        };
+        for role_id in invitation.role_ids {
+            self.admin_user_repository
+                .attach_admin_user_with_role(datastore, database_session, admin_user_model.id.clone(), role_id, logged_in_user.clone())
+                .await?;
+        }
+
+        self.admin_user_repository
+            .find_by_id(datastore, database_session, admin_user_model.id)
+            .await
+    }
+
+    fn expires_in(&self) -> Duration {
+        Duration::from_secs((self.expire_in_hours * 60 * 60) as u64)
+    }
+
+    fn sign_invitation_token(&self, invitation_id: &str, jti: String) -> Result<String> {
+        let now = chrono::Utc::now();
+        let claims = AdminUserInvitationClaims {
+            sub: invitation_id.to_string(),
+            jti,
+            purpose: String::from(ADMIN_USER_INVITATION_PURPOSE),
+            iat: now.timestamp() as usize,
+            exp: (now + chrono::Duration::hours(self.expire_in_hours)).timestamp() as usize,
+        };
+
+        Ok(encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret_key.as_ref()),
+        )?)
+    }
+
+    fn decode_invitation_token(&self, token: &str) -> Result<AdminUserInvitationClaims> {
+        let claims = decode::<AdminUserInvitationClaims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret_key.as_ref()),
+            &Validation::default(),
+        )
+            .map_err(|_| Error::Authentication)?
+            .claims;
+
+        if claims.purpose != ADMIN_USER_INVITATION_PURPOSE {
+            return Err(Error::Authentication);
+        }
+
+        Ok(claims)
+    }
+}
+
+fn hash_invitation_jti(jti: &str) -> String {
+    hex::encode(Sha256::digest(jti.as_bytes()))
+}
@@ -1,8 +1,12 @@
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
-use argon2::password_hash::SaltString;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use lettre::{AsyncTransport, Message};
 use lettre::message::{header, MultiPart, SinglePart};
 use rand::distributions::{Alphanumeric, DistString};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, Secret, TOTP};
 use crate::{
     error::Result,
     models::{
@@ -18,59 +22,84 @@ use crate::{
 use crate::api::handlers::admin_user::admin_user_forgot_password_api_handler::ForgotPasswordViewModel;
 use crate::error::Error;
 use crate::models::admin_user_model::CreatableAdminUserModel;
+use crate::models::admin_user_two_factor_model::{AdminUserTwoFactorModel, CreatableAdminUserTwoFactorModel, TwoFactorEnrolmentModel};
 use crate::models::ModelCount;
 use crate::models::password_rest_model::{CreatablePasswordResetModel, PasswordResetModel};
-use crate::models::token_claim_model::LoggedInUser;
+use crate::models::token_claim_model::{LoggedInUser, TokenClaims, TwoFactorChallengeClaims};
 use crate::providers::avored_template_provider::AvoRedTemplateProvider;
+use crate::repositories::admin_user_two_factor_repository::AdminUserTwoFactorRepository;
 use crate::repositories::password_reset_repository::PasswordResetRepository;
 use crate::repositories::role_repository::RoleRepository;
 
+const TWO_FACTOR_ISSUER: &str = "AvoRed";
+const TWO_FACTOR_CHALLENGE_PURPOSE: &str = "two_factor";
+const TWO_FACTOR_CHALLENGE_EXPIRE_IN_MINUTES: i64 = 5;
+const TWO_FACTOR_RECOVERY_CODE_COUNT: usize = 10;
+
 pub struct AdminUserService {
     admin_user_repository: AdminUserRepository,
     role_repository: RoleRepository,
-    password_reset_repository: PasswordResetRepository
+    password_reset_repository: PasswordResetRepository,
+    admin_user_two_factor_repository: AdminUserTwoFactorRepository
 }
 
 impl AdminUserService {
     pub fn new(
         admin_user_repository: AdminUserRepository,
         role_repository: RoleRepository,
-        password_reset_repository: PasswordResetRepository
+        password_reset_repository: PasswordResetRepository,
+        admin_user_two_factor_repository: AdminUserTwoFactorRepository
     ) -> Result<Self> {
         Ok(AdminUserService {
             admin_user_repository,
             role_repository,
-            password_reset_repository
+            password_reset_repository,
+            admin_user_two_factor_repository
         })
     }
 }
 impl AdminUserService {
+    /// Nothing is sent when the email does not belong to an admin user.
     pub async fn sent_forgot_password_email(
         &self,
         (datastore, database_session): &DB,
         template: &AvoRedTemplateProvider,
         react_admin_url: &str,
-        to_address: String
+        to_address: String,
+        expire_in_minutes: i64
     ) -> Result<bool>
     {
         let from_address = String::from("info@avored.com");
         let email_subject = "Forgot your password?";
 
-        let token = Alphanumeric.sample_string(&mut rand::thread_rng(), 22);
+        let admin_user_count = self.admin_user_repository
+            .count_of_email(datastore, database_session, to_address.clone())
+            .await?;
+        if admin_user_count.total == 0 {
+            return Ok(false);
+        }
+
+        let token = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
 
         let creatable_password_reset_model = CreatablePasswordResetModel {
             email: to_address.clone(),
-            token,
+            token_hash: hash_password_reset_token(&token),
         };
 
-        //@todo before creating a token make sure expire any past token that could have been generated?
-
-        let password_reset_model = self
-            .password_reset_repository
-            .create_password_reset(datastore, database_session, creatable_password_reset_model)
+        // only the latest link can be used
+        self.password_reset_repository
+            .expire_password_tokens_by_email(datastore, database_session, to_address.clone())
+            .await?;
+        self.password_reset_repository
+            .create_password_reset(
+                datastore,
+                database_session,
+                creatable_password_reset_model,
+                Duration::from_secs((expire_in_minutes * 60) as u64)
+            )
             .await?;
 
-        let link = format!("{react_admin_url}/admin/reset-password/{}", password_reset_model.token);
+        let link = format!("{react_admin_url}/admin/reset-password/{token}");
         let data = ForgotPasswordViewModel {
             link
         };
@@ -111,26 +140,6 @@ impl AdminUserService {
         Ok(argon2.verify_password(plain_password.as_bytes(), &parsed_hash).is_ok())
     }
 
-    pub async fn has_permission(
-        &self,
-        logged_in_user: LoggedInUser,
-        permission_identifier: String
-    ) -> Result<bool> {
-        if logged_in_user.admin_user_model.is_super_admin {
-            return Ok(true)
-        }
-        let mut has_permission = false;
-        for role in logged_in_user.admin_user_model.roles {
-            for permission in role.permissions {
-                if permission == permission_identifier {
-                    has_permission = true;
-                }
-            }
-        }
-
-        Ok(has_permission)
-    }
-
     pub async fn find_by_email(
         &self,
         (datastore, database_session): &DB,
@@ -151,6 +160,53 @@ impl AdminUserService {
             .await
     }
 
+    pub async fn update_profile_image(
+        &self,
+        (datastore, database_session): &DB,
+        id: String,
+        profile_image: String,
+        logged_in_username: String
+    ) -> Result<AdminUserModel> {
+        self.admin_user_repository
+            .update_profile_image(datastore, database_session, id, profile_image, logged_in_username)
+            .await
+    }
+
+    /// Runs on startup. Profile images stored as a path below the public folder become urls
+    /// of the back end app.
+    pub async fn upgrade_legacy_profile_images(
+        &self,
+        (datastore, database_session): &DB,
+        back_end_app_url: &str
+    ) -> Result<()> {
+        let url_prefix = format!("{}/public/", back_end_app_url.trim_end_matches('/'));
+
+        self.admin_user_repository
+            .prefix_relative_profile_images(datastore, database_session, &url_prefix)
+            .await
+    }
+
+    pub async fn update_active_status(
+        &self,
+        (datastore, database_session): &DB,
+        id: String,
+        is_active: bool,
+        logged_in_username: String
+    ) -> Result<AdminUserModel> {
+        self.admin_user_repository
+            .update_active_status(datastore, database_session, id, is_active, logged_in_username)
+            .await
+    }
+
+    pub async fn count_of_active_super_admins(
+        &self,
+        (datastore, database_session): &DB,
+    ) -> Result<ModelCount> {
+        self.admin_user_repository
+            .count_of_active_super_admins(datastore, database_session)
+            .await
+    }
+
     pub async fn get_password_reset_by_email(
         &self,
         (datastore, database_session): &DB,
@@ -158,30 +214,26 @@ impl AdminUserService {
         token: String
     ) -> Result<PasswordResetModel> {
         self.password_reset_repository
-            .get_password_reset_by_email(datastore, database_session, email, token)
+            .get_password_reset_by_email(datastore, database_session, email, hash_password_reset_token(&token))
             .await
     }
 
+    /// Any pending password reset link stops working once the password is changed.
     pub async fn update_password_by_email(
         &self,
         (datastore, database_session): &DB,
         new_password: String,
         email: String
     ) -> Result<bool> {
-        self.admin_user_repository
-            .update_password_by_email(datastore, database_session, new_password, email)
-            .await
-    }
+        let update_status = self.admin_user_repository
+            .update_password_by_email(datastore, database_session, new_password, email.clone())
+            .await?;
 
-    pub async fn expire_password_token_by_email_and_token(
-        &self,
-        (datastore, database_session): &DB,
-        email: String,
-        token: String
-    ) -> Result<bool> {
         self.password_reset_repository
-            .expire_password_token_by_email_and_token(datastore, database_session, email, token)
-            .await
+            .expire_password_tokens_by_email(datastore, database_session, email)
+            .await?;
+
+        Ok(update_status)
     }
 
     pub async fn update_admin_user(
@@ -297,29 +349,247 @@ impl AdminUserService {
         Ok(admin_user_model)
     }
 
-    pub fn get_password_hash_from_raw_password(
+    pub async fn count_of_email(
+        &self,
+        (datastore, database_session): &DB,
+        email: String,
+    ) -> Result<ModelCount> {
+        self.admin_user_repository
+            .count_of_email(datastore, database_session, email)
+            .await
+    }
+}
+
+impl AdminUserService {
+    pub fn create_login_token(
         &self,
-        raw_password: String,
-        password_salt: &String
-    ) -> Result<String>{
+        admin_user_model: AdminUserModel,
+        jwt_secret_key: &str
+    ) -> Result<String> {
+        // covers the two factor and single sign-on logins as well
+        if !admin_user_model.is_active {
+            return Err(Error::AccountDeactivated);
+        }
 
-        let password = raw_password.as_bytes();
-        let salt = SaltString::from_b64(password_salt)?;
+        let now = chrono::Utc::now();
+        let iat = now.timestamp() as usize;
+        let exp = (now + chrono::Duration::minutes(60)).timestamp() as usize;
+        let claims: TokenClaims = TokenClaims {
+            sub: admin_user_model.clone().id,
+            name: admin_user_model.clone().full_name,
+            email: admin_user_model.clone().email,
+            admin_user_model,
+            exp,
+            iat,
+        };
 
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password, &salt)?.to_string();
+        Ok(encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(jwt_secret_key.as_ref()),
+        )?)
+    }
+
+    /// The challenge token only proves that the password was correct. It does not carry the
+    /// admin user model, so it can never be decoded as [`TokenClaims`] by the jwt middleware.
+    pub fn create_two_factor_challenge_token(
+        &self,
+        admin_user_id: String,
+        jwt_secret_key: &str
+    ) -> Result<String> {
+        let now = chrono::Utc::now();
+        let iat = now.timestamp() as usize;
+        let exp = (now + chrono::Duration::minutes(TWO_FACTOR_CHALLENGE_EXPIRE_IN_MINUTES)).timestamp() as usize;
+        let claims = TwoFactorChallengeClaims {
+            sub: admin_user_id,
+            purpose: String::from(TWO_FACTOR_CHALLENGE_PURPOSE),
+            iat,
+            exp,
+        };
 
-        Ok(password_hash)
+        Ok(encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(jwt_secret_key.as_ref()),
+        )?)
     }
 
-    pub async fn count_of_email(
+    /// Returns the admin user id the challenge token was issued for.
+    pub fn decode_two_factor_challenge_token(
+        &self,
+        challenge_token: &str,
+        jwt_secret_key: &str
+    ) -> Result<String> {
+        let claims = decode::<TwoFactorChallengeClaims>(
+            challenge_token,
+            &DecodingKey::from_secret(jwt_secret_key.as_ref()),
+            &Validation::default(),
+        )?.claims;
+
+        if claims.purpose != TWO_FACTOR_CHALLENGE_PURPOSE {
+            return Err(Error::Authentication);
+        }
+
+        Ok(claims.sub)
+    }
+
+    pub async fn find_two_factor_by_admin_user_id(
         &self,
         (datastore, database_session): &DB,
-        email: String,
-    ) -> Result<ModelCount> {
-        self.admin_user_repository
-            .count_of_email(datastore, database_session, email)
+        admin_user_id: &str
+    ) -> Result<AdminUserTwoFactorModel> {
+        self.admin_user_two_factor_repository
+            .find_by_admin_user_id(datastore, database_session, admin_user_id)
             .await
     }
+
+    /// Generates a new secret for the admin user. The secret stays pending until the first code
+    /// is confirmed, so an abandoned enrolment never locks the admin user out.
+    pub async fn generate_two_factor_secret(
+        &self,
+        (datastore, database_session): &DB,
+        admin_user_model: &AdminUserModel
+    ) -> Result<TwoFactorEnrolmentModel> {
+        let mut secret_bytes = [0u8; 20];
+        rand::thread_rng().fill_bytes(&mut secret_bytes);
+        let secret = Secret::Raw(secret_bytes.to_vec()).to_encoded().to_string();
+
+        let creatable_two_factor_model = CreatableAdminUserTwoFactorModel {
+            admin_user_id: admin_user_model.id.clone(),
+            secret: secret.clone(),
+        };
+        self.admin_user_two_factor_repository
+            .create_pending_two_factor(datastore, database_session, creatable_two_factor_model)
+            .await?;
+
+        let totp = self.get_totp(&secret, &admin_user_model.email)?;
+
+        Ok(TwoFactorEnrolmentModel {
+            secret,
+            otpauth_uri: totp.get_url(),
+        })
+    }
+
+    /// A totp code is accepted once. The time step of an accepted code is stored and
+    /// codes of that step or an earlier one are rejected, even inside the skew window.
+    pub async fn check_two_factor_code(
+        &self,
+        (datastore, database_session): &DB,
+        two_factor_model: &AdminUserTwoFactorModel,
+        account_name: &str,
+        code: &str
+    ) -> Result<bool> {
+        let totp = self.get_totp(&two_factor_model.secret, account_name)?;
+        let current_step = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() / totp.step;
+        let skew = u64::from(totp.skew);
+
+        let matched_step = (current_step.saturating_sub(skew)..=current_step + skew)
+            .find(|time_step| totp.generate(time_step * totp.step) == code.trim());
+        let time_step = match matched_step.and_then(|time_step| i64::try_from(time_step).ok()) {
+            Some(time_step) if time_step > two_factor_model.last_used_step => time_step,
+            _ => return Ok(false)
+        };
+
+        self.admin_user_two_factor_repository
+            .update_last_used_step(datastore, database_session, &two_factor_model.admin_user_id, time_step)
+            .await
+    }
+
+    /// Marks the pending secret as enabled and returns the plain recovery codes.
+    /// Only the hashes are stored, so this is the one time the codes can be shown.
+    pub async fn enable_two_factor(
+        &self,
+        (datastore, database_session): &DB,
+        admin_user_id: &str
+    ) -> Result<Vec<String>> {
+        let recovery_codes = self.generate_recovery_codes();
+        let hashed_recovery_codes = recovery_codes
+            .iter()
+            .map(|recovery_code| self.hash_recovery_code(recovery_code))
+            .collect();
+
+        self.admin_user_two_factor_repository
+            .enable_two_factor(datastore, database_session, admin_user_id, hashed_recovery_codes)
+            .await?;
+
+        Ok(recovery_codes)
+    }
+
+    /// Accepts either a current totp code or an unused recovery code.
+    /// A recovery code is removed as soon as it has been used.
+    pub async fn verify_two_factor_login(
+        &self,
+        db: &DB,
+        two_factor_model: &AdminUserTwoFactorModel,
+        account_name: &str,
+        code: &str
+    ) -> Result<bool> {
+        if self.check_two_factor_code(db, two_factor_model, account_name, code).await? {
+            return Ok(true);
+        }
+
+        let (datastore, database_session) = db;
+
+        let hashed_code = self.hash_recovery_code(code);
+        if !two_factor_model.recovery_codes.contains(&hashed_code) {
+            return Ok(false);
+        }
+
+        let remaining_recovery_codes = two_factor_model
+            .recovery_codes
+            .iter()
+            .filter(|recovery_code| **recovery_code != hashed_code)
+            .cloned()
+            .collect();
+
+        self.admin_user_two_factor_repository
+            .update_recovery_codes(datastore, database_session, &two_factor_model.admin_user_id, remaining_recovery_codes)
+            .await
+    }
+
+    pub async fn disable_two_factor(
+        &self,
+        (datastore, database_session): &DB,
+        admin_user_id: &str
+    ) -> Result<bool> {
+        self.admin_user_two_factor_repository
+            .delete_by_admin_user_id(datastore, database_session, admin_user_id)
+            .await
+    }
+
+    fn get_totp(&self, secret: &str, account_name: &str) -> Result<TOTP> {
+        let secret_bytes = Secret::Encoded(secret.to_string()).to_bytes()?;
+
+        Ok(TOTP::new(
+            Algorithm::SHA1,
+            6,
+            1,
+            30,
+            secret_bytes,
+            Some(String::from(TWO_FACTOR_ISSUER)),
+            account_name.to_string(),
+        )?)
+    }
+
+    fn generate_recovery_codes(&self) -> Vec<String> {
+        (0..TWO_FACTOR_RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let code = Alphanumeric
+                    .sample_string(&mut rand::thread_rng(), 10)
+                    .to_lowercase();
+                format!("{}-{}", &code[..5], &code[5..])
+            })
+            .collect()
+    }
+
+    fn hash_recovery_code(&self, recovery_code: &str) -> String {
+        hex::encode(Sha256::digest(recovery_code.trim().to_lowercase().as_bytes()))
+    }
+}
+
+fn hash_password_reset_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
 }
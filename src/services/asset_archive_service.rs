@@ -0,0 +1,268 @@
+use std::io::{Cursor, Read, Seek, Write};
+use std::sync::Arc;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rust_i18n::t;
+use tokio::runtime::Handle;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use crate::error::{Error, Result};
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+use crate::providers::avored_storage_provider::AssetStorage;
+use crate::services::asset_upload_service::MULTIPART_OVERHEAD_BYTES;
+
+/// Entries larger than this have to compress less than `MAX_COMPRESSION_RATIO`.
+const COMPRESSION_RATIO_MIN_SIZE: u64 = 1024 * 1024;
+const MAX_COMPRESSION_RATIO: u64 = 100;
+/// Junk added by the archivers of some operating systems, skipped on upload.
+const IGNORED_ENTRY_NAMES: [&str; 3] = ["__MACOSX", ".DS_Store", "Thumbs.db"];
+
+/// A file or folder of an uploaded archive, the path is relative and checked.
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    pub path: Vec<String>,
+    /// `None` for folders.
+    pub data: Option<Vec<u8>>,
+}
+
+/// A stored file to put into a downloaded archive.
+#[derive(Debug, Clone)]
+pub struct ArchiveFile {
+    pub entry_name: String,
+    pub key: String,
+    pub is_private: bool,
+}
+
+pub struct AssetArchiveService {
+    max_size_kb: usize,
+    max_entries: usize,
+    max_uncompressed_kb: usize,
+    storage: Arc<dyn AssetStorage>,
+    private_storage: Arc<dyn AssetStorage>,
+}
+
+impl AssetArchiveService {
+    pub fn new(
+        config: &AvoRedConfigProvider,
+        storage: Arc<dyn AssetStorage>,
+        private_storage: Arc<dyn AssetStorage>
+    ) -> Result<Self> {
+        Ok(AssetArchiveService {
+            max_size_kb: config.asset_archive_max_size_kb,
+            max_entries: config.asset_archive_max_entries,
+            max_uncompressed_kb: config.asset_archive_max_uncompressed_kb,
+            storage,
+            private_storage,
+        })
+    }
+}
+
+impl AssetArchiveService {
+    /// Body limit of the archive upload route.
+    pub fn max_request_size(&self) -> usize {
+        self.max_size_kb * 1024 + MULTIPART_OVERHEAD_BYTES
+    }
+
+    /// Unpacks the archive in memory. The sizes in the archive are not trusted, every entry is
+    /// read up to what is left of the uncompressed limit.
+    pub async fn extract(&self, data: Vec<u8>) -> Result<Vec<ArchiveEntry>> {
+        let max_entries = self.max_entries;
+        let max_uncompressed_size = self.max_uncompressed_kb as u64 * 1024;
+
+        tokio::task::spawn_blocking(move || extract_entries(data, max_entries, max_uncompressed_size))
+            .await
+            .map_err(|e| Error::Generic(format!("the archive could not be unpacked: {e}")))?
+    }
+
+    /// Fails for folders which add up to more than an uploaded archive may unpack to.
+    pub fn validate_download_size(&self, total_size: i64) -> Result<()> {
+        if total_size > (self.max_uncompressed_kb * 1024) as i64 {
+            return Err(archive_error(t!("asset_archive_too_large", size = self.max_uncompressed_kb).to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Writes the stored files into a zip under their entry names. The zip is spooled to an
+    /// unlinked temporary file, so it is gone as soon as the returned file is dropped.
+    pub async fn write_archive(&self, files: Vec<ArchiveFile>) -> Result<tokio::fs::File> {
+        let storage = self.storage.clone();
+        let private_storage = self.private_storage.clone();
+        let handle = Handle::current();
+
+        let file = tokio::task::spawn_blocking(move || -> Result<std::fs::File> {
+            let suffix: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(16)
+                .map(char::from)
+                .collect();
+            let spool_path = std::env::temp_dir().join(format!("avored-archive-{suffix}.zip"));
+            let spool_file = std::fs::File::options()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&spool_path)?;
+            std::fs::remove_file(&spool_path)?;
+
+            let mut spool_file = write_entries(spool_file, files, |file| match file.is_private {
+                true => handle.block_on(private_storage.get(&file.key)),
+                false => handle.block_on(storage.get(&file.key)),
+            })?;
+            spool_file.rewind()?;
+
+            Ok(spool_file)
+        })
+            .await
+            .map_err(|e| Error::Generic(format!("the archive could not be written: {e}")))??;
+
+        Ok(tokio::fs::File::from_std(file))
+    }
+}
+
+fn extract_entries(data: Vec<u8>, max_entries: usize, max_uncompressed_size: u64) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = ZipArchive::new(Cursor::new(data))
+        .map_err(|_| archive_error(t!("asset_archive_invalid").to_string()))?;
+    if archive.len() > max_entries {
+        return Err(archive_error(t!("asset_archive_too_many_entries", max = max_entries).to_string()));
+    }
+
+    let mut entries: Vec<ArchiveEntry> = vec![];
+    let mut uncompressed_size: u64 = 0;
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|_| archive_error(t!("asset_archive_invalid").to_string()))?;
+
+        let path = match archive_entry_path(file.name()) {
+            Some(path) if file.enclosed_name().is_some() && !file.is_symlink() => path,
+            _ => return Err(archive_error(t!("asset_archive_unsafe_path", path = file.name()).to_string())),
+        };
+        if path.iter().any(|part| IGNORED_ENTRY_NAMES.contains(&part.as_str()) || part.starts_with("._")) {
+            continue;
+        }
+        if file.is_dir() {
+            entries.push(ArchiveEntry { path, data: None });
+            continue;
+        }
+
+        let too_large = || archive_error(t!("asset_archive_too_large", size = max_uncompressed_size / 1024).to_string());
+        let remaining_size = max_uncompressed_size - uncompressed_size;
+        if file.size() > remaining_size {
+            return Err(too_large());
+        }
+
+        let compressed_size = file.compressed_size().max(1);
+        let mut entry_data: Vec<u8> = vec![];
+        (&mut file)
+            .take(remaining_size + 1)
+            .read_to_end(&mut entry_data)
+            .map_err(|_| archive_error(t!("asset_archive_invalid").to_string()))?;
+
+        let entry_size = entry_data.len() as u64;
+        let is_bomb = entry_size >= COMPRESSION_RATIO_MIN_SIZE && entry_size / compressed_size > MAX_COMPRESSION_RATIO;
+        if entry_size > remaining_size || is_bomb {
+            return Err(too_large());
+        }
+
+        uncompressed_size += entry_size;
+        entries.push(ArchiveEntry { path, data: Some(entry_data) });
+    }
+
+    Ok(entries)
+}
+
+fn write_entries<W, F>(writer: W, files: Vec<ArchiveFile>, mut read_file: F) -> Result<W>
+where
+    W: Write + Seek,
+    F: FnMut(&ArchiveFile) -> Result<Vec<u8>>,
+{
+    let mut zip_writer = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+
+    for file in files {
+        let data = read_file(&file)?;
+        zip_writer
+            .start_file(file.entry_name, options)
+            .map_err(|e| Error::Generic(format!("the archive could not be written: {e}")))?;
+        zip_writer.write_all(&data)?;
+    }
+
+    zip_writer
+        .finish()
+        .map_err(|e| Error::Generic(format!("the archive could not be written: {e}")))
+}
+
+/// The parts of a relative path, `None` for absolute paths and paths leaving the archive.
+fn archive_entry_path(name: &str) -> Option<Vec<String>> {
+    if name.starts_with('/') || name.contains(['\\', ':', '\0']) {
+        return None;
+    }
+
+    let path: Vec<String> = name
+        .trim_end_matches('/')
+        .split('/')
+        .map(String::from)
+        .collect();
+    if path.iter().any(|part| part.is_empty() || part == "." || part == "..") {
+        return None;
+    }
+
+    Some(path)
+}
+
+fn archive_error(message: String) -> Error {
+    Error::BadRequest(ErrorResponse {
+        status: false,
+        errors: vec![ErrorMessage {
+            key: String::from("file"),
+            message
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+    use super::{archive_entry_path, extract_entries};
+
+    fn zip_of(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut zip_writer = ZipWriter::new(Cursor::new(vec![]));
+        for (name, data) in entries {
+            zip_writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip_writer.write_all(data).unwrap();
+        }
+
+        zip_writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_archive_entry_path() {
+        assert_eq!(archive_entry_path("photos/summer/beach.jpg").unwrap(), vec!["photos", "summer", "beach.jpg"]);
+        assert_eq!(archive_entry_path("photos/").unwrap(), vec!["photos"]);
+        assert!(archive_entry_path("../beach.jpg").is_none());
+        assert!(archive_entry_path("photos/../../beach.jpg").is_none());
+        assert!(archive_entry_path("/etc/passwd").is_none());
+        assert!(archive_entry_path("C:\\beach.jpg").is_none());
+    }
+
+    #[test]
+    fn test_extract_entries() {
+        let archive = zip_of(&[("notes/a.txt", b"hello".to_vec()), ("__MACOSX/._a.txt", b"junk".to_vec())]);
+        let entries = extract_entries(archive, 10, 1024 * 1024).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, vec!["notes", "a.txt"]);
+
+        assert!(extract_entries(zip_of(&[("../a.txt", b"hello".to_vec())]), 10, 1024 * 1024).is_err());
+        assert!(extract_entries(zip_of(&[("a.txt", b"a".to_vec()), ("b.txt", b"b".to_vec())]), 1, 1024 * 1024).is_err());
+
+        // highly compressible content is refused whatever the limit
+        let bomb = zip_of(&[("zeros.txt", vec![0; 4 * 1024 * 1024])]);
+        assert!(extract_entries(bomb.clone(), 10, 1024 * 1024).is_err());
+        assert!(extract_entries(bomb, 10, 64 * 1024 * 1024).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use std::sync::Arc;
+use serde::Serialize;
+use tracing::log::error;
+use crate::error::Result;
+use crate::models::asset_reference_model::{AssetReferenceModel, AssetReferenceSource, CreatableAssetReferenceModel};
+use crate::models::component_model::ComponentModel;
+use crate::models::model_model::ModelModel;
+use crate::models::page_model::NewPageModel;
+use crate::models::setting_model::SettingModel;
+use crate::providers::avored_database_provider::DB;
+use crate::providers::avored_storage_provider::AssetStorage;
+use crate::repositories::asset_reference_repository::AssetReferenceRepository;
+use crate::repositories::asset_repository::AssetRepository;
+use crate::repositories::component_repository::ComponentRepository;
+use crate::repositories::model_repository::ModelRepository;
+use crate::repositories::page_repository::PageRepository;
+use crate::repositories::setting_repository::SettingRepository;
+
+const LOCAL_UPLOAD_PREFIX: &str = "public/upload/";
+/// Urls which address the asset by its id: image renditions and private files.
+const ASSET_ID_URL_PREFIXES: [&str; 3] = ["public/image/", "api/private-asset/", "signed-asset/"];
+/// Longer values are content, not a single id or storage key.
+const MAX_REFERENCE_VALUE_LENGTH: usize = 1024;
+
+pub struct AssetReferenceService {
+    asset_reference_repository: AssetReferenceRepository,
+    asset_repository: AssetRepository,
+    page_repository: PageRepository,
+    component_repository: ComponentRepository,
+    model_repository: ModelRepository,
+    setting_repository: SettingRepository,
+    storage: Arc<dyn AssetStorage>,
+}
+
+impl AssetReferenceService {
+    pub fn new(
+        asset_reference_repository: AssetReferenceRepository,
+        asset_repository: AssetRepository,
+        page_repository: PageRepository,
+        component_repository: ComponentRepository,
+        model_repository: ModelRepository,
+        setting_repository: SettingRepository,
+        storage: Arc<dyn AssetStorage>,
+    ) -> Result<Self> {
+        Ok(AssetReferenceService {
+            asset_reference_repository,
+            asset_repository,
+            page_repository,
+            component_repository,
+            model_repository,
+            setting_repository,
+            storage,
+        })
+    }
+}
+
+impl AssetReferenceService {
+    /// Where the assets are used, sorted by the kind and the name of the source.
+    pub async fn usages(
+        &self,
+        (datastore, database_session): &DB,
+        asset_ids: Vec<String>
+    ) -> Result<Vec<AssetReferenceModel>> {
+        self.asset_reference_repository
+            .all_by_asset_ids(datastore, database_session, asset_ids)
+            .await
+    }
+
+    pub async fn index_page(&self, db: &DB, page_model: &NewPageModel) -> Result<()> {
+        let fields = page_model.page_fields
+            .iter()
+            .map(|page_field| (page_field.identifier.clone(), to_json(&page_field.field_content)))
+            .collect();
+
+        self.index_source(db, AssetReferenceSource::Page, &page_model.id, &page_model.name, fields).await
+    }
+
+    pub async fn index_component(&self, db: &DB, component_model: &ComponentModel) -> Result<()> {
+        let fields = component_model.elements
+            .iter()
+            .map(|element| (element.identifier.clone(), to_json(&element.element_data)))
+            .collect();
+
+        self.index_source(db, AssetReferenceSource::Component, &component_model.id, &component_model.name, fields).await
+    }
+
+    pub async fn index_model(&self, db: &DB, model_model: &ModelModel) -> Result<()> {
+        let fields = vec![
+            (String::from("name"), to_json(&model_model.name)),
+            (String::from("identifier"), to_json(&model_model.identifier)),
+        ];
+
+        self.index_source(db, AssetReferenceSource::Model, &model_model.id, &model_model.name, fields).await
+    }
+
+    pub async fn index_setting(&self, db: &DB, setting_model: &SettingModel) -> Result<()> {
+        let fields = vec![(String::from("value"), to_json(&setting_model.value))];
+
+        self.index_source(db, AssetReferenceSource::Setting, &setting_model.id, &setting_model.identifier, fields).await
+    }
+
+    pub async fn remove_source(
+        &self,
+        (datastore, database_session): &DB,
+        source_type: AssetReferenceSource,
+        source_id: &str
+    ) -> Result<()> {
+        self.asset_reference_repository
+            .delete_by_source(datastore, database_session, source_type, source_id)
+            .await
+    }
+
+    /// Indexes every source again, for content saved before the index existed. A source
+    /// which fails is logged and skipped.
+    pub async fn rebuild(&self, db: &DB) -> Result<()> {
+        let (datastore, database_session) = db;
+
+        for page_model in self.page_repository.all(datastore, database_session).await? {
+            if let Err(e) = self.index_page(db, &page_model).await {
+                error!("there is an issue while indexing the assets of page {}: {e:?}", page_model.id);
+            }
+        }
+        for component_model in self.component_repository.all(datastore, database_session).await? {
+            if let Err(e) = self.index_component(db, &component_model).await {
+                error!("there is an issue while indexing the assets of component {}: {e:?}", component_model.id);
+            }
+        }
+        for model_model in self.model_repository.all(datastore, database_session).await? {
+            if let Err(e) = self.index_model(db, &model_model).await {
+                error!("there is an issue while indexing the assets of model {}: {e:?}", model_model.id);
+            }
+        }
+        for setting_model in self.setting_repository.all(datastore, database_session).await? {
+            if let Err(e) = self.index_setting(db, &setting_model).await {
+                error!("there is an issue while indexing the assets of setting {}: {e:?}", setting_model.id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the references of the source with the files its fields point to, by storage
+    /// url, upload path, image rendition url or a value which is an asset id or key on its own.
+    async fn index_source(
+        &self,
+        (datastore, database_session): &DB,
+        source_type: AssetReferenceSource,
+        source_id: &str,
+        source_name: &str,
+        fields: Vec<(String, serde_json::Value)>
+    ) -> Result<()> {
+        let url_prefixes = [self.storage.url(""), LOCAL_UPLOAD_PREFIX.to_string()];
+        let mut field_candidates: Vec<(String, Vec<String>, Vec<String>)> = vec![];
+
+        for (field, value) in fields {
+            let mut texts: Vec<String> = vec![];
+            collect_texts(value, &mut texts);
+
+            let mut paths: Vec<String> = vec![];
+            let mut asset_ids: Vec<String> = vec![];
+            for text in &texts {
+                reference_candidates(text, &url_prefixes, &mut paths, &mut asset_ids);
+            }
+            field_candidates.push((field, paths, asset_ids));
+        }
+
+        let all_paths: Vec<String> = field_candidates
+            .iter()
+            .flat_map(|(_, paths, _)| paths.iter())
+            .flat_map(|path| [path.clone(), format!("{LOCAL_UPLOAD_PREFIX}{path}")])
+            .collect();
+        let all_asset_ids: Vec<String> = field_candidates
+            .iter()
+            .flat_map(|(_, _, asset_ids)| asset_ids.clone())
+            .collect();
+        let files = match all_paths.is_empty() && all_asset_ids.is_empty() {
+            true => vec![],
+            false => self.asset_repository
+                .all_files_by_paths_or_ids(datastore, database_session, all_paths, all_asset_ids)
+                .await?,
+        };
+
+        let mut creatable_asset_references: Vec<CreatableAssetReferenceModel> = vec![];
+        for (field, paths, asset_ids) in field_candidates {
+            for file in &files {
+                let is_referenced = paths.contains(&file.path) || asset_ids.contains(&file.id);
+                let is_indexed = creatable_asset_references
+                    .iter()
+                    .any(|reference| reference.asset_id == file.id && reference.field == field);
+                if is_referenced && !is_indexed {
+                    creatable_asset_references.push(CreatableAssetReferenceModel {
+                        asset_id: file.id.clone(),
+                        field: field.clone(),
+                    });
+                }
+            }
+        }
+
+        self.asset_reference_repository
+            .replace_for_source(datastore, database_session, source_type, source_id, source_name, creatable_asset_references)
+            .await
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+fn collect_texts(value: serde_json::Value, texts: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(text) => texts.push(text),
+        serde_json::Value::Array(values) => values.into_iter().for_each(|value| collect_texts(value, texts)),
+        serde_json::Value::Object(object) => object.into_iter().for_each(|(_, value)| collect_texts(value, texts)),
+        _ => {}
+    }
+}
+
+/// Storage keys and asset ids the text may point to, they are looked up afterwards.
+fn reference_candidates(text: &str, url_prefixes: &[String], paths: &mut Vec<String>, asset_ids: &mut Vec<String>) {
+    let value = text.trim();
+    if !value.is_empty() && value.len() <= MAX_REFERENCE_VALUE_LENGTH && !value.contains(char::is_whitespace) {
+        paths.push(value.trim_start_matches('/').to_string());
+        asset_ids.push(value.to_string());
+    }
+
+    for url_prefix in url_prefixes.iter().filter(|url_prefix| !url_prefix.is_empty()) {
+        for (start, _) in text.match_indices(url_prefix.as_str()) {
+            let key = reference_value(&text[start + url_prefix.len()..], &[]);
+            if let Ok(key) = urlencoding::decode(key) {
+                paths.push(key.to_string());
+            }
+        }
+    }
+
+    for url_prefix in ASSET_ID_URL_PREFIXES {
+        for (start, _) in text.match_indices(url_prefix) {
+            let asset_id = reference_value(&text[start + url_prefix.len()..], &['/']);
+            asset_ids.push(asset_id.to_string());
+        }
+    }
+}
+
+/// The part of the text up to the end of the url or the quoted value.
+fn reference_value<'a>(text: &'a str, extra_delimiters: &[char]) -> &'a str {
+    let end = text
+        .find(|c: char| c.is_whitespace() || "\"'<>()?#\\".contains(c) || extra_delimiters.contains(&c))
+        .unwrap_or(text.len());
+
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reference_candidates;
+
+    #[test]
+    fn test_reference_candidates() {
+        let url_prefixes = [String::from("https://cdn.example.com/bucket/"), String::from("public/upload/")];
+        let mut paths = vec![];
+        let mut asset_ids = vec![];
+        let text = r#"<p><img src="https://cdn.example.com/bucket/banners/summer%20sale.png?v=2">
+            <img src="/public/upload/logo.png" /><img src='/public/image/abc123?w=200'>
+            <a href="/signed-asset/def456?expires=1&signature=0a">Whitepaper</a></p>"#;
+        reference_candidates(text, &url_prefixes, &mut paths, &mut asset_ids);
+
+        assert_eq!(paths, vec!["banners/summer sale.png", "logo.png"]);
+        assert_eq!(asset_ids, vec!["abc123", "def456"]);
+
+        let mut paths = vec![];
+        let mut asset_ids = vec![];
+        reference_candidates(" abc123 ", &url_prefixes, &mut paths, &mut asset_ids);
+        assert_eq!(paths, vec!["abc123"]);
+        assert_eq!(asset_ids, vec!["abc123"]);
+    }
+}
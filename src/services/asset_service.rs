@@ -1,27 +1,103 @@
-use crate::{error::Result, PER_PAGE, providers::avored_database_provider::DB, repositories::asset_repository::AssetRepository};
-use crate::models::asset_model::{AssetPagination, CreatableAssetModelNew, MetaDataType, NewAssetModel};
+use std::sync::Arc;
+use crate::{error::{Error, Result}, PER_PAGE, providers::avored_database_provider::DB, repositories::asset_repository::AssetRepository};
+use crate::models::asset_model::{AssetOperationResultModel, AssetPagination, AssetSearchModel, DuplicateAssetGroupModel, CreatableAssetModelNew, MetaDataType, NewAssetModel, SignedAssetUrlModel, UpdatableAssetModel};
 use crate::models::Pagination;
 use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+use crate::providers::avored_storage_provider::AssetStorage;
+use crate::repositories::asset_reference_repository::AssetReferenceRepository;
+use hmac::{Hmac, Mac};
+use rust_i18n::t;
+use sha2::Sha256;
+use tracing::log::error;
+
+/// Characters of the checksum added to the file urls as `?v=`.
+const ASSET_URL_VERSION_LENGTH: usize = 16;
 
 pub struct AssetService {
     asset_repository: AssetRepository,
+    asset_reference_repository: AssetReferenceRepository,
+    storage: Arc<dyn AssetStorage>,
+    private_storage: Arc<dyn AssetStorage>,
+    back_end_app_url: String,
+    url_signing_secret: String,
+    signed_url_expires_in: i64,
+    signed_url_max_expires_in: i64,
 }
 
 impl AssetService {
-    pub fn new(asset_repository: AssetRepository) -> Result<Self> {
-        Ok(AssetService { asset_repository })
+    pub fn new(
+        config: &AvoRedConfigProvider,
+        asset_repository: AssetRepository,
+        asset_reference_repository: AssetReferenceRepository,
+        storage: Arc<dyn AssetStorage>,
+        private_storage: Arc<dyn AssetStorage>
+    ) -> Result<Self> {
+        Ok(AssetService {
+            asset_repository,
+            asset_reference_repository,
+            storage,
+            private_storage,
+            back_end_app_url: config.back_end_app_url.trim_end_matches('/').to_string(),
+            url_signing_secret: config.asset_url_signing_secret.clone(),
+            signed_url_expires_in: config.asset_signed_url_expires_in,
+            signed_url_max_expires_in: config.asset_signed_url_max_expires_in,
+        })
     }
 }
 impl AssetService {
     pub async fn paginate(
         &self,
-        (datastore, database_session): &DB,
+        db: &DB,
         current_page: i64,
-        parent_id: String
+        parent_id: String,
+        logged_in_user: &LoggedInUser
     ) -> Result<AssetPagination> {
+        let (datastore, database_session) = db;
         let start = (current_page - 1) * PER_PAGE;
         let to = start + PER_PAGE;
 
+        if let Some(folders) = self.restricted_folders(db, logged_in_user).await? {
+            let parent = self.find_parent(db, &parent_id).await?;
+            let is_parent_allowed = parent
+                .as_ref()
+                .is_some_and(|parent| is_within_folders(parent, &folders));
+
+            // above the allowed folders only the way down to them is listed
+            if !is_parent_allowed {
+                let assets: Vec<NewAssetModel> = self
+                    .asset_repository
+                    .all_by_parent_id(datastore, database_session, parent_id)
+                    .await?
+                    .into_iter()
+                    .filter(|asset| {
+                        is_within_folders(asset, &folders)
+                            || folders
+                                .iter()
+                                .any(|folder| folder.path.starts_with(&format!("{}/", asset.path)))
+                    })
+                    .map(|asset| self.with_url(asset))
+                    .collect();
+                let total = assets.len() as i64;
+
+                return Ok(AssetPagination {
+                    data: assets,
+                    pagination: Pagination {
+                        total,
+                        per_page: PER_PAGE,
+                        current_page: 1,
+                        from: 1,
+                        to: total,
+                        has_previous_page: false,
+                        next_page_number: 2,
+                        has_next_page: false,
+                        previous_page_number: 0,
+                    },
+                });
+            }
+        }
+
         let asset_model_count = self
             .asset_repository
             .get_total_count(datastore, database_session, parent_id.clone())
@@ -51,7 +127,53 @@ impl AssetService {
         let assets = self
             .asset_repository
             .paginate(datastore, database_session, start, parent_id)
+            .await?
+            .into_iter()
+            .map(|asset| self.with_url(asset))
+            .collect();
+
+        Ok(AssetPagination {
+            data: assets,
+            pagination,
+        })
+    }
+
+    /// Searches the assets of every folder the admin user can see.
+    pub async fn search(
+        &self,
+        db: &DB,
+        current_page: i64,
+        search: AssetSearchModel,
+        logged_in_user: &LoggedInUser
+    ) -> Result<AssetPagination> {
+        let (datastore, database_session) = db;
+        let start = (current_page - 1) * PER_PAGE;
+        let to = start + PER_PAGE;
+        let folders = self.restricted_folders(db, logged_in_user).await?;
+
+        let asset_model_count = self
+            .asset_repository
+            .search_count(datastore, database_session, &search, folders.as_deref())
             .await?;
+        let assets = self
+            .asset_repository
+            .search(datastore, database_session, &search, folders.as_deref(), start)
+            .await?
+            .into_iter()
+            .map(|asset| self.with_url(asset))
+            .collect();
+
+        let pagination = Pagination {
+            total: asset_model_count.total,
+            per_page: PER_PAGE,
+            current_page,
+            from: (start + 1),
+            to,
+            has_previous_page: current_page > 1,
+            next_page_number: (current_page + 1),
+            has_next_page: asset_model_count.total > to,
+            previous_page_number: (current_page - 1),
+        };
 
         Ok(AssetPagination {
             data: assets,
@@ -71,12 +193,18 @@ impl AssetService {
 
     pub async fn create_asset(
         &self,
-        (datastore, database_session): &DB,
+        db: &DB,
         creatable_asset_model: CreatableAssetModelNew,
+        logged_in_user: &LoggedInUser
     ) -> Result<NewAssetModel> {
-        self.asset_repository
+        let (datastore, database_session) = db;
+        self.authorize_parent(db, &creatable_asset_model.parent_id, logged_in_user).await?;
+
+        let asset_model = self.asset_repository
             .create_asset(datastore, database_session, creatable_asset_model)
-            .await
+            .await?;
+
+        Ok(self.with_url(asset_model))
     }
 
     pub async fn find_by_id(
@@ -84,19 +212,255 @@ impl AssetService {
         (datastore, database_session): &DB,
         asset_id: &str
     ) -> Result<NewAssetModel> {
-        self.asset_repository
+        let asset_model = self.asset_repository
             .find_by_id(datastore, database_session, asset_id)
-            .await
+            .await?;
+
+        Ok(self.with_url(asset_model))
+    }
+
+    /// Asset lookup for the admin api, it fails for assets outside the folders the admin
+    /// user roles are restricted to.
+    pub async fn find_accessible_by_id(
+        &self,
+        db: &DB,
+        asset_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewAssetModel> {
+        let asset_model = self.find_by_id(db, asset_id).await?;
+        if let Some(folders) = self.restricted_folders(db, logged_in_user).await? {
+            if !is_within_folders(&asset_model, &folders) {
+                return Err(Error::Forbidden);
+            }
+        }
+
+        Ok(asset_model)
     }
 
+    /// Checks that the admin user may add assets below the parent, an empty parent id is
+    /// the upload root.
+    pub async fn authorize_parent(
+        &self,
+        db: &DB,
+        parent_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<()> {
+        if let Some(folders) = self.restricted_folders(db, logged_in_user).await? {
+            let parent = self.find_parent(db, parent_id).await?;
+            if !parent.is_some_and(|parent| is_within_folders(&parent, &folders)) {
+                return Err(Error::Forbidden);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Assets which are used somewhere are only deleted when forced.
     pub async fn delete_by_id(
         &self,
-        (datastore, database_session): &DB,
+        db: &DB,
         asset_id: &str,
+        force: bool,
+        logged_in_user: &LoggedInUser
     ) -> Result<bool> {
-        self.asset_repository
+        let (datastore, database_session) = db;
+        let asset_model = self.find_accessible_by_id(db, asset_id, logged_in_user).await?;
+        if !force {
+            self.ensure_unreferenced(db, std::slice::from_ref(&asset_model)).await?;
+        }
+
+        match asset_model.asset_type.as_str() {
+            "FOLDER" => self.delete_folder_data(&asset_model.path).await?,
+            _ => self.storage_for(&asset_model).delete(&asset_model.path).await?,
+        }
+
+        let result = self.asset_repository
             .delete_by_id(datastore, database_session, asset_id)
-            .await
+            .await?;
+        if result {
+            self.remove_references(db, std::slice::from_ref(&asset_model)).await;
+        }
+
+        Ok(result)
+    }
+
+    /// The asset with everything that goes with it when it is deleted, folders come before
+    /// their content. Folders with content are only deletable when deleting recursively and
+    /// files which are used somewhere only when forced.
+    pub async fn deletable_assets(
+        &self,
+        db: &DB,
+        asset_id: &str,
+        recursive: bool,
+        force: bool,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Vec<NewAssetModel>> {
+        let asset_model = self.find_accessible_by_id(db, asset_id, logged_in_user).await?;
+        if asset_model.asset_type != "FOLDER" {
+            if !force {
+                self.ensure_unreferenced(db, std::slice::from_ref(&asset_model)).await?;
+            }
+
+            return Ok(vec![asset_model]);
+        }
+
+        let descendants = self.descendants(db, &asset_model.id).await?;
+        if !recursive && !descendants.is_empty() {
+            return Err(Error::BadRequest(ErrorResponse {
+                status: false,
+                errors: vec![ErrorMessage {
+                    key: String::from("folder_existed"),
+                    message: t!("asset_folder_not_empty").to_string()
+                }]
+            }));
+        }
+
+        let mut assets = vec![asset_model];
+        assets.extend(descendants);
+        if !force {
+            self.ensure_unreferenced(db, &assets).await?;
+        }
+
+        Ok(assets)
+    }
+
+    /// Deletes all the assets or none of them, nothing is deleted when one of them can not be.
+    /// Returns the report per asset and every asset which is gone, content of folders included.
+    pub async fn delete_assets(
+        &self,
+        db: &DB,
+        asset_ids: &[String],
+        recursive: bool,
+        force: bool,
+        logged_in_user: &LoggedInUser
+    ) -> Result<(Vec<AssetOperationResultModel>, Vec<NewAssetModel>)> {
+        let mut results: Vec<AssetOperationResultModel> = vec![];
+        let mut deletable_assets: Vec<NewAssetModel> = vec![];
+
+        for asset_id in unique_ids(asset_ids) {
+            match self.deletable_assets(db, &asset_id, recursive, force, logged_in_user).await {
+                Ok(assets) => {
+                    results.push(AssetOperationResultModel::succeeded(&asset_id));
+                    for asset_model in assets {
+                        if !deletable_assets.iter().any(|deletable| deletable.id == asset_model.id) {
+                            deletable_assets.push(asset_model);
+                        }
+                    }
+                },
+                Err(e) => results.push(AssetOperationResultModel::failed(&asset_id, &e)),
+            }
+        }
+
+        if results.iter().any(|result| !result.success) {
+            return Ok((mark_not_processed(results), vec![]));
+        }
+
+        let deleted_assets = self.remove_assets(db, deletable_assets).await?;
+
+        Ok((results, deleted_assets))
+    }
+
+    /// Deletes the records and their references in one transaction. The stored data is only
+    /// cleaned up once that went through, so no record ever points to a removed file.
+    pub async fn remove_assets(&self, (datastore, database_session): &DB, assets: Vec<NewAssetModel>) -> Result<Vec<NewAssetModel>> {
+        let asset_ids = assets.iter().map(|asset_model| asset_model.id.clone()).collect();
+        let result = self.asset_repository
+            .delete_by_ids(datastore, database_session, asset_ids)
+            .await?;
+        if !result {
+            return Err(Error::Generic(String::from("there is an issue while deleting the asset records in DB")));
+        }
+
+        let (mut folders, files): (Vec<&NewAssetModel>, Vec<&NewAssetModel>) = assets
+            .iter()
+            .partition(|asset_model| asset_model.asset_type == "FOLDER");
+        for file in files {
+            if let Err(e) = self.storage_for(file).delete(&file.path).await {
+                error!("there is an issue while removing the deleted file {}: {e:?}", file.path);
+            }
+        }
+
+        // the deepest folders go first, so every folder is empty when it is removed
+        folders.sort_by_key(|folder| std::cmp::Reverse(folder.path.matches('/').count()));
+        for folder in folders {
+            if let Err(e) = self.delete_folder_data(&folder.path).await {
+                error!("there is an issue while removing the deleted folder {}: {e:?}", folder.path);
+            }
+        }
+
+        Ok(assets)
+    }
+
+    /// Adds and removes tags on all the assets in one transaction, nothing changes when one of
+    /// them is not accessible.
+    pub async fn tag_assets(
+        &self,
+        db: &DB,
+        asset_ids: &[String],
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Vec<AssetOperationResultModel>> {
+        let (datastore, database_session) = db;
+        let asset_ids = unique_ids(asset_ids);
+        let mut results: Vec<AssetOperationResultModel> = vec![];
+
+        for asset_id in &asset_ids {
+            match self.find_accessible_by_id(db, asset_id, logged_in_user).await {
+                Ok(_) => results.push(AssetOperationResultModel::succeeded(asset_id)),
+                Err(e) => results.push(AssetOperationResultModel::failed(asset_id, &e)),
+            }
+        }
+
+        if results.iter().any(|result| !result.success) {
+            return Ok(mark_not_processed(results));
+        }
+
+        self.asset_repository
+            .update_tags_by_ids(datastore, database_session, asset_ids, add_tags, remove_tags, &logged_in_user.email)
+            .await?;
+
+        Ok(results)
+    }
+
+    /// Fails when one of the files is used in a page, component, model or setting.
+    async fn ensure_unreferenced(&self, (datastore, database_session): &DB, assets: &[NewAssetModel]) -> Result<()> {
+        let file_ids: Vec<String> = assets
+            .iter()
+            .filter(|asset_model| asset_model.asset_type == "FILE")
+            .map(|asset_model| asset_model.id.clone())
+            .collect();
+        if file_ids.is_empty() {
+            return Ok(());
+        }
+
+        let asset_references = self.asset_reference_repository
+            .all_by_asset_ids(datastore, database_session, file_ids)
+            .await?;
+        if !asset_references.is_empty() {
+            return Err(Error::BadRequest(ErrorResponse {
+                status: false,
+                errors: vec![ErrorMessage {
+                    key: String::from("asset_in_use"),
+                    message: t!("asset_in_use", count = asset_references.len()).to_string()
+                }]
+            }));
+        }
+
+        Ok(())
+    }
+
+    async fn remove_references(&self, (datastore, database_session): &DB, assets: &[NewAssetModel]) {
+        let asset_ids = assets.iter().map(|asset_model| asset_model.id.clone()).collect();
+        if let Err(e) = self.asset_reference_repository
+            .delete_by_asset_ids(datastore, database_session, asset_ids)
+            .await {
+            error!("there is an issue while removing the references of the deleted assets: {e:?}");
+        }
+    }
+
+    pub async fn store_file(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<()> {
+        self.storage.put(key, data, content_type).await
     }
 
     pub async fn create_asset_folder(
@@ -107,8 +471,9 @@ impl AssetService {
         logged_in_user: LoggedInUser
     ) -> Result<NewAssetModel> {
         let (datastore, database_session) = db;
+        self.authorize_parent(db, &parent_id, &logged_in_user).await?;
 
-        let mut full_path = format!("public/upload/{}", name.clone());
+        let mut full_path = name.clone();
 
         if !parent_id.is_empty() {
             let asset = self
@@ -117,7 +482,7 @@ impl AssetService {
 
             full_path = format!("{}/{}",asset.path, name.clone());
         }
-        tokio::fs::create_dir_all(&format!("./{}", full_path.clone())).await?;
+        self.storage.create_folder(&full_path).await?;
 
         // @todo fix this default color????
         let color= String::from("text-gray-400");
@@ -129,23 +494,672 @@ impl AssetService {
             path: full_path,
             asset_type: "FOLDER".to_string(),
             metadata: MetaDataType::FolderTypeMetaData {color},
+            ..Default::default()
         };
 
-        self.asset_repository
+        let asset_model = self.asset_repository
             .create_asset_folder(datastore, database_session, creatable_asset_model)
-            .await
+            .await?;
+
+        Ok(self.with_url(asset_model))
     }
 
-    pub async fn update_asset_path(
+    /// The folder below the parent with this name, it is created when there is none yet.
+    pub async fn find_or_create_folder(
         &self,
-        (datastore, database_session): &DB,
+        db: &DB,
         name: &str,
-        new_path: &str,
+        parent_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewAssetModel> {
+        let (datastore, database_session) = db;
+        let existing_folder = self.asset_repository
+            .all_by_parent_id(datastore, database_session, parent_id.to_string())
+            .await?
+            .into_iter()
+            .find(|child| child.asset_type == "FOLDER" && child.name == name);
+
+        match existing_folder {
+            Some(folder) => Ok(self.with_url(folder)),
+            None => self.create_asset_folder(db, name.to_string(), parent_id.to_string(), logged_in_user.clone()).await,
+        }
+    }
+
+    /// Everything below the folder, every folder comes before its content.
+    pub async fn folder_contents(
+        &self,
+        db: &DB,
+        folder_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<(NewAssetModel, Vec<NewAssetModel>)> {
+        let folder = self.find_accessible_by_id(db, folder_id, logged_in_user).await?;
+        if folder.asset_type != "FOLDER" {
+            return Err(Error::NotFound(format!("no folder found with id {folder_id}")));
+        }
+
+        let descendants = self.descendants(db, &folder.id).await?;
+
+        Ok((folder, descendants.into_iter().map(|asset_model| self.with_url(asset_model)).collect()))
+    }
+
+    pub async fn rename_asset(
+        &self,
+        db: &DB,
         asset_id: &str,
+        name: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewAssetModel> {
+        let asset_model = self.find_accessible_by_id(db, asset_id, logged_in_user).await?;
+
+        // the asset stays in its folder, only the last part of the key changes
+        let new_path = match asset_model.path.rsplit_once('/') {
+            Some((folder_path, _)) => format!("{folder_path}/{name}"),
+            None => name.to_string(),
+        };
+        let parent_id = asset_model.parent_id.clone();
+
+        self.relocate(db, asset_model, &parent_id, name, &new_path, &logged_in_user.email).await
+    }
+
+    /// Moves a file or a folder with everything below it, an empty parent id is the upload root.
+    pub async fn move_asset(
+        &self,
+        db: &DB,
+        asset_id: &str,
+        parent_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewAssetModel> {
+        let asset_model = self.find_accessible_by_id(db, asset_id, logged_in_user).await?;
+        let new_path = self.move_target_path(db, &asset_model, parent_id, logged_in_user).await?;
+        let name = asset_model.name.clone();
+
+        self.relocate(db, asset_model, parent_id, &name, &new_path, &logged_in_user.email).await
+    }
+
+    /// Moves all the assets or none of them, nothing is moved when one of them can not be.
+    /// Assets inside a selected folder move along with that folder.
+    pub async fn move_assets(
+        &self,
+        db: &DB,
+        asset_ids: &[String],
+        parent_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Vec<AssetOperationResultModel>> {
+        let mut results: Vec<AssetOperationResultModel> = vec![];
+        let mut movable_assets: Vec<(NewAssetModel, String)> = vec![];
+
+        for asset_id in unique_ids(asset_ids) {
+            let checked = match self.find_accessible_by_id(db, &asset_id, logged_in_user).await {
+                Ok(asset_model) => self
+                    .move_target_path(db, &asset_model, parent_id, logged_in_user)
+                    .await
+                    .map(|new_path| (asset_model, new_path)),
+                Err(e) => Err(e),
+            };
+            let checked = checked.and_then(|(asset_model, new_path)| {
+                if movable_assets.iter().any(|(_, other_path)| *other_path == new_path) {
+                    return Err(parent_id_error(t!("asset_name_taken").to_string()));
+                }
+
+                Ok((asset_model, new_path))
+            });
+
+            match checked {
+                Ok(movable_asset) => {
+                    results.push(AssetOperationResultModel::succeeded(&asset_id));
+                    movable_assets.push(movable_asset);
+                },
+                Err(e) => results.push(AssetOperationResultModel::failed(&asset_id, &e)),
+            }
+        }
+
+        if results.iter().any(|result| !result.success) {
+            return Ok(mark_not_processed(results));
+        }
+
+        let selected_folders: Vec<NewAssetModel> = movable_assets
+            .iter()
+            .map(|(asset_model, _)| asset_model.clone())
+            .filter(|asset_model| asset_model.asset_type == "FOLDER")
+            .collect();
+        let mut relocations: Vec<(NewAssetModel, String)> = vec![];
+        for (asset_model, new_path) in movable_assets {
+            let is_in_selected_folder = selected_folders
+                .iter()
+                .any(|folder| asset_model.path.starts_with(&format!("{}/", folder.path)));
+            if is_in_selected_folder {
+                continue;
+            }
+
+            let asset_id = asset_model.id.clone();
+            let name = asset_model.name.clone();
+            match self.plan_relocation(db, asset_model, parent_id, &name, &new_path).await {
+                Ok(planned) => relocations.extend(planned),
+                Err(e) => {
+                    if let Some(result) = results.iter_mut().find(|result| result.asset_id == asset_id) {
+                        *result = AssetOperationResultModel::failed(&asset_id, &e);
+                    }
+                }
+            }
+        }
+
+        if results.iter().any(|result| !result.success) {
+            return Ok(mark_not_processed(results));
+        }
+
+        if let Err(e) = self.apply_relocations(db, &relocations, &logged_in_user.email).await {
+            return Ok(results
+                .into_iter()
+                .map(|result| AssetOperationResultModel::failed(&result.asset_id, &e))
+                .collect());
+        }
+
+        Ok(results)
+    }
+
+    /// The path of the asset below the parent, the parent has to be a folder outside of the asset.
+    async fn move_target_path(
+        &self,
+        db: &DB,
+        asset_model: &NewAssetModel,
+        parent_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<String> {
+        self.authorize_parent(db, parent_id, logged_in_user).await?;
+
+        let new_path = match self.find_parent(db, parent_id).await? {
+            Some(parent) => {
+                let is_into_itself = parent.id == asset_model.id
+                    || parent.path.starts_with(&format!("{}/", asset_model.path));
+                if parent.asset_type != "FOLDER" || is_into_itself {
+                    return Err(parent_id_error(t!("asset_move_target_invalid").to_string()));
+                }
+
+                format!("{}/{}", parent.path, asset_model.name)
+            },
+            None => asset_model.name.clone(),
+        };
+
+        Ok(new_path)
+    }
+
+    /// Moves the stored data and updates the path of the asset and of everything below it.
+    async fn relocate(
+        &self,
+        db: &DB,
+        asset_model: NewAssetModel,
+        parent_id: &str,
+        name: &str,
+        new_path: &str,
         logged_in_username: &str
     ) -> Result<NewAssetModel> {
-        self.asset_repository
-            .update_asset_path(datastore, database_session, name, new_path, asset_id, logged_in_username)
-            .await
+        if asset_model.path == new_path {
+            return Ok(self.with_url(asset_model));
+        }
+
+        let asset_id = asset_model.id.clone();
+        let relocations = self.plan_relocation(db, asset_model, parent_id, name, new_path).await?;
+        self.apply_relocations(db, &relocations, logged_in_username).await?;
+
+        self.find_by_id(db, &asset_id).await
+    }
+
+    /// The asset and everything below it at their new location, each with the path it is
+    /// stored under now. Nothing is changed yet.
+    async fn plan_relocation(
+        &self,
+        db: &DB,
+        asset_model: NewAssetModel,
+        parent_id: &str,
+        name: &str,
+        new_path: &str
+    ) -> Result<Vec<(NewAssetModel, String)>> {
+        let (datastore, database_session) = db;
+        if asset_model.path == new_path {
+            return Ok(vec![]);
+        }
+
+        let is_path_taken = self.asset_repository
+            .all_by_parent_id(datastore, database_session, parent_id.to_string())
+            .await?
+            .iter()
+            .any(|sibling| sibling.id != asset_model.id && sibling.path == new_path);
+        if is_path_taken {
+            return Err(parent_id_error(t!("asset_name_taken").to_string()));
+        }
+
+        let old_path = asset_model.path.clone();
+        let descendants = match asset_model.asset_type.as_str() {
+            "FOLDER" => self.descendants(db, &asset_model.id).await?,
+            _ => vec![],
+        };
+
+        let mut relocations = vec![(
+            NewAssetModel {
+                parent_id: parent_id.to_string(),
+                name: name.to_string(),
+                path: new_path.to_string(),
+                ..asset_model
+            },
+            old_path.clone()
+        )];
+        for descendant in descendants {
+            let relative_path = descendant.path
+                .strip_prefix(&format!("{old_path}/"))
+                .unwrap_or(&descendant.name)
+                .to_string();
+            let descendant_old_path = descendant.path.clone();
+            relocations.push((
+                NewAssetModel {
+                    path: format!("{new_path}/{relative_path}"),
+                    ..descendant
+                },
+                descendant_old_path
+            ));
+        }
+
+        Ok(relocations)
+    }
+
+    /// Moves the stored data first and then updates all the records in one transaction. When
+    /// either fails the data that was moved already is moved back, so the stored data and the
+    /// records never disagree. Folders always come before their content.
+    async fn apply_relocations(
+        &self,
+        (datastore, database_session): &DB,
+        relocations: &[(NewAssetModel, String)],
+        logged_in_username: &str
+    ) -> Result<()> {
+        let mut moved: Vec<&(NewAssetModel, String)> = vec![];
+        let mut moved_result: Result<()> = Ok(());
+        for relocation in relocations {
+            let (asset_model, old_path) = relocation;
+            let result = match asset_model.asset_type.as_str() {
+                "FOLDER" => self.storage.create_folder(&asset_model.path).await,
+                _ => self.storage_for(asset_model).rename(old_path, &asset_model.path).await,
+            };
+            if let Err(e) = result {
+                moved_result = Err(e);
+                break;
+            }
+            moved.push(relocation);
+        }
+
+        if moved_result.is_ok() {
+            let asset_models: Vec<NewAssetModel> = relocations
+                .iter()
+                .map(|(asset_model, _)| asset_model.clone())
+                .collect();
+            moved_result = self.asset_repository
+                .update_asset_locations(datastore, database_session, &asset_models, logged_in_username)
+                .await;
+        }
+
+        if let Err(e) = moved_result {
+            // the content goes back before the folders it was moved into are removed
+            for (asset_model, old_path) in moved.into_iter().rev() {
+                let result = match asset_model.asset_type.as_str() {
+                    "FOLDER" => self.delete_folder_data(&asset_model.path).await,
+                    _ => self.storage_for(asset_model).rename(&asset_model.path, old_path).await,
+                };
+                if let Err(e) = result {
+                    error!("there is an issue while moving back {} to {old_path}: {e:?}", asset_model.path);
+                }
+            }
+
+            return Err(e);
+        }
+
+        // the old folders are empty by now, the deepest go first
+        let old_folders = relocations
+            .iter()
+            .rev()
+            .filter(|(asset_model, _)| asset_model.asset_type == "FOLDER")
+            .map(|(_, old_path)| old_path.as_str());
+        for old_folder in old_folders {
+            if let Err(e) = self.delete_folder_data(old_folder).await {
+                error!("there is an issue while removing the moved folder {old_folder}: {e:?}");
+            }
+        }
+
+        Ok(())
     }
+
+    /// Everything below the folder, every folder comes before its content.
+    async fn descendants(&self, (datastore, database_session): &DB, folder_id: &str) -> Result<Vec<NewAssetModel>> {
+        let mut descendants: Vec<NewAssetModel> = vec![];
+        let mut folder_ids = vec![folder_id.to_string()];
+
+        while let Some(folder_id) = folder_ids.pop() {
+            let children = self.asset_repository
+                .all_by_parent_id(datastore, database_session, folder_id)
+                .await?;
+            for child in children {
+                if child.asset_type == "FOLDER" {
+                    folder_ids.push(child.id.clone());
+                }
+                descendants.push(child);
+            }
+        }
+
+        Ok(descendants)
+    }
+
+    pub async fn update_asset(
+        &self,
+        db: &DB,
+        updatable_asset_model: UpdatableAssetModel,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewAssetModel> {
+        let (datastore, database_session) = db;
+        let asset_model = self.find_accessible_by_id(db, &updatable_asset_model.id, logged_in_user).await?;
+
+        // only decoded images have a size to place the focal point and crops in
+        let has_focus = updatable_asset_model.focal_point.is_some() || !updatable_asset_model.crops.is_empty();
+        if has_focus && (asset_model.width <= 0 || asset_model.height <= 0) {
+            return Err(focus_error("focal_point", t!("image_focus_not_image").to_string()));
+        }
+        for crop in &updatable_asset_model.crops {
+            if i64::from(crop.x) + i64::from(crop.width) > asset_model.width
+                || i64::from(crop.y) + i64::from(crop.height) > asset_model.height {
+                return Err(focus_error("crops", t!(
+                    "image_crop_outside",
+                    name = crop.name,
+                    width = asset_model.width,
+                    height = asset_model.height
+                ).to_string()));
+            }
+        }
+
+        let asset_model = self.asset_repository
+            .update_asset(datastore, database_session, updatable_asset_model)
+            .await?;
+
+        Ok(self.with_url(asset_model))
+    }
+
+    /// Moves the file between the public and the private storage.
+    pub async fn update_visibility(
+        &self,
+        db: &DB,
+        asset_id: &str,
+        is_private: bool,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewAssetModel> {
+        let (datastore, database_session) = db;
+        let asset_model = self.find_accessible_by_id(db, asset_id, logged_in_user).await?;
+        if asset_model.asset_type != "FILE" {
+            return Err(Error::BadRequest(ErrorResponse {
+                status: false,
+                errors: vec![ErrorMessage {
+                    key: String::from("is_private"),
+                    message: t!("asset_visibility_folder").to_string()
+                }]
+            }));
+        }
+        if asset_model.is_private == is_private {
+            return Ok(self.with_url(asset_model));
+        }
+
+        let from_storage = self.storage_for(&asset_model);
+        let to_storage = match is_private {
+            true => &self.private_storage,
+            false => &self.storage,
+        };
+        let data = from_storage.get(&asset_model.path).await?;
+        let content_type = asset_model.metadata.get_file_metadata().file_type;
+        to_storage.put(&asset_model.path, data, &content_type).await?;
+
+        let updated_asset_model = self.asset_repository
+            .update_visibility(datastore, database_session, asset_id, is_private, &logged_in_user.email)
+            .await?;
+        if let Err(e) = from_storage.delete(&asset_model.path).await {
+            error!("there is an issue while removing the file {} after a visibility change: {e:?}", asset_model.path);
+        }
+
+        Ok(self.with_url(updated_asset_model))
+    }
+
+    /// The file and its content, from whichever storage it is kept in. Without an admin user
+    /// the caller has checked the access already, like with a signed url.
+    pub async fn file_data(
+        &self,
+        db: &DB,
+        asset_id: &str,
+        logged_in_user: Option<&LoggedInUser>
+    ) -> Result<(NewAssetModel, Vec<u8>)> {
+        let asset_model = match logged_in_user {
+            Some(logged_in_user) => self.find_accessible_by_id(db, asset_id, logged_in_user).await?,
+            None => self.find_by_id(db, asset_id).await?,
+        };
+        if asset_model.asset_type != "FILE" {
+            return Err(Error::NotFound(format!("asset {asset_id} is not a file")));
+        }
+
+        let data = self.storage_for(&asset_model).get(&asset_model.path).await?;
+
+        Ok((asset_model, data))
+    }
+
+    pub fn signed_url_max_expires_in(&self) -> i64 {
+        self.signed_url_max_expires_in
+    }
+
+    /// A url for the file which works without logging in until it expires.
+    pub fn signed_url(&self, asset_model: &NewAssetModel, expires_in: Option<i64>) -> Result<SignedAssetUrlModel> {
+        let expires_at = chrono::Utc::now().timestamp() + expires_in.unwrap_or(self.signed_url_expires_in);
+        let signature = hex::encode(self.url_signature(&asset_model.id, expires_at)?.finalize().into_bytes());
+
+        Ok(SignedAssetUrlModel {
+            url: format!(
+                "{}/signed-asset/{}?expires={expires_at}&signature={signature}",
+                self.back_end_app_url,
+                asset_model.id
+            ),
+            expires_at,
+        })
+    }
+
+    pub fn verify_signed_url(&self, asset_id: &str, expires_at: i64, signature: &str) -> bool {
+        if expires_at < chrono::Utc::now().timestamp() {
+            return false;
+        }
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        self.url_signature(asset_id, expires_at)
+            .is_ok_and(|url_signature| url_signature.verify_slice(&signature).is_ok())
+    }
+
+    /// Files the admin user can access with exactly this content, the ones in the parent
+    /// folder come first.
+    pub async fn find_duplicates(
+        &self,
+        db: &DB,
+        checksum: &str,
+        parent_id: &str,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Vec<NewAssetModel>> {
+        let (datastore, database_session) = db;
+        let asset_models = self.asset_repository
+            .all_by_checksum(datastore, database_session, checksum)
+            .await?;
+        let mut duplicates = self.accessible(db, asset_models, logged_in_user).await?;
+        duplicates.sort_by_key(|asset_model| asset_model.parent_id != parent_id);
+
+        Ok(duplicates)
+    }
+
+    /// Groups of files with the same content, the groups wasting the most storage come first.
+    pub async fn duplicate_groups(
+        &self,
+        db: &DB,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Vec<DuplicateAssetGroupModel>> {
+        let (datastore, database_session) = db;
+        let asset_models = self.asset_repository
+            .all_duplicates(datastore, database_session)
+            .await?;
+        let asset_models = self.accessible(db, asset_models, logged_in_user).await?;
+
+        let mut groups: Vec<DuplicateAssetGroupModel> = vec![];
+        for asset_model in asset_models {
+            match groups.last_mut() {
+                Some(group) if group.checksum == asset_model.checksum => group.assets.push(asset_model),
+                _ => groups.push(DuplicateAssetGroupModel {
+                    checksum: asset_model.checksum.clone(),
+                    file_size: asset_model.file_size,
+                    wasted_size: 0,
+                    assets: vec![asset_model],
+                }),
+            }
+        }
+
+        groups.retain(|group| group.assets.len() > 1);
+        for group in &mut groups {
+            group.wasted_size = group.file_size * (group.assets.len() as i64 - 1);
+        }
+        groups.sort_by_key(|group| std::cmp::Reverse(group.wasted_size));
+
+        Ok(groups)
+    }
+
+    /// Files get the url the storage serves them from, folders have none.
+    fn with_url(&self, mut asset_model: NewAssetModel) -> NewAssetModel {
+        if asset_model.asset_type == "FILE" && asset_model.is_private {
+            asset_model.url = format!("{}/api/private-asset/{}", self.back_end_app_url, asset_model.id);
+        } else if asset_model.asset_type == "FILE" {
+            // the checksum changes with the content, so versioned urls can be cached for good
+            asset_model.url = match asset_model.checksum.get(..ASSET_URL_VERSION_LENGTH) {
+                Some(version) => format!("{}?v={version}", self.storage.url(&asset_model.path)),
+                None => self.storage.url(&asset_model.path),
+            };
+        }
+
+        asset_model
+    }
+
+    /// The expiry is signed with the id, so neither can be changed in the url.
+    fn url_signature(&self, asset_id: &str, expires_at: i64) -> Result<Hmac<Sha256>> {
+        let mut url_signature = Hmac::<Sha256>::new_from_slice(self.url_signing_secret.as_bytes())
+            .map_err(|_| Error::ConfigMissing(String::from("AVORED_ASSET_URL_SIGNING_SECRET")))?;
+        url_signature.update(format!("{asset_id}:{expires_at}").as_bytes());
+
+        Ok(url_signature)
+    }
+
+    fn storage_for(&self, asset_model: &NewAssetModel) -> &Arc<dyn AssetStorage> {
+        match asset_model.is_private {
+            true => &self.private_storage,
+            false => &self.storage,
+        }
+    }
+
+    /// The private storage gets its folders as private files are stored, both are cleaned up.
+    async fn delete_folder_data(&self, key: &str) -> Result<()> {
+        self.private_storage.delete_folder(key).await?;
+
+        self.storage.delete_folder(key).await
+    }
+
+    async fn find_parent(&self, db: &DB, parent_id: &str) -> Result<Option<NewAssetModel>> {
+        if parent_id.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(self.find_by_id(db, parent_id).await?))
+    }
+
+    async fn accessible(
+        &self,
+        db: &DB,
+        asset_models: Vec<NewAssetModel>,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Vec<NewAssetModel>> {
+        let asset_models = match self.restricted_folders(db, logged_in_user).await? {
+            Some(folders) => asset_models
+                .into_iter()
+                .filter(|asset_model| is_within_folders(asset_model, &folders))
+                .collect(),
+            None => asset_models,
+        };
+
+        Ok(asset_models.into_iter().map(|asset_model| self.with_url(asset_model)).collect())
+    }
+
+    /// The folders of the admin user roles, `None` when the admin user is not restricted.
+    /// Folders which no longer exist are skipped.
+    async fn restricted_folders(
+        &self,
+        db: &DB,
+        logged_in_user: &LoggedInUser
+    ) -> Result<Option<Vec<NewAssetModel>>> {
+        let folder_ids = match logged_in_user.asset_folder_restrictions() {
+            Some(folder_ids) => folder_ids,
+            None => return Ok(None),
+        };
+
+        let mut folders = vec![];
+        for folder_id in folder_ids {
+            if let Ok(folder) = self.find_by_id(db, &folder_id).await {
+                folders.push(folder);
+            }
+        }
+
+        Ok(Some(folders))
+    }
+}
+
+fn parent_id_error(message: String) -> Error {
+    Error::BadRequest(ErrorResponse {
+        status: false,
+        errors: vec![ErrorMessage {
+            key: String::from("parent_id"),
+            message
+        }]
+    })
+}
+
+fn focus_error(key: &str, message: String) -> Error {
+    Error::BadRequest(ErrorResponse {
+        status: false,
+        errors: vec![ErrorMessage {
+            key: key.to_string(),
+            message
+        }]
+    })
+}
+
+/// The ids in the given order without duplicates.
+fn unique_ids(asset_ids: &[String]) -> Vec<String> {
+    let mut unique_ids: Vec<String> = vec![];
+    for asset_id in asset_ids {
+        if !unique_ids.contains(asset_id) {
+            unique_ids.push(asset_id.clone());
+        }
+    }
+
+    unique_ids
+}
+
+/// Turns the passed checks of a bulk operation which did not run into failures.
+fn mark_not_processed(results: Vec<AssetOperationResultModel>) -> Vec<AssetOperationResultModel> {
+    results
+        .into_iter()
+        .map(|result| match result.success {
+            true => AssetOperationResultModel {
+                success: false,
+                message: t!("asset_bulk_not_processed").to_string(),
+                ..result
+            },
+            false => result,
+        })
+        .collect()
+}
+
+fn is_within_folders(asset: &NewAssetModel, folders: &[NewAssetModel]) -> bool {
+    folders
+        .iter()
+        .any(|folder| asset.id == folder.id || asset.path.starts_with(&format!("{}/", folder.path)))
 }
@@ -0,0 +1,223 @@
+use regex::{Captures, Regex};
+use rust_i18n::t;
+use crate::error::{Error, Result};
+use crate::models::asset_model::AllowedAssetTypeModel;
+use crate::models::validation_error::ErrorMessage;
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+
+/// Room for the multipart boundaries and headers on top of the file itself.
+pub const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+const OLE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+/// Elements which can run script or pull in other documents, they are removed with their content.
+const SVG_UNSAFE_ELEMENTS: [&str; 5] = ["script", "foreignObject", "iframe", "embed", "object"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedFileType {
+    pub mime_type: &'static str,
+    pub extension: &'static str,
+}
+
+pub struct AssetUploadService {
+    allowed_types: Vec<AllowedAssetTypeModel>,
+}
+
+impl AssetUploadService {
+    pub fn new(config: &AvoRedConfigProvider) -> Result<Self> {
+        let mut allowed_types = vec![];
+        for definition in &config.asset_allowed_types {
+            let allowed_type = AllowedAssetTypeModel::from_definition(definition)
+                .ok_or_else(|| Error::ConfigMissing(format!("AVORED_ASSET_ALLOWED_TYPES ({definition})")))?;
+            allowed_types.push(allowed_type);
+        }
+
+        Ok(AssetUploadService { allowed_types })
+    }
+}
+
+impl AssetUploadService {
+    /// Body limit of the upload route, the largest allowed file plus the multipart overhead.
+    pub fn max_request_size(&self) -> usize {
+        let largest_kb = self.allowed_types
+            .iter()
+            .map(|allowed_type| allowed_type.max_file_size_kb)
+            .max()
+            .unwrap_or_default();
+
+        largest_kb * 1024 + MULTIPART_OVERHEAD_BYTES
+    }
+
+    pub fn validate_asset(&self, detected_file_type: Option<DetectedFileType>, file_size: usize) -> Vec<ErrorMessage> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        let allowed_type = detected_file_type.and_then(|detected_file_type| {
+            self.allowed_types
+                .iter()
+                .find(|allowed_type| allowed_type.mime_type == detected_file_type.mime_type)
+        });
+
+        match allowed_type {
+            Some(allowed_type) if file_size > allowed_type.max_file_size_kb * 1024 => {
+                errors.push(ErrorMessage {
+                    key: String::from("file"),
+                    message: t!("asset_too_large", size = allowed_type.max_file_size_kb).to_string()
+                });
+            },
+            Some(_) => {},
+            None => {
+                errors.push(ErrorMessage {
+                    key: String::from("file"),
+                    message: t!("asset_type_not_allowed").to_string()
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+/// Detects the type from the content of the file. The file name is only used to tell apart
+/// types which share a signature, like the legacy office formats or csv and plain text.
+pub fn detect_file_type(file_name: &str, data: &[u8]) -> Option<DetectedFileType> {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+
+    let (mime_type, extension) = if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        ("image/jpeg", "jpg")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        ("image/png", "png")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        ("image/gif", "gif")
+    } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        ("image/webp", "webp")
+    } else if data.starts_with(b"%PDF-") {
+        ("application/pdf", "pdf")
+    } else if data.len() >= 12 && &data[4..8] == b"ftyp" {
+        match &data[8..12] {
+            b"qt  " => ("video/quicktime", "mov"),
+            _ => ("video/mp4", "mp4"),
+        }
+    } else if data.starts_with(b"PK\x03\x04") {
+        if contains(data, b"word/") {
+            ("application/vnd.openxmlformats-officedocument.wordprocessingml.document", "docx")
+        } else if contains(data, b"xl/") {
+            ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "xlsx")
+        } else if contains(data, b"ppt/") {
+            ("application/vnd.openxmlformats-officedocument.presentationml.presentation", "pptx")
+        } else {
+            ("application/zip", "zip")
+        }
+    } else if data.starts_with(&OLE_SIGNATURE) {
+        match extension.as_str() {
+            "doc" => ("application/msword", "doc"),
+            "xls" => ("application/vnd.ms-excel", "xls"),
+            "ppt" => ("application/vnd.ms-powerpoint", "ppt"),
+            _ => return None,
+        }
+    } else {
+        let text = std::str::from_utf8(data).ok().filter(|text| !text.contains('\0'))?;
+        if is_svg(text) {
+            ("image/svg+xml", "svg")
+        } else if extension == "csv" {
+            ("text/csv", "csv")
+        } else {
+            ("text/plain", "txt")
+        }
+    };
+
+    Some(DetectedFileType { mime_type, extension })
+}
+
+/// Removes script, event handlers and unsafe links from an svg, as it is served as a document.
+/// Svgs with a doctype are refused, entities can not be checked without expanding them.
+pub fn sanitize_svg(data: &[u8]) -> Option<Vec<u8>> {
+    let svg = std::str::from_utf8(data).ok()?;
+    if svg.to_lowercase().contains("<!doctype") || svg.to_lowercase().contains("<!entity") {
+        return None;
+    }
+
+    let mut svg = svg.to_string();
+    for element in SVG_UNSAFE_ELEMENTS {
+        let element_with_content = Regex::new(&format!(r"(?is)<{element}\b.*?</{element}\s*>")).ok()?;
+        let self_closing_element = Regex::new(&format!(r"(?is)<{element}\b[^>]*>")).ok()?;
+        svg = element_with_content.replace_all(&svg, "").to_string();
+        svg = self_closing_element.replace_all(&svg, "").to_string();
+    }
+
+    let event_handler = Regex::new(r#"(?i)\s+on[a-z]+\s*=\s*("[^"]*"|'[^']*'|[^\s>]+)"#).ok()?;
+    svg = event_handler.replace_all(&svg, "").to_string();
+
+    let link = Regex::new(r#"(?i)\s+((?:xlink:)?href)\s*=\s*("[^"]*"|'[^']*')"#).ok()?;
+    svg = link
+        .replace_all(&svg, |captures: &Captures| {
+            let value = captures[2].trim_matches(|c| c == '"' || c == '\'').trim();
+            if is_safe_link(value) {
+                captures[0].to_string()
+            } else {
+                String::new()
+            }
+        })
+        .to_string();
+
+    if !is_svg(&svg) {
+        return None;
+    }
+
+    Some(svg.into_bytes())
+}
+
+fn is_svg(text: &str) -> bool {
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let head: String = text.chars().take(1024).collect::<String>().to_lowercase();
+
+    text.starts_with('<') && head.contains("<svg")
+}
+
+/// Fragments, relative paths, web links and embedded raster images.
+fn is_safe_link(value: &str) -> bool {
+    let value = value.to_lowercase();
+    if value.contains('&') {
+        return false;
+    }
+
+    value.starts_with('#')
+        || value.starts_with("http://")
+        || value.starts_with("https://")
+        || ["png", "jpeg", "gif", "webp"].iter().any(|format| value.starts_with(&format!("data:image/{format};")))
+        || !value.contains(':')
+}
+
+fn contains(data: &[u8], needle: &[u8]) -> bool {
+    data.windows(needle.len()).any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_file_type, sanitize_svg};
+
+    #[test]
+    fn test_detect_file_type() {
+        // the content decides, the name only settles the ambiguous ones
+        assert_eq!(detect_file_type("photo.png", b"%PDF-1.7 ...").unwrap().mime_type, "application/pdf");
+        assert_eq!(detect_file_type("report.csv", b"name,email\n").unwrap().mime_type, "text/csv");
+        assert_eq!(detect_file_type("page.png", b"<html><body></body></html>").unwrap().mime_type, "text/plain");
+        assert_eq!(detect_file_type("logo", b"<?xml version=\"1.0\"?>\n<svg></svg>").unwrap().extension, "svg");
+        assert!(detect_file_type("binary.png", &[0x00, 0x01, 0xFE]).is_none());
+    }
+
+    #[test]
+    fn test_sanitize_svg() {
+        let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" onload="alert(1)">
+            <script>alert(2)</script>
+            <a href="javascript:alert(3)"><circle r="4" fill="red" /></a>
+            <use href="#shape" />
+        </svg>"##;
+        let sanitized = String::from_utf8(sanitize_svg(svg).unwrap()).unwrap();
+
+        assert!(!sanitized.contains("alert"));
+        assert!(sanitized.contains(r#"<circle r="4" fill="red" />"#));
+        assert!(sanitized.contains(r##"href="#shape""##));
+        assert!(sanitize_svg(br#"<!DOCTYPE svg [<!ENTITY x "y">]><svg>&x;</svg>"#).is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use image::imageops::FilterType;
+use image::ImageFormat;
+use rand::distributions::{Alphanumeric, DistString};
+use rust_i18n::t;
+use tracing::log::error;
+use crate::error::{Error, Result};
+use crate::models::validation_error::ErrorMessage;
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+use crate::providers::avored_storage_provider::AssetStorage;
+use crate::services::asset_upload_service::MULTIPART_OVERHEAD_BYTES;
+
+/// Every avatar is stored in these sizes as `{name}-{size}.png`, the stored url points at the
+/// largest one.
+pub const AVATAR_SIZES: [u32; 3] = [64, 128, 256];
+const AVATAR_DIRECTORY: &str = "avatars";
+const AVATAR_ALLOWED_FORMATS: [ImageFormat; 4] = [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::Gif, ImageFormat::WebP];
+
+pub struct AvatarService {
+    max_file_size_kb: usize,
+    storage: Arc<dyn AssetStorage>,
+}
+
+impl AvatarService {
+    pub fn new(config: &AvoRedConfigProvider, storage: Arc<dyn AssetStorage>) -> Result<Self> {
+        Ok(AvatarService {
+            max_file_size_kb: config.avatar_max_file_size_kb,
+            storage,
+        })
+    }
+}
+
+impl AvatarService {
+    /// Body limit of the routes which take an avatar.
+    pub fn max_request_size(&self) -> usize {
+        self.max_file_size_kb * 1024 + MULTIPART_OVERHEAD_BYTES
+    }
+
+    /// The type is taken from the content of the file, not from its name.
+    pub fn validate_avatar(&self, data: &[u8]) -> Vec<ErrorMessage> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+
+        if data.len() > self.max_file_size_kb * 1024 {
+            errors.push(ErrorMessage {
+                key: String::from("image"),
+                message: t!("avatar_too_large", size = self.max_file_size_kb).to_string()
+            });
+        }
+
+        let is_allowed_format = image::guess_format(data)
+            .map(|format| AVATAR_ALLOWED_FORMATS.contains(&format))
+            .unwrap_or(false);
+        if !is_allowed_format {
+            errors.push(ErrorMessage {
+                key: String::from("image"),
+                message: t!("avatar_invalid_type").to_string()
+            });
+        }
+
+        errors
+    }
+
+    /// Crops the image to a centred square, stores it in every size and returns the url.
+    pub async fn store_avatar(&self, data: Vec<u8>) -> Result<String> {
+        let file_stem = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+
+        let resized_avatars = tokio::task::spawn_blocking(move || resize_avatar(&data))
+            .await
+            .map_err(|_| Error::Generic(String::from("avatar processing was interrupted")))??;
+
+        for (size, png) in resized_avatars {
+            self.storage
+                .put(&format!("{AVATAR_DIRECTORY}/{file_stem}-{size}.png"), png, "image/png")
+                .await?;
+        }
+
+        let largest_size = AVATAR_SIZES[AVATAR_SIZES.len() - 1];
+
+        Ok(self.storage.url(&format!("{AVATAR_DIRECTORY}/{file_stem}-{largest_size}.png")))
+    }
+
+    /// Removes every size of an avatar stored by `store_avatar`. Anything else, like the generated
+    /// default avatar or a legacy upload, is left alone. Failures are only logged as the new avatar
+    /// is already saved by then.
+    pub async fn delete_avatar(&self, profile_image: &str) {
+        let largest_size = AVATAR_SIZES[AVATAR_SIZES.len() - 1];
+        let file_stem = profile_image
+            .strip_prefix(&self.storage.url(&format!("{AVATAR_DIRECTORY}/")))
+            .and_then(|file_name| file_name.strip_suffix(&format!("-{largest_size}.png")));
+        let file_stem = match file_stem {
+            Some(file_stem) if !file_stem.is_empty() && !file_stem.contains('/') => file_stem,
+            _ => return,
+        };
+
+        for size in AVATAR_SIZES {
+            let key = format!("{AVATAR_DIRECTORY}/{file_stem}-{size}.png");
+            if let Err(e) = self.storage.delete(&key).await {
+                error!("there is an issue while removing the previous avatar {key}: {e:?}");
+            }
+        }
+    }
+}
+
+fn resize_avatar(data: &[u8]) -> Result<Vec<(u32, Vec<u8>)>> {
+    let image = image::load_from_memory(data)?;
+    let side = image.width().min(image.height());
+    let square = image.crop_imm((image.width() - side) / 2, (image.height() - side) / 2, side, side);
+
+    let mut resized_avatars = Vec::new();
+    for size in AVATAR_SIZES {
+        let mut png = Cursor::new(Vec::new());
+        square
+            .resize_exact(size, size, FilterType::Lanczos3)
+            .write_to(&mut png, ImageFormat::Png)?;
+        resized_avatars.push((size, png.into_inner()));
+    }
+
+    Ok(resized_avatars)
+}
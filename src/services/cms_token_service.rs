@@ -0,0 +1,238 @@
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use surrealdb::sql::Datetime;
+use crate::error::{Error, Result};
+use crate::models::cms_token_model::{CmsTokenModel, CmsTokenScope, CreatableCmsTokenModel, IssuedCmsTokenModel};
+use crate::providers::avored_database_provider::DB;
+use crate::repositories::cms_token_repository::CmsTokenRepository;
+use crate::services::setting_service::SettingService;
+
+const CMS_TOKEN_PREFIX: &str = "avored_cms_";
+const LEGACY_CMS_TOKEN_SETTING: &str = "auth_cms_token";
+const CMS_TOKEN_LENGTH: usize = 40;
+const CMS_TOKEN_DISPLAY_PREFIX_LENGTH: usize = 16;
+/// Avoids a database write on every single cms request.
+const CMS_TOKEN_LAST_USED_RESOLUTION_IN_SECONDS: i64 = 60;
+
+pub struct CmsTokenService {
+    cms_token_repository: CmsTokenRepository
+}
+
+impl CmsTokenService {
+    pub fn new(cms_token_repository: CmsTokenRepository) -> Result<Self> {
+        Ok(CmsTokenService { cms_token_repository })
+    }
+}
+
+impl CmsTokenService {
+    pub async fn all(
+        &self,
+        (datastore, database_session): &DB
+    ) -> Result<Vec<CmsTokenModel>> {
+        self.cms_token_repository
+            .all(datastore, database_session)
+            .await
+    }
+
+    pub async fn create_cms_token(
+        &self,
+        (datastore, database_session): &DB,
+        name: String,
+        scopes: Vec<String>,
+        allowed_origins: Vec<String>,
+        expires_at: Option<Datetime>,
+        logged_in_username: String
+    ) -> Result<IssuedCmsTokenModel> {
+        let token = generate_cms_token();
+
+        let creatable_cms_token_model = CreatableCmsTokenModel {
+            name,
+            token_prefix: token_display_prefix(&token),
+            token_hash: hash_cms_token(&token),
+            scopes,
+            allowed_origins,
+            expires_at,
+            logged_in_username
+        };
+
+        let cms_token = self
+            .cms_token_repository
+            .create_cms_token(datastore, database_session, creatable_cms_token_model)
+            .await?;
+
+        Ok(IssuedCmsTokenModel { token, cms_token })
+    }
+
+    /// Older installs shared one plain token from the auth_cms_token setting. It becomes a
+    /// hashed cms token with every scope, so the frontends using it keep working, and the
+    /// setting is removed. The admin user who last saved the setting issues the token.
+    pub async fn migrate_legacy_setting_token(
+        &self,
+        db: &DB,
+        setting_service: &SettingService
+    ) -> Result<bool> {
+        let setting_model = match setting_service
+            .find_by_identifier(db, String::from(LEGACY_CMS_TOKEN_SETTING))
+            .await {
+            Ok(setting_model) => setting_model,
+            Err(_) => return Ok(false)
+        };
+
+        if !setting_model.value.trim().is_empty() {
+            let token = setting_model.value.trim().to_string();
+            let creatable_cms_token_model = CreatableCmsTokenModel {
+                name: String::from("Legacy CMS token"),
+                token_prefix: token_display_prefix(&token),
+                token_hash: hash_cms_token(&token),
+                scopes: CmsTokenScope::all().iter().map(|scope| scope.as_str().to_string()).collect(),
+                allowed_origins: vec![],
+                expires_at: None,
+                logged_in_username: setting_model.updated_by
+            };
+            let (datastore, database_session) = db;
+            self.cms_token_repository
+                .create_cms_token(datastore, database_session, creatable_cms_token_model)
+                .await?;
+        }
+
+        setting_service
+            .delete_by_identifier(db, LEGACY_CMS_TOKEN_SETTING)
+            .await
+    }
+
+    /// Replaces the secret of the token, the old one stops working straight away.
+    pub async fn rotate_cms_token(
+        &self,
+        (datastore, database_session): &DB,
+        cms_token_id: &str,
+        logged_in_username: String
+    ) -> Result<IssuedCmsTokenModel> {
+        let token = generate_cms_token();
+
+        let cms_token = self
+            .cms_token_repository
+            .update_token_hash(
+                datastore,
+                database_session,
+                cms_token_id,
+                token_display_prefix(&token),
+                hash_cms_token(&token),
+                logged_in_username
+            )
+            .await?;
+
+        Ok(IssuedCmsTokenModel { token, cms_token })
+    }
+
+    pub async fn revoke_cms_token(
+        &self,
+        (datastore, database_session): &DB,
+        cms_token_id: &str
+    ) -> Result<bool> {
+        self.cms_token_repository
+            .find_by_id(datastore, database_session, cms_token_id)
+            .await?;
+
+        self.cms_token_repository
+            .delete_by_id(datastore, database_session, cms_token_id)
+            .await
+    }
+
+    /// Returns Authentication for unknown or expired tokens and Forbidden when the token
+    /// is valid but is missing the scope or is used from a not allowed origin.
+    pub async fn authenticate(
+        &self,
+        (datastore, database_session): &DB,
+        token: &str,
+        origin: Option<&str>,
+        scope: CmsTokenScope
+    ) -> Result<CmsTokenModel> {
+        let cms_token_model = match self
+            .cms_token_repository
+            .find_by_token_hash(datastore, database_session, &hash_cms_token(token))
+            .await {
+            Ok(cms_token_model) => cms_token_model,
+            Err(Error::NotFound(_)) => return Err(Error::Authentication),
+            Err(e) => return Err(e)
+        };
+
+        if cms_token_model.is_expired() {
+            return Err(Error::Authentication);
+        }
+
+        if !cms_token_model.has_scope(scope) || !cms_token_model.is_origin_allowed(origin) {
+            return Err(Error::Forbidden);
+        }
+
+        let is_last_used_stale = match &cms_token_model.last_used_at {
+            Some(last_used_at) => (chrono::Utc::now() - last_used_at.0).num_seconds()
+                >= CMS_TOKEN_LAST_USED_RESOLUTION_IN_SECONDS,
+            None => true
+        };
+        if is_last_used_stale {
+            self.cms_token_repository
+                .update_last_used_at(datastore, database_session, &cms_token_model.id)
+                .await?;
+        }
+
+        Ok(cms_token_model)
+    }
+}
+
+fn generate_cms_token() -> String {
+    format!(
+        "{CMS_TOKEN_PREFIX}{}",
+        Alphanumeric.sample_string(&mut rand::thread_rng(), CMS_TOKEN_LENGTH)
+    )
+}
+
+fn token_display_prefix(token: &str) -> String {
+    token.chars().take(CMS_TOKEN_DISPLAY_PREFIX_LENGTH).collect()
+}
+
+fn hash_cms_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use tower::ServiceExt;
+    use crate::api::rest_api_routes::tests::{get_axum_app, send_get_request, setup_avored_db};
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_migrate_legacy_setting_token() -> Result<()>
+    {
+        let (app, state) = get_axum_app().await?;
+        setup_avored_db(app.clone()).await;
+
+        let (datastore, database_session) = &state.db;
+        let sql = "
+            CREATE settings CONTENT {
+                identifier: 'auth_cms_token',
+                value: 'legacy-shared-frontend-token',
+                created_by: 'admin@admin.com',
+                updated_by: 'admin@admin.com',
+                created_at: time::now(),
+                updated_at: time::now()
+            };";
+        datastore.execute(sql, database_session, None).await?;
+
+        assert!(state.cms_token_service.migrate_legacy_setting_token(&state.db, &state.setting_service).await?);
+        assert!(state.setting_service.find_by_identifier(&state.db, String::from("auth_cms_token")).await.is_err());
+
+        // the frontend keeps using the token it already had
+        let response = app
+            .oneshot(send_get_request("/cms/page", String::from("legacy-shared-frontend-token")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // there is nothing left to migrate on the next start
+        assert!(!state.cms_token_service.migrate_legacy_setting_token(&state.db, &state.setting_service).await?);
+
+        Ok(())
+    }
+}
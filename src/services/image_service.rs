@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use rust_i18n::t;
+use crate::error::{Error, Result};
+use crate::models::asset_model::{MetaDataType, NewAssetModel};
+use crate::models::image_rendition_model::{
+    FocalPointModel, ImageCropModel, ImageFit, ImageOutputFormat, ImagePresetModel, ImageRenditionModel
+};
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+use crate::providers::avored_storage_provider::AssetStorage;
+
+/// Renditions are kept outside the public folder, so they are only reachable through the
+/// image endpoint.
+const IMAGE_CACHE_DIRECTORY: &str = "cache/image";
+/// Raster types the image crate can decode here, svgs are served as they are.
+const RENDERABLE_TYPES: [&str; 4] = ["image/jpeg", "image/png", "image/gif", "image/webp"];
+
+pub struct ImageService {
+    presets: Vec<ImagePresetModel>,
+    max_dimension: u32,
+    size_step: u32,
+    cache_max_size: u64,
+    back_end_app_url: String,
+    storage: Arc<dyn AssetStorage>,
+}
+
+impl ImageService {
+    pub fn new(config: &AvoRedConfigProvider, storage: Arc<dyn AssetStorage>) -> Result<Self> {
+        let mut presets = vec![];
+        for definition in &config.image_presets {
+            let preset = ImagePresetModel::from_definition(definition)
+                .ok_or_else(|| Error::ConfigMissing(format!("AVORED_IMAGE_PRESETS ({definition})")))?;
+            presets.push(preset);
+        }
+
+        Ok(ImageService {
+            presets,
+            max_dimension: config.image_max_dimension,
+            size_step: config.image_size_step,
+            cache_max_size: config.image_cache_max_kb_per_asset * 1024,
+            back_end_app_url: config.back_end_app_url.trim_end_matches('/').to_string(),
+            storage,
+        })
+    }
+}
+
+impl ImageService {
+    pub fn max_dimension(&self) -> u32 {
+        self.max_dimension
+    }
+
+    pub fn size_step(&self) -> u32 {
+        self.size_step
+    }
+
+    pub fn find_preset(&self, name: &str) -> Option<&ImagePresetModel> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    pub fn is_image(&self, asset_model: &NewAssetModel) -> bool {
+        match &asset_model.metadata {
+            MetaDataType::FileTypeMetaData { file_type } => RENDERABLE_TYPES.contains(&file_type.as_str()),
+            _ => false,
+        }
+    }
+
+    /// The crop of the asset the rendition asks for, if the asset has one by that name.
+    pub fn find_crop<'a>(&self, asset_model: &'a NewAssetModel, rendition: &ImageRenditionModel) -> Option<&'a ImageCropModel> {
+        let name = rendition.crop.as_deref()?;
+
+        asset_model.crops.iter().find(|crop| crop.name == name)
+    }
+
+    /// Tells renditions apart which only differ in the crop or the focal point of the asset,
+    /// so editing them never serves a stale file.
+    pub fn rendition_key(&self, asset_model: &NewAssetModel, rendition: &ImageRenditionModel) -> String {
+        let mut key = rendition.cache_key();
+        if let Some(crop) = self.find_crop(asset_model, rendition) {
+            key.push_str(&format!("-crop-{}", crop.cache_key()));
+        }
+        if let (ImageFit::Cover, Some(focal_point)) = (rendition.fit, asset_model.focal_point) {
+            key.push_str(&format!("-focus-{}-{}", focal_point.x, focal_point.y));
+        }
+
+        key
+    }
+
+    /// Public urls of the named crops, keyed by name. Private images have none.
+    pub fn crop_urls(&self, asset_model: &NewAssetModel) -> BTreeMap<String, String> {
+        if !self.is_image(asset_model) || asset_model.is_private {
+            return BTreeMap::new();
+        }
+
+        asset_model.crops
+            .iter()
+            .map(|crop| (
+                crop.name.clone(),
+                format!("{}/public/image/{}?crop={}", self.back_end_app_url, asset_model.id, crop.name)
+            ))
+            .collect()
+    }
+
+    /// Returns the cached rendition, it is rendered and cached first when needed.
+    pub async fn rendition(
+        &self,
+        asset_model: &NewAssetModel,
+        rendition: &ImageRenditionModel
+    ) -> Result<(Vec<u8>, ImageOutputFormat)> {
+        let output_format = rendition.format.unwrap_or_else(|| {
+            ImageFormat::from_path(&asset_model.path)
+                .map(ImageOutputFormat::from_image_format)
+                .unwrap_or(ImageOutputFormat::Png)
+        });
+        let cache_directory = Path::new(IMAGE_CACHE_DIRECTORY).join(&asset_model.id);
+        let cache_path = cache_directory.join(format!("{}.{}", self.rendition_key(asset_model, rendition), output_format.as_str()));
+
+        if tokio::fs::try_exists(&cache_path).await? {
+            return Ok((tokio::fs::read(&cache_path).await?, output_format));
+        }
+        // the image endpoint is public, so the renditions of an asset can not grow without end
+        if directory_size(&cache_directory).await? >= self.cache_max_size {
+            return Err(Error::BadRequest(ErrorResponse {
+                status: false,
+                errors: vec![ErrorMessage {
+                    key: String::from("preset"),
+                    message: t!("image_cache_full").to_string()
+                }]
+            }));
+        }
+
+        let original = self.storage.get(&asset_model.path).await?;
+        let rendition = rendition.clone();
+        let crop = self.find_crop(asset_model, &rendition).cloned();
+        let focal_point = asset_model.focal_point.unwrap_or_default();
+        let rendered = tokio::task::spawn_blocking(move || render(&original, &rendition, crop, focal_point, output_format))
+            .await
+            .map_err(|_| Error::Generic(String::from("image rendering was interrupted")))??;
+
+        // written under a temporary name first, so a parallel request never reads half a file
+        tokio::fs::create_dir_all(&cache_directory).await?;
+        let temporary_path = temporary_path(&cache_path);
+        tokio::fs::write(&temporary_path, &rendered).await?;
+        tokio::fs::rename(&temporary_path, &cache_path).await?;
+
+        Ok((rendered, output_format))
+    }
+
+    /// Presets use the crop of the asset named like them, as they do when requested.
+    pub async fn generate_presets(&self, asset_model: &NewAssetModel) -> Result<()> {
+        for preset in &self.presets {
+            let rendition = ImageRenditionModel {
+                crop: Some(preset.name.clone()),
+                ..preset.rendition.clone()
+            };
+            self.rendition(asset_model, &rendition).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_renditions(&self, asset_id: &str) -> Result<()> {
+        let cache_directory = Path::new(IMAGE_CACHE_DIRECTORY).join(asset_id);
+        if tokio::fs::try_exists(&cache_directory).await? {
+            tokio::fs::remove_dir_all(cache_directory).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn render(
+    data: &[u8],
+    rendition: &ImageRenditionModel,
+    crop: Option<ImageCropModel>,
+    focal_point: FocalPointModel,
+    output_format: ImageOutputFormat
+) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(data)?;
+
+    // the focal point is kept where it is in the original, relative to the crop
+    let (image, focal_point) = match crop {
+        Some(crop) => {
+            let focal_point = FocalPointModel {
+                x: (focal_point.x * image.width() as f64 - crop.x as f64) / crop.width as f64,
+                y: (focal_point.y * image.height() as f64 - crop.y as f64) / crop.height as f64,
+            };
+            (image.crop_imm(crop.x, crop.y, crop.width, crop.height), focal_point)
+        },
+        None => (image, focal_point),
+    };
+
+    let image = match (rendition.fit, rendition.width, rendition.height) {
+        (_, None, None) => image,
+        (ImageFit::Cover, Some(width), Some(height)) => cover(&image, width, height, focal_point),
+        (ImageFit::Fill, width, height) => image.resize_exact(
+            width.unwrap_or(image.width()),
+            height.unwrap_or(image.height()),
+            FilterType::Lanczos3
+        ),
+        (_, width, height) => image.resize(
+            width.unwrap_or(u32::MAX),
+            height.unwrap_or(u32::MAX),
+            FilterType::Lanczos3
+        ),
+    };
+
+    // jpeg has no alpha channel and the webp encoder only takes 8 bit colours
+    let image = match output_format {
+        ImageOutputFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+        ImageOutputFormat::Webp => DynamicImage::ImageRgba8(image.to_rgba8()),
+        ImageOutputFormat::Png => image,
+    };
+
+    let mut output = Cursor::new(Vec::new());
+    image.write_to(&mut output, output_format.image_format())?;
+
+    Ok(output.into_inner())
+}
+
+/// Scales the image to fill the size and cuts off what is left over, as far from the focal
+/// point as possible.
+fn cover(image: &DynamicImage, width: u32, height: u32, focal_point: FocalPointModel) -> DynamicImage {
+    let scale = (width as f64 / image.width() as f64).max(height as f64 / image.height() as f64);
+    let window_width = ((width as f64 / scale).round() as u32).clamp(1, image.width());
+    let window_height = ((height as f64 / scale).round() as u32).clamp(1, image.height());
+
+    image
+        .crop_imm(
+            window_offset(image.width(), window_width, focal_point.x),
+            window_offset(image.height(), window_height, focal_point.y),
+            window_width,
+            window_height
+        )
+        .resize_exact(width, height, FilterType::Lanczos3)
+}
+
+/// Start of a window of the size which is centered on the focal point and stays in the image.
+fn window_offset(size: u32, window: u32, focal_point: f64) -> u32 {
+    let center = focal_point.clamp(0.0, 1.0) * size as f64;
+
+    (center - window as f64 / 2.0).round().clamp(0.0, (size - window) as f64) as u32
+}
+
+async fn directory_size(directory: &Path) -> Result<u64> {
+    if !tokio::fs::try_exists(directory).await? {
+        return Ok(0);
+    }
+
+    let mut size = 0;
+    let mut entries = tokio::fs::read_dir(directory).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        size += entry.metadata().await?.len();
+    }
+
+    Ok(size)
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}.tmp", rand::random::<u32>()));
+
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, Rgb, RgbImage};
+    use crate::models::image_rendition_model::FocalPointModel;
+    use super::{cover, window_offset};
+
+    #[test]
+    fn test_window_offset() {
+        assert_eq!(window_offset(300, 200, 0.5), 50);
+        assert_eq!(window_offset(300, 200, 0.0), 0);
+        assert_eq!(window_offset(300, 200, 1.0), 100);
+        assert_eq!(window_offset(300, 200, 0.6), 80);
+        assert_eq!(window_offset(200, 200, 0.9), 0);
+    }
+
+    #[test]
+    fn test_cover_keeps_the_focal_point() {
+        // the left half is red and the right half blue
+        let image = RgbImage::from_fn(300, 100, |x, _| match x < 150 {
+            true => Rgb([255, 0, 0]),
+            false => Rgb([0, 0, 255]),
+        });
+        let image = DynamicImage::ImageRgb8(image);
+
+        let left = cover(&image, 50, 50, FocalPointModel { x: 0.1, y: 0.5 }).to_rgb8();
+        assert_eq!((left.width(), left.height()), (50, 50));
+        assert_eq!(left.get_pixel(25, 25), &Rgb([255, 0, 0]));
+
+        let right = cover(&image, 50, 50, FocalPointModel { x: 0.9, y: 0.5 }).to_rgb8();
+        assert_eq!(right.get_pixel(25, 25), &Rgb([0, 0, 255]));
+    }
+}
@@ -0,0 +1,211 @@
+use std::net::SocketAddr;
+use axum::extract::ConnectInfo;
+use axum::http::HeaderMap;
+use lettre::{AsyncTransport, Message};
+use lettre::message::{header, MultiPart, SinglePart};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use crate::api::handlers::admin_user::unlock_account_api_handler::UnlockAccountViewModel;
+use crate::error::{Error, Result};
+use crate::models::admin_user_lockout_model::CreatableAdminUserLockoutModel;
+use crate::models::login_attempt_model::LoginAttemptAction;
+use crate::providers::avored_database_provider::DB;
+use crate::providers::avored_template_provider::AvoRedTemplateProvider;
+use crate::repositories::admin_user_lockout_repository::AdminUserLockoutRepository;
+use crate::repositories::login_attempt_repository::LoginAttemptRepository;
+
+pub struct LoginThrottleService {
+    login_attempt_repository: LoginAttemptRepository,
+    admin_user_lockout_repository: AdminUserLockoutRepository
+}
+
+impl LoginThrottleService {
+    pub fn new(
+        login_attempt_repository: LoginAttemptRepository,
+        admin_user_lockout_repository: AdminUserLockoutRepository
+    ) -> Result<Self> {
+        Ok(LoginThrottleService {
+            login_attempt_repository,
+            admin_user_lockout_repository
+        })
+    }
+}
+
+impl LoginThrottleService {
+    /// Returns the number of seconds the client has to wait when the key already used up
+    /// all attempts of the sliding window.
+    pub async fn too_many_attempts(
+        &self,
+        (datastore, database_session): &DB,
+        action: LoginAttemptAction,
+        key: &str,
+        max_attempts: i64,
+        window_seconds: i64
+    ) -> Result<Option<i64>> {
+        let attempt_count = self
+            .login_attempt_repository
+            .count_login_attempts(datastore, database_session, action, key, window_seconds)
+            .await?;
+
+        if attempt_count.total < max_attempts {
+            return Ok(None);
+        }
+
+        let oldest_attempt = self
+            .login_attempt_repository
+            .find_oldest_login_attempt(datastore, database_session, action, key, window_seconds)
+            .await?;
+        let elapsed_seconds = (chrono::Utc::now() - oldest_attempt.created_at.0).num_seconds();
+
+        Ok(Some((window_seconds - elapsed_seconds).max(1)))
+    }
+
+    pub async fn hit(
+        &self,
+        (datastore, database_session): &DB,
+        action: LoginAttemptAction,
+        key: &str,
+        window_seconds: i64
+    ) -> Result<bool> {
+        self.login_attempt_repository
+            .create_login_attempt(datastore, database_session, action, key, window_seconds)
+            .await
+    }
+
+    pub async fn clear(
+        &self,
+        (datastore, database_session): &DB,
+        action: LoginAttemptAction,
+        key: &str
+    ) -> Result<bool> {
+        self.login_attempt_repository
+            .delete_login_attempts(datastore, database_session, action, key)
+            .await
+    }
+
+    /// Returns the remaining lockout in seconds, if the account is currently locked.
+    pub async fn find_active_lockout(
+        &self,
+        (datastore, database_session): &DB,
+        email: &str
+    ) -> Result<Option<i64>> {
+        match self
+            .admin_user_lockout_repository
+            .find_active_by_email(datastore, database_session, email)
+            .await {
+            Ok(lockout_model) => {
+                let remaining_seconds = (lockout_model.locked_until.0 - chrono::Utc::now()).num_seconds();
+
+                Ok(Some(remaining_seconds.max(1)))
+            },
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e)
+        }
+    }
+
+    pub async fn lock_account(
+        &self,
+        (datastore, database_session): &DB,
+        template: &AvoRedTemplateProvider,
+        react_admin_url: &str,
+        email: String,
+        lockout_seconds: i64
+    ) -> Result<bool> {
+        let from_address = String::from("info@avored.com");
+        let email_subject = "Your account is locked";
+
+        let token = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+
+        let creatable_lockout_model = CreatableAdminUserLockoutModel {
+            email: email.clone(),
+            token: hash_unlock_token(&token),
+            lockout_seconds
+        };
+
+        self.admin_user_lockout_repository
+            .create_lockout(datastore, database_session, creatable_lockout_model)
+            .await?;
+
+        let link = format!(
+            "{react_admin_url}/admin/unlock-account/{token}?email={}",
+            urlencoding::encode(&email)
+        );
+        let data = UnlockAccountViewModel {
+            link,
+            minutes: (lockout_seconds + 59) / 60
+        };
+
+        let unlock_account_email_content = template.handlebars.render("unlock-account", &data)?;
+
+        let message = Message::builder()
+            .from(from_address.parse()?)
+            .to(email.parse()?)
+            .subject(email_subject)
+            .multipart(
+                MultiPart::alternative()
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(header::ContentType::TEXT_HTML)
+                            .body(unlock_account_email_content),
+                    ),
+            )?;
+
+        match template.mailer.send(message).await {
+            Ok(_) => Ok(true),
+            Err(_) => Err(Error::Generic(String::from("error while sending an email"))),
+        }
+    }
+
+    /// Removes the lockout and the failed login attempts of the email, so the admin user
+    /// can log in again straight away.
+    pub async fn unlock_account(
+        &self,
+        (datastore, database_session): &DB,
+        email: &str,
+        token: &str
+    ) -> Result<bool> {
+        let is_unlocked = self
+            .admin_user_lockout_repository
+            .delete_by_email_and_token(datastore, database_session, email, &hash_unlock_token(token))
+            .await?;
+
+        if is_unlocked {
+            self.login_attempt_repository
+                .delete_login_attempts(datastore, database_session, LoginAttemptAction::Login, email)
+                .await?;
+        }
+
+        Ok(is_unlocked)
+    }
+
+    /// The X-Forwarded-For header can be set by any client, so it is only used when the
+    /// app is configured to run behind a trusted proxy.
+    pub fn client_ip(
+        &self,
+        headers: &HeaderMap,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        trust_proxy_headers: bool
+    ) -> String {
+        if trust_proxy_headers {
+            let forwarded_for = headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty());
+
+            if let Some(ip) = forwarded_for {
+                return ip;
+            }
+        }
+
+        match connect_info {
+            Some(ConnectInfo(socket_addr)) => socket_addr.ip().to_string(),
+            None => String::from("unknown")
+        }
+    }
+}
+
+fn hash_unlock_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -6,3 +6,14 @@ pub mod asset_service;
 pub mod setting_service;
 pub mod model_service;
 pub mod cms_service;
+pub mod login_throttle_service;
+pub mod password_service;
+pub mod cms_token_service;
+pub mod personal_access_token_service;
+pub mod oidc_service;
+pub mod admin_user_invitation_service;
+pub mod avatar_service;
+pub mod image_service;
+pub mod asset_upload_service;
+pub mod asset_reference_service;
+pub mod asset_archive_service;
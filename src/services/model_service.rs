@@ -79,6 +79,15 @@ impl ModelService {
         })
     }
 
+    pub async fn all(
+        &self,
+        (datastore, database_session): &DB,
+    ) -> Result<Vec<ModelModel>> {
+        self.model_repository
+            .all(datastore, database_session)
+            .await
+    }
+
     pub async fn find_by_id(
         &self,
         (datastore, database_session): &DB,
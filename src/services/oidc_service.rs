@@ -0,0 +1,502 @@
+use std::sync::RwLock;
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use rand::distributions::{Alphanumeric, DistString};
+use rust_i18n::t;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tracing::log::error;
+use url::Url;
+use crate::error::{Error, Result};
+use crate::models::admin_user_identity_model::CreatableAdminUserIdentityModel;
+use crate::models::admin_user_model::{AdminUserModel, CreatableAdminUserModel};
+use crate::models::oidc_login_state_model::{CreatableOidcLoginStateModel, OidcIdentityModel};
+use crate::models::token_claim_model::LoggedInUser;
+use crate::models::validation_error::{ErrorMessage, ErrorResponse};
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+use crate::providers::avored_database_provider::DB;
+use crate::repositories::admin_user_identity_repository::AdminUserIdentityRepository;
+use crate::repositories::admin_user_repository::AdminUserRepository;
+use crate::repositories::oidc_login_state_repository::OidcLoginStateRepository;
+use crate::repositories::role_repository::RoleRepository;
+use crate::services::password_service::PasswordService;
+
+/// How long the admin user has to finish the login at the identity provider.
+const OIDC_LOGIN_STATE_EXPIRES_IN_SECONDS: i64 = 600;
+const OIDC_CODE_VERIFIER_LENGTH: usize = 64;
+const OIDC_HTTP_TIMEOUT_IN_SECONDS: u64 = 10;
+
+#[derive(Deserialize, Debug, Clone)]
+struct OidcDiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct OidcTokenResponse {
+    id_token: String,
+}
+
+struct Cached<T> {
+    value: T,
+    fetched_at: DateTime<Utc>,
+}
+
+pub struct OidcService {
+    http_client: reqwest::Client,
+    oidc_login_state_repository: OidcLoginStateRepository,
+    admin_user_identity_repository: AdminUserIdentityRepository,
+    admin_user_repository: AdminUserRepository,
+    role_repository: RoleRepository,
+    enabled: bool,
+    issuer_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_url: String,
+    scopes: Vec<String>,
+    groups_claim: String,
+    group_role_map: Vec<(String, String)>,
+    cache_seconds: i64,
+    discovery: RwLock<Option<Cached<OidcDiscoveryDocument>>>,
+    jwks: RwLock<Option<Cached<JwkSet>>>,
+}
+
+impl OidcService {
+    pub fn new(
+        config: &AvoRedConfigProvider,
+        oidc_login_state_repository: OidcLoginStateRepository,
+        admin_user_identity_repository: AdminUserIdentityRepository,
+        admin_user_repository: AdminUserRepository,
+        role_repository: RoleRepository,
+    ) -> Result<Self> {
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(OIDC_HTTP_TIMEOUT_IN_SECONDS))
+            .build()?;
+
+        Ok(OidcService {
+            http_client,
+            oidc_login_state_repository,
+            admin_user_identity_repository,
+            admin_user_repository,
+            role_repository,
+            enabled: config.oidc_enabled,
+            issuer_url: config.oidc_issuer_url.trim_end_matches('/').to_string(),
+            client_id: config.oidc_client_id.clone(),
+            client_secret: config.oidc_client_secret.clone(),
+            redirect_url: config.oidc_redirect_url.clone(),
+            scopes: config.oidc_scopes.clone(),
+            groups_claim: config.oidc_groups_claim.clone(),
+            group_role_map: config.oidc_group_role_map.clone(),
+            cache_seconds: config.oidc_jwks_cache_seconds,
+            discovery: RwLock::new(None),
+            jwks: RwLock::new(None),
+        })
+    }
+}
+
+impl OidcService {
+    fn ensure_enabled(&self) -> Result<()> {
+        if !self.enabled || self.issuer_url.is_empty() {
+            return Err(Error::NotFound(String::from("single sign on is not enabled")));
+        }
+
+        Ok(())
+    }
+
+    /// Stores a fresh state, nonce and pkce verifier and returns the url the admin user
+    /// has to be redirected to.
+    pub async fn authorization_url(
+        &self,
+        (datastore, database_session): &DB
+    ) -> Result<String> {
+        self.ensure_enabled()?;
+        let discovery = self.discovery().await?;
+
+        let creatable_login_state_model = CreatableOidcLoginStateModel {
+            state: Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            nonce: Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            code_verifier: Alphanumeric.sample_string(&mut rand::thread_rng(), OIDC_CODE_VERIFIER_LENGTH),
+            expires_in_seconds: OIDC_LOGIN_STATE_EXPIRES_IN_SECONDS,
+        };
+        let login_state_model = self
+            .oidc_login_state_repository
+            .create_login_state(datastore, database_session, creatable_login_state_model)
+            .await?;
+
+        let scope = self.scopes.join(" ");
+        let code_challenge = pkce_code_challenge(&login_state_model.code_verifier);
+        let authorization_url = Url::parse_with_params(
+            &discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", self.redirect_url.as_str()),
+                ("scope", scope.as_str()),
+                ("state", login_state_model.state.as_str()),
+                ("nonce", login_state_model.nonce.as_str()),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ]
+        )?;
+
+        Ok(authorization_url.to_string())
+    }
+
+    /// Exchanges the authorization code and returns the identity from the validated id token.
+    /// Unknown states, failed exchanges and invalid id tokens all end up as Authentication.
+    pub async fn authenticate(
+        &self,
+        (datastore, database_session): &DB,
+        code: &str,
+        state: &str
+    ) -> Result<OidcIdentityModel> {
+        self.ensure_enabled()?;
+
+        let login_state_model = match self
+            .oidc_login_state_repository
+            .take_by_state(datastore, database_session, state)
+            .await {
+            Ok(login_state_model) => login_state_model,
+            Err(Error::NotFound(_)) => return Err(Error::Authentication),
+            Err(e) => return Err(e)
+        };
+        if login_state_model.is_expired() {
+            return Err(Error::Authentication);
+        }
+
+        let discovery = self.discovery().await?;
+        let token_response = self
+            .http_client
+            .post(&discovery.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.redirect_url.as_str()),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("code_verifier", login_state_model.code_verifier.as_str()),
+            ])
+            .send()
+            .await?;
+        if !token_response.status().is_success() {
+            error!("the oidc token endpoint responded with {}", token_response.status());
+            return Err(Error::Authentication);
+        }
+        let token_response: OidcTokenResponse = token_response.json().await?;
+
+        self.validate_id_token(&discovery, &token_response.id_token, &login_state_model.nonce).await
+    }
+
+    /// Finds the admin user bound to the subject of the identity, or creates one on the
+    /// first login. An existing admin user with the same email is never linked on the fly,
+    /// it has to be linked with link_admin_user first. Roles mapped from the groups are
+    /// only ever added, roles assigned inside AvoRed are left alone.
+    pub async fn find_or_create_admin_user(
+        &self,
+        db: &DB,
+        password_service: &PasswordService,
+        identity: OidcIdentityModel
+    ) -> Result<AdminUserModel> {
+        let (datastore, database_session) = db;
+        let admin_user_model = match self
+            .admin_user_identity_repository
+            .find_by_issuer_and_subject(datastore, database_session, &identity.issuer, &identity.subject)
+            .await {
+            Ok(identity_model) => {
+                self.admin_user_repository
+                    .find_by_id(datastore, database_session, identity_model.admin_user_id)
+                    .await?
+            },
+            Err(Error::NotFound(_)) => {
+                let admin_user_model = self
+                    .create_by_identity(db, password_service, &identity)
+                    .await?;
+
+                let creatable_identity_model = CreatableAdminUserIdentityModel {
+                    admin_user_id: admin_user_model.id.clone(),
+                    issuer: identity.issuer.clone(),
+                    subject: identity.subject.clone(),
+                };
+                self.admin_user_identity_repository
+                    .create_identity(datastore, database_session, creatable_identity_model)
+                    .await?;
+
+                admin_user_model
+            },
+            Err(e) => return Err(e)
+        };
+
+        let mapped_role_identifiers: Vec<&String> = self
+            .group_role_map
+            .iter()
+            .filter(|(group, _)| identity.groups.contains(group))
+            .map(|(_, role_identifier)| role_identifier)
+            .collect();
+        let missing_roles: Vec<_> = self
+            .role_repository
+            .all(datastore, database_session)
+            .await?
+            .into_iter()
+            .filter(|role_model| mapped_role_identifiers.contains(&&role_model.identifier))
+            .filter(|role_model| !admin_user_model.roles.iter().any(|role| role.id == role_model.id))
+            .collect();
+
+        if missing_roles.is_empty() {
+            return Ok(admin_user_model);
+        }
+
+        let logged_in_user = LoggedInUser {
+            id: admin_user_model.id.clone(),
+            name: admin_user_model.full_name.clone(),
+            email: admin_user_model.email.clone(),
+            demo_data_status: false,
+            admin_user_model: admin_user_model.clone(),
+            token_permissions: None
+        };
+        for role_model in missing_roles {
+            self.admin_user_repository
+                .attach_admin_user_with_role(
+                    datastore,
+                    database_session,
+                    admin_user_model.id.clone(),
+                    role_model.id,
+                    logged_in_user.clone()
+                )
+                .await?;
+        }
+
+        self.admin_user_repository
+            .find_by_id(datastore, database_session, admin_user_model.id)
+            .await
+    }
+
+    /// Binds the identity to an admin user that is already signed in, so a local account
+    /// can use single sign on from then on.
+    pub async fn link_admin_user(
+        &self,
+        (datastore, database_session): &DB,
+        admin_user_id: &str,
+        identity: OidcIdentityModel
+    ) -> Result<bool> {
+        match self
+            .admin_user_identity_repository
+            .find_by_issuer_and_subject(datastore, database_session, &identity.issuer, &identity.subject)
+            .await {
+            Ok(_) => return Err(oidc_error("code", t!("oidc_identity_already_linked").to_string())),
+            Err(Error::NotFound(_)) => {},
+            Err(e) => return Err(e)
+        };
+
+        let creatable_identity_model = CreatableAdminUserIdentityModel {
+            admin_user_id: admin_user_id.to_string(),
+            issuer: identity.issuer,
+            subject: identity.subject,
+        };
+        self.admin_user_identity_repository
+            .create_identity(datastore, database_session, creatable_identity_model)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn create_by_identity(
+        &self,
+        (datastore, database_session): &DB,
+        password_service: &PasswordService,
+        identity: &OidcIdentityModel
+    ) -> Result<AdminUserModel> {
+        let email_count = self.admin_user_repository
+            .count_of_email(datastore, database_session, identity.email.clone())
+            .await?;
+        if email_count.total > 0 {
+            return Err(oidc_error("email", t!("oidc_account_not_linked").to_string()));
+        }
+
+        // single sign on users never get to know this password, they can set one with forgot password
+        let unusable_password = Alphanumeric.sample_string(&mut rand::thread_rng(), 64);
+        let creatable_admin_user_model = CreatableAdminUserModel {
+            full_name: identity.name.clone(),
+            email: identity.email.clone(),
+            password: password_service.hash_password(&unusable_password)?,
+            profile_image: String::from(""),
+            is_super_admin: false,
+            logged_in_username: identity.email.clone(),
+            role_ids: vec![]
+        };
+
+        self.admin_user_repository
+            .create_admin_user(datastore, database_session, creatable_admin_user_model)
+            .await
+    }
+
+    async fn validate_id_token(
+        &self,
+        discovery: &OidcDiscoveryDocument,
+        id_token: &str,
+        nonce: &str
+    ) -> Result<OidcIdentityModel> {
+        let header = decode_header(id_token).map_err(invalid_id_token)?;
+        // a symmetric algorithm would mean trusting a key we never got from the jwks
+        if matches!(header.alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+            return Err(Error::Authentication);
+        }
+
+        let jwk = self.find_jwk(&discovery.jwks_uri, header.kid.as_deref()).await?;
+        let decoding_key = DecodingKey::from_jwk(&jwk).map_err(invalid_id_token)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&discovery.issuer]);
+        validation.set_audience(&[&self.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = decode::<Map<String, Value>>(id_token, &decoding_key, &validation)
+            .map_err(invalid_id_token)?
+            .claims;
+
+        if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+            return Err(Error::Authentication);
+        }
+        // the email is used to name new admin users, so it has to be verified by the idp
+        if claims.get("email_verified").and_then(Value::as_bool) != Some(true) {
+            return Err(Error::Authentication);
+        }
+
+        let email = match claims.get("email").and_then(Value::as_str) {
+            Some(email) if !email.is_empty() => email.to_string(),
+            _ => return Err(Error::Authentication)
+        };
+        let name = claims
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(email.as_str())
+            .to_string();
+        let groups = match claims.get(&self.groups_claim) {
+            Some(Value::Array(groups)) => groups
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|group| group.to_string())
+                .collect(),
+            Some(Value::String(group)) => vec![group.to_string()],
+            _ => vec![]
+        };
+
+        Ok(OidcIdentityModel {
+            issuer: discovery.issuer.clone(),
+            subject: claims.get("sub").and_then(Value::as_str).unwrap_or_default().to_string(),
+            email,
+            name,
+            groups
+        })
+    }
+
+    async fn discovery(&self) -> Result<OidcDiscoveryDocument> {
+        if let Some(discovery) = read_cache(&self.discovery, self.cache_seconds) {
+            return Ok(discovery);
+        }
+
+        let discovery_url = format!("{}/.well-known/openid-configuration", self.issuer_url);
+        let discovery: OidcDiscoveryDocument = self
+            .http_client
+            .get(discovery_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if discovery.issuer.trim_end_matches('/') != self.issuer_url {
+            error!("the discovered oidc issuer {} does not match the configured issuer", discovery.issuer);
+            return Err(Error::ConfigMissing(String::from("AVORED_OIDC_ISSUER_URL")));
+        }
+
+        write_cache(&self.discovery, discovery.clone());
+
+        Ok(discovery)
+    }
+
+    /// Uses the cached key set, unless the key id is unknown, as that is what a key
+    /// rotation at the identity provider looks like.
+    async fn find_jwk(&self, jwks_uri: &str, kid: Option<&str>) -> Result<Jwk> {
+        if let Some(jwk) = read_cache(&self.jwks, self.cache_seconds)
+            .and_then(|jwks| select_jwk(&jwks, kid)) {
+            return Ok(jwk);
+        }
+
+        let jwks: JwkSet = self
+            .http_client
+            .get(jwks_uri)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let jwk = select_jwk(&jwks, kid);
+        write_cache(&self.jwks, jwks);
+
+        jwk.ok_or(Error::Authentication)
+    }
+}
+
+fn select_jwk(jwks: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+    match kid {
+        Some(kid) => jwks.find(kid).cloned(),
+        None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+        None => None
+    }
+}
+
+fn read_cache<T: Clone>(cache: &RwLock<Option<Cached<T>>>, cache_seconds: i64) -> Option<T> {
+    let cached = cache.read().ok()?;
+    cached
+        .as_ref()
+        .filter(|cached| (Utc::now() - cached.fetched_at).num_seconds() < cache_seconds)
+        .map(|cached| cached.value.clone())
+}
+
+fn write_cache<T>(cache: &RwLock<Option<Cached<T>>>, value: T) {
+    if let Ok(mut cached) = cache.write() {
+        *cached = Some(Cached { value, fetched_at: Utc::now() });
+    }
+}
+
+fn pkce_code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+fn invalid_id_token(e: jsonwebtoken::errors::Error) -> Error {
+    error!("the oidc id token is not valid: {e:?}");
+    Error::Authentication
+}
+
+fn oidc_error(key: &str, message: String) -> Error {
+    let error_response = ErrorResponse {
+        status: false,
+        errors: vec![ErrorMessage {
+            key: String::from(key),
+            message
+        }]
+    };
+
+    Error::BadRequest(error_response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pkce_code_challenge;
+
+    #[test]
+    fn test_pkce_code_challenge() {
+        // example from RFC 7636 appendix B
+        assert_eq!(
+            pkce_code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    error::Result,
+    error::{Error, Result},
     models::{page_model::PagePagination, Pagination},
     providers::avored_database_provider::DB,
     repositories::page_repository::PageRepository,
@@ -7,6 +7,11 @@ use crate::{
 };
 use crate::models::ModelCount;
 use crate::models::page_model::{NewCreatablePageModel, NewPageModel, NewUpdatablePageModel, PutPageIdentifierModel};
+use crate::models::permission_model::Permission;
+use crate::models::token_claim_model::LoggedInUser;
+
+/// Columns the page table can be ordered by, anything else falls back to the id.
+const PAGE_ORDER_COLUMNS: [&str; 6] = ["id", "name", "identifier", "status", "created_at", "updated_at"];
 
 pub struct PageService {
     page_repository: PageRepository,
@@ -22,18 +27,48 @@ impl PageService {
         &self,
         (datastore, database_session): &DB,
         current_page: i64,
-        order: String
+        order: String,
+        logged_in_user: &LoggedInUser
     ) -> Result<PagePagination> {
         let start = current_page * PER_PAGE;
         let to = start + PER_PAGE;
 
-        let admin_user_count = self
-            .page_repository
-            .get_total_count(datastore, database_session)
-            .await?;
+        // the order ends up in the query, so only known columns and directions are used
+        let (order_column, order_type) = order_by(&order).unwrap_or(("id", "ASC"));
+
+        // the per record rules can not be expressed in the query, so restricted admin
+        // users page through the pages they are allowed to see.
+        let (total, pages) = if logged_in_user.can_view_all_pages() {
+            let page_count = self
+                .page_repository
+                .get_total_count(datastore, database_session)
+                .await?;
+            let pages = self
+                .page_repository
+                .paginate(datastore, database_session, start, order_column.to_string(), order_type.to_string())
+                .await?;
+
+            (page_count.total, pages)
+        } else {
+            let pages: Vec<NewPageModel> = self
+                .page_repository
+                .all_ordered(datastore, database_session, order_column.to_string(), order_type.to_string())
+                .await?
+                .into_iter()
+                .filter(|page| logged_in_user.can_view_page(page))
+                .collect();
+            let total = pages.len() as i64;
+            let pages = pages
+                .into_iter()
+                .skip(start as usize)
+                .take(PER_PAGE as usize)
+                .collect();
+
+            (total, pages)
+        };
 
         let mut has_next_page = false;
-        if admin_user_count.total > to {
+        if total > to {
             has_next_page = true;
         };
         let mut has_previous_page = false;
@@ -42,7 +77,7 @@ impl PageService {
         };
 
         let pagination = Pagination {
-            total: admin_user_count.total,
+            total,
             per_page: PER_PAGE,
             current_page,
             from: (start + 1),
@@ -53,25 +88,24 @@ impl PageService {
             previous_page_number: (current_page - 1),
         };
 
-        let mut order_column = "id";
-        let mut order_type  = "ASC";
-        let mut parts = order.split(':');
-        if parts.clone().count() == 2 {
-            order_column = parts.clone().nth(0).unwrap_or("");
-            order_type = parts.nth(1).unwrap_or("");
-        }
-        let pages = self
-            .page_repository
-            .paginate(datastore, database_session, start, order_column.to_string(), order_type.to_string())
-            .await?;
-
         Ok(PagePagination {
             data: pages,
             pagination,
         })
     }
 
-    pub async fn remove_by_id(&self, (datastore, database_session): &DB, id: &String) -> Result<bool> {
+    pub async fn remove_by_id(
+        &self,
+        db: &DB,
+        id: &String,
+        logged_in_user: &LoggedInUser
+    ) -> Result<bool> {
+        let (datastore, database_session) = db;
+        let page_model = self.find_by_id(db, id.to_string()).await?;
+        if !logged_in_user.can_edit_page(&page_model) {
+            return Err(Error::Forbidden);
+        }
+
         self.page_repository.remove_by_id(datastore,database_session, id).await?;
         Ok(true)
     }
@@ -81,6 +115,12 @@ impl PageService {
         self.page_repository.all(datastore,database_session).await
     }
 
+    pub async fn all_accessible(&self, db: &DB, logged_in_user: &LoggedInUser) -> Result<Vec<NewPageModel>> {
+        let pages = self.all(db).await?;
+
+        Ok(pages.into_iter().filter(|page| logged_in_user.can_view_page(page)).collect())
+    }
+
     pub async fn find_by_id(
         &self,
         (datastore, database_session): &DB,
@@ -91,11 +131,34 @@ impl PageService {
             .await
     }
 
+    /// Page lookup for the admin api, it fails for pages outside the admin user roles.
+    pub async fn find_accessible_by_id(
+        &self,
+        db: &DB,
+        id: String,
+        logged_in_user: &LoggedInUser
+    ) -> Result<NewPageModel> {
+        let page_model = self.find_by_id(db, id).await?;
+        if !logged_in_user.can_view_page(&page_model) {
+            return Err(Error::Forbidden);
+        }
+
+        Ok(page_model)
+    }
+
     pub async fn new_create_page(
         &self,
         (datastore, database_session): &DB,
-        creatable_page_model: NewCreatablePageModel
+        creatable_page_model: NewCreatablePageModel,
+        logged_in_user: &LoggedInUser
     ) -> Result<NewPageModel> {
+        if !logged_in_user.can_use_page_identifier(&creatable_page_model.identifier) {
+            return Err(Error::Forbidden);
+        }
+        if !creatable_page_model.locked_role_ids.is_empty() && !logged_in_user.has_permission(Permission::PageLock) {
+            return Err(Error::Forbidden);
+        }
+
         self.page_repository
             .new_create_page(datastore, database_session, creatable_page_model)
             .await
@@ -103,9 +166,23 @@ impl PageService {
 
     pub async fn update_page(
         &self,
-        (datastore, database_session): &DB,
-        updatable_page_model: NewUpdatablePageModel
+        db: &DB,
+        updatable_page_model: NewUpdatablePageModel,
+        logged_in_user: &LoggedInUser
     ) -> Result<NewPageModel> {
+        let (datastore, database_session) = db;
+        let page_model = self.find_by_id(db, updatable_page_model.id.clone()).await?;
+        if !logged_in_user.can_edit_page(&page_model)
+            || !logged_in_user.can_use_page_identifier(&updatable_page_model.identifier)
+        {
+            return Err(Error::Forbidden);
+        }
+        if page_model.locked_role_ids != updatable_page_model.locked_role_ids
+            && !logged_in_user.has_permission(Permission::PageLock)
+        {
+            return Err(Error::Forbidden);
+        }
+
         self.page_repository
             .new_update_page(datastore, database_session, updatable_page_model)
             .await
@@ -123,11 +200,32 @@ impl PageService {
 
     pub async fn update_page_identifier(
         &self,
-        (datastore, database_session): &DB,
-        put_page_identifier_model: PutPageIdentifierModel
+        db: &DB,
+        put_page_identifier_model: PutPageIdentifierModel,
+        logged_in_user: &LoggedInUser
     ) -> Result<NewPageModel> {
+        let (datastore, database_session) = db;
+        let page_model = self.find_by_id(db, put_page_identifier_model.id.clone()).await?;
+        if !logged_in_user.can_edit_page(&page_model)
+            || !logged_in_user.can_use_page_identifier(&put_page_identifier_model.identifier)
+        {
+            return Err(Error::Forbidden);
+        }
+
         self.page_repository
             .update_page_identifier(datastore, database_session, put_page_identifier_model)
             .await
     }
 }
+
+fn order_by(order: &str) -> Option<(&str, &str)> {
+    let (column, order_type) = order.split_once(':')?;
+    let order_type = ["ASC", "DESC"]
+        .into_iter()
+        .find(|allowed| allowed.eq_ignore_ascii_case(order_type))?;
+
+    PAGE_ORDER_COLUMNS
+        .into_iter()
+        .find(|allowed| *allowed == column)
+        .map(|column| (column, order_type))
+}
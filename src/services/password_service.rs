@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, Version};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::SaltString;
+use rust_i18n::t;
+use crate::error::Result;
+use crate::models::validation_error::ErrorMessage;
+use crate::providers::avored_config_provider::AvoRedConfigProvider;
+
+pub struct PasswordService {
+    min_length: usize,
+    require_uppercase: bool,
+    require_lowercase: bool,
+    require_number: bool,
+    require_symbol: bool,
+    breached_passwords: HashSet<String>,
+    argon2_params: Params,
+    legacy_password_salt: Option<String>
+}
+
+impl PasswordService {
+    pub fn new(config: &AvoRedConfigProvider) -> Result<Self> {
+        let breached_list = std::fs::read_to_string(&config.password_breached_list_path)?;
+        let breached_passwords = breached_list
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.to_lowercase())
+            .collect();
+
+        let argon2_params = Params::new(
+            config.argon2_memory_kib,
+            config.argon2_iterations,
+            config.argon2_parallelism,
+            None
+        )?;
+
+        Ok(PasswordService {
+            min_length: config.password_min_length,
+            require_uppercase: config.password_require_uppercase,
+            require_lowercase: config.password_require_lowercase,
+            require_number: config.password_require_number,
+            require_symbol: config.password_require_symbol,
+            breached_passwords,
+            argon2_params,
+            legacy_password_salt: config.password_salt.clone()
+        })
+    }
+}
+
+impl PasswordService {
+    /// Checks the password against the configured policy. The email and full name are the
+    /// ones of the admin user the password is for.
+    pub fn validate_password(
+        &self,
+        password: &str,
+        email: &str,
+        full_name: &str
+    ) -> Vec<ErrorMessage> {
+        let mut errors: Vec<ErrorMessage> = vec![];
+        let mut push_error = |message: String| errors.push(ErrorMessage {
+            key: String::from("password"),
+            message
+        });
+
+        if password.chars().count() < self.min_length {
+            push_error(t!("password_min_length", length = self.min_length).to_string());
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            push_error(t!("password_requires_uppercase").to_string());
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            push_error(t!("password_requires_lowercase").to_string());
+        }
+        if self.require_number && !password.chars().any(|c| c.is_ascii_digit()) {
+            push_error(t!("password_requires_number").to_string());
+        }
+        if self.require_symbol && password.chars().all(|c| c.is_alphanumeric()) {
+            push_error(t!("password_requires_symbol").to_string());
+        }
+
+        let lower_password = password.to_lowercase();
+        let email_local_part = email.split('@').next().unwrap_or_default();
+        let mut personal_words = std::iter::once(email)
+            .chain(std::iter::once(email_local_part))
+            .chain(full_name.split_whitespace())
+            .map(|word| word.trim().to_lowercase())
+            // very short name parts would reject far too many passwords
+            .filter(|word| word.chars().count() >= 3);
+        if personal_words.any(|word| lower_password.contains(&word)) {
+            push_error(t!("password_contains_personal_info").to_string());
+        }
+
+        if self.breached_passwords.contains(&lower_password) {
+            push_error(t!("password_breached").to_string());
+        }
+
+        errors
+    }
+
+    /// Every hash gets its own random salt.
+    pub fn hash_password(&self, raw_password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        let password_hash = self
+            .argon2()
+            .hash_password(raw_password.as_bytes(), &salt)?
+            .to_string();
+
+        Ok(password_hash)
+    }
+
+    /// A hash needs to be rehashed when it was made with other argon2 params than the
+    /// configured ones, or with the old shared salt.
+    pub fn needs_rehash(&self, password_hash: &str) -> bool {
+        let parsed_hash = match PasswordHash::new(password_hash) {
+            Ok(parsed_hash) => parsed_hash,
+            Err(_) => return true
+        };
+
+        if parsed_hash.algorithm != argon2::ARGON2ID_IDENT
+            || parsed_hash.version != Some(Version::V0x13.into()) {
+            return true;
+        }
+
+        let is_same_params = match Params::try_from(&parsed_hash) {
+            Ok(params) => params.m_cost() == self.argon2_params.m_cost()
+                && params.t_cost() == self.argon2_params.t_cost()
+                && params.p_cost() == self.argon2_params.p_cost(),
+            Err(_) => false
+        };
+        if !is_same_params {
+            return true;
+        }
+
+        match (&self.legacy_password_salt, parsed_hash.salt) {
+            (Some(legacy_salt), Some(salt)) => salt.as_str() == legacy_salt,
+            _ => false
+        }
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.argon2_params.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use argon2::{Argon2, PasswordHasher};
+    use argon2::password_hash::SaltString;
+    use crate::api::rest_api_routes::tests::get_axum_app;
+    use crate::error::Result;
+
+    #[tokio::test]
+    async fn test_password_service() -> Result<()>
+    {
+        let (_app, state) = get_axum_app().await?;
+        let password_service = &state.password_service;
+
+        assert!(password_service.validate_password("Tr0ub4dor&Horse", "admin@admin.com", "Admin").is_empty());
+        assert_eq!(password_service.validate_password("short", "admin@admin.com", "Admin").len(), 3);
+        assert!(!password_service.validate_password("Password123!", "admin@admin.com", "Admin").is_empty());
+        assert!(!password_service.validate_password("JohnSmith2024", "admin@admin.com", "John Smith").is_empty());
+
+        let password_hash = password_service.hash_password("Tr0ub4dor&Horse")?;
+        assert!(!password_service.needs_rehash(&password_hash));
+        assert_ne!(password_hash, password_service.hash_password("Tr0ub4dor&Horse")?);
+
+        let legacy_salt = SaltString::from_b64(state.config.password_salt.as_ref().unwrap())?;
+        let legacy_hash = Argon2::default()
+            .hash_password(b"Tr0ub4dor&Horse", &legacy_salt)?
+            .to_string();
+        assert!(password_service.needs_rehash(&legacy_hash));
+
+        Ok(())
+    }
+}
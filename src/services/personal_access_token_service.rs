@@ -0,0 +1,119 @@
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use surrealdb::sql::Datetime;
+use crate::error::{Error, Result};
+use crate::models::personal_access_token_model::{CreatablePersonalAccessTokenModel, IssuedPersonalAccessTokenModel, PersonalAccessTokenModel};
+use crate::providers::avored_database_provider::DB;
+use crate::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+
+/// Tells personal access tokens apart from login jwt tokens in the authorization header.
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "avored_pat_";
+const PERSONAL_ACCESS_TOKEN_LENGTH: usize = 40;
+const PERSONAL_ACCESS_TOKEN_DISPLAY_PREFIX_LENGTH: usize = 16;
+const PERSONAL_ACCESS_TOKEN_LAST_USED_RESOLUTION_IN_SECONDS: i64 = 60;
+
+pub struct PersonalAccessTokenService {
+    personal_access_token_repository: PersonalAccessTokenRepository
+}
+
+impl PersonalAccessTokenService {
+    pub fn new(personal_access_token_repository: PersonalAccessTokenRepository) -> Result<Self> {
+        Ok(PersonalAccessTokenService { personal_access_token_repository })
+    }
+}
+
+impl PersonalAccessTokenService {
+    pub async fn all_by_admin_user_id(
+        &self,
+        (datastore, database_session): &DB,
+        admin_user_id: &str
+    ) -> Result<Vec<PersonalAccessTokenModel>> {
+        self.personal_access_token_repository
+            .all_by_admin_user_id(datastore, database_session, admin_user_id)
+            .await
+    }
+
+    pub async fn create_personal_access_token(
+        &self,
+        (datastore, database_session): &DB,
+        admin_user_id: String,
+        name: String,
+        permissions: Vec<String>,
+        expires_at: Datetime
+    ) -> Result<IssuedPersonalAccessTokenModel> {
+        let token = format!(
+            "{PERSONAL_ACCESS_TOKEN_PREFIX}{}",
+            Alphanumeric.sample_string(&mut rand::thread_rng(), PERSONAL_ACCESS_TOKEN_LENGTH)
+        );
+
+        let creatable_personal_access_token_model = CreatablePersonalAccessTokenModel {
+            admin_user_id,
+            name,
+            token_prefix: token.chars().take(PERSONAL_ACCESS_TOKEN_DISPLAY_PREFIX_LENGTH).collect(),
+            token_hash: hash_personal_access_token(&token),
+            permissions,
+            expires_at
+        };
+
+        let personal_access_token = self
+            .personal_access_token_repository
+            .create_personal_access_token(datastore, database_session, creatable_personal_access_token_model)
+            .await?;
+
+        Ok(IssuedPersonalAccessTokenModel { token, personal_access_token })
+    }
+
+    pub async fn revoke_personal_access_token(
+        &self,
+        (datastore, database_session): &DB,
+        personal_access_token_id: &str,
+        admin_user_id: &str
+    ) -> Result<bool> {
+        let is_deleted = self
+            .personal_access_token_repository
+            .delete_by_id_and_admin_user_id(datastore, database_session, personal_access_token_id, admin_user_id)
+            .await?;
+
+        if !is_deleted {
+            return Err(Error::NotFound(format!("no personal access token found with id {personal_access_token_id}")));
+        }
+
+        Ok(is_deleted)
+    }
+
+    pub async fn authenticate(
+        &self,
+        (datastore, database_session): &DB,
+        token: &str
+    ) -> Result<PersonalAccessTokenModel> {
+        let personal_access_token_model = match self
+            .personal_access_token_repository
+            .find_by_token_hash(datastore, database_session, &hash_personal_access_token(token))
+            .await {
+            Ok(personal_access_token_model) => personal_access_token_model,
+            Err(Error::NotFound(_)) => return Err(Error::Authentication),
+            Err(e) => return Err(e)
+        };
+
+        if personal_access_token_model.is_expired() {
+            return Err(Error::Authentication);
+        }
+
+        let is_last_used_stale = match &personal_access_token_model.last_used_at {
+            Some(last_used_at) => (chrono::Utc::now() - last_used_at.0).num_seconds()
+                >= PERSONAL_ACCESS_TOKEN_LAST_USED_RESOLUTION_IN_SECONDS,
+            None => true
+        };
+        if is_last_used_stale {
+            self.personal_access_token_repository
+                .update_last_used_at(datastore, database_session, &personal_access_token_model.id)
+                .await?;
+        }
+
+        Ok(personal_access_token_model)
+    }
+}
+
+fn hash_personal_access_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,5 +1,6 @@
+use tracing::log::error;
 use crate::{
-    error::Result,
+    error::{Error, Result},
     models::{
         role_model::{CreatableRole, RoleModel, RolePagination, UpdatableRoleModel},
         Pagination,
@@ -9,6 +10,7 @@ use crate::{
     PER_PAGE,
 };
 use crate::models::ModelCount;
+use crate::models::permission_model::Permission;
 use crate::models::role_model::{PutRoleIdentifierModel, RoleOptionModel};
 
 pub struct RoleService {
@@ -142,4 +144,55 @@ impl RoleService {
             .update_role(datastore, database_session, updatable_role_model)
             .await
     }
+
+    /// Runs on startup. Permissions saved with a legacy identifier are rewritten to the
+    /// registry identifier, any other unknown permission stops the app from starting.
+    pub async fn validate_role_permissions(
+        &self,
+        (datastore, database_session): &DB,
+    ) -> Result<()> {
+        let roles = self.role_repository.all(datastore, database_session).await?;
+        let mut unknown_permissions: Vec<String> = vec![];
+
+        for role in roles {
+            let mut permissions: Vec<String> = vec![];
+            let mut has_legacy_permission = false;
+
+            for permission in &role.permissions {
+                let registered_permission = match Permission::from_identifier(permission) {
+                    Some(registered_permission) => registered_permission,
+                    None => match Permission::from_legacy_identifier(permission) {
+                        Some(registered_permission) => {
+                            has_legacy_permission = true;
+                            registered_permission
+                        },
+                        None => {
+                            unknown_permissions.push(format!("{}: {permission}", role.identifier));
+                            continue;
+                        }
+                    }
+                };
+                let identifier = registered_permission.as_str().to_string();
+                if !permissions.contains(&identifier) {
+                    permissions.push(identifier);
+                }
+            }
+
+            if has_legacy_permission {
+                self.role_repository
+                    .update_role_permissions(datastore, database_session, role.id, permissions)
+                    .await?;
+            }
+        }
+
+        if !unknown_permissions.is_empty() {
+            error!("roles have unknown permissions: {}", unknown_permissions.join(", "));
+            return Err(Error::Generic(format!(
+                "unknown role permissions: {}",
+                unknown_permissions.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
 }
@@ -47,6 +47,15 @@ impl SettingService {
             .update_setting(datastore, database_session, updatable_setting)
             .await
     }
+    pub async fn delete_by_identifier(
+        &self,
+        (datastore, database_session): &DB,
+        identifier: &str
+    ) -> Result<bool> {
+        self.setting_repository
+            .delete_by_identifier(datastore, database_session, identifier)
+            .await
+    }
 }
 impl SettingService {
 }